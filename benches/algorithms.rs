@@ -28,6 +28,7 @@ criterion_group!(
     recursive_division::generate_100_x_100,
     sidewinder::generate_10_x_10,
     sidewinder::generate_100_x_100,
+    validation::validate_1000_x_1000,
 );
 criterion_main!(benches);
 
@@ -425,3 +426,20 @@ mod sidewinder {
         });
     }
 }
+
+mod validation {
+    use super::{Criterion, OrthogonalMazeBuilder, RecursiveBacktracking};
+
+    pub fn validate_1000_x_1000(c: &mut Criterion) {
+        let maze = OrthogonalMazeBuilder::new()
+            .height(1000)
+            .width(1000)
+            .algorithm(Box::new(RecursiveBacktracking))
+            .build()
+            .unwrap();
+
+        c.bench_function("validation/validate_1000_x_1000", |b| {
+            b.iter(|| assert!(maze.is_valid()));
+        });
+    }
+}
@@ -0,0 +1,35 @@
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(
+    benches,
+    batch::generate_256_serial,
+    batch::generate_256_parallel
+);
+criterion_main!(benches);
+
+mod batch {
+    use bevy_knossos::maze::{OrthogonalMazeBuilder, generate_batch};
+    use criterion::Criterion;
+
+    fn builder() -> OrthogonalMazeBuilder {
+        OrthogonalMazeBuilder::new().width(30).height(30).seed(1)
+    }
+
+    pub fn generate_256_serial(c: &mut Criterion) {
+        let builder = builder();
+        c.bench_function("batch/generate_256_serial", |b| {
+            b.iter(|| {
+                (0..256)
+                    .map(|seed| builder.clone().seed(seed).build().unwrap())
+                    .collect::<Vec<_>>()
+            });
+        });
+    }
+
+    pub fn generate_256_parallel(c: &mut Criterion) {
+        let builder = builder();
+        c.bench_function("batch/generate_256_parallel", |b| {
+            b.iter(|| generate_batch(256, &builder).unwrap());
+        });
+    }
+}
@@ -0,0 +1,114 @@
+//! Compares the memory of mazes with and without the `compact` feature
+//!
+//! Run `cargo bench --bench memory` and `cargo bench --bench memory --features compact` to get the
+//! peak heap usage before and after packing the cells, printed next to the timings.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(
+    benches,
+    memory::new_5000_x_5000,
+    memory::generate_1000_x_1000
+);
+criterion_main!(benches);
+
+/// An allocator keeping track of the current and the peak number of allocated bytes
+struct Counting;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            Self::grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+impl Counting {
+    fn grow(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    /// Returns the peak number of bytes allocated while running `f` on top of the memory already
+    /// in use
+    fn peak_of<T>(f: impl FnOnce() -> T) -> usize {
+        let baseline = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+        drop(f());
+        PEAK.load(Ordering::Relaxed) - baseline
+    }
+}
+
+mod memory {
+    use super::Counting;
+    use bevy_knossos::maze::{OrthogonalMaze, OrthogonalMazeBuilder, RecursiveBacktracking};
+    use criterion::Criterion;
+
+    const STORAGE: &str = if cfg!(feature = "compact") {
+        "packed"
+    } else {
+        "full"
+    };
+
+    pub fn new_5000_x_5000(c: &mut Criterion) {
+        let peak = Counting::peak_of(|| OrthogonalMaze::new(5000, 5000));
+        println!("memory/new_5000_x_5000 ({STORAGE} cells): peak heap {peak} bytes");
+
+        let mut group = c.benchmark_group("memory");
+        group.sample_size(10);
+        group.bench_function("new_5000_x_5000", |b| {
+            b.iter(|| OrthogonalMaze::new(5000, 5000));
+        });
+        group.finish();
+    }
+
+    pub fn generate_1000_x_1000(c: &mut Criterion) {
+        let builder = OrthogonalMazeBuilder::new()
+            .width(1000)
+            .height(1000)
+            .algorithm(Box::new(RecursiveBacktracking))
+            .seed(1);
+        let peak = Counting::peak_of(|| builder.build().unwrap());
+        println!("memory/generate_1000_x_1000 ({STORAGE} cells): peak heap {peak} bytes");
+
+        let mut group = c.benchmark_group("memory");
+        group.sample_size(10);
+        group.bench_function("generate_1000_x_1000", |b| {
+            b.iter(|| builder.build().unwrap());
+        });
+        group.finish();
+    }
+}
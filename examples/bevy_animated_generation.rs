@@ -0,0 +1,99 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_knossos::{
+    Coords, CoordsComponent, KnossosPlugin,
+    maze::{CarveSteps, Cell, OrthogonalMazeBuilder, Prim},
+};
+
+const WIDTH: usize = 12;
+const HEIGHT: usize = 10;
+/// Distance between the centers of two neighboring tiles in pixels
+const TILE_SPACING: f32 = 45.;
+
+fn main() {
+    let steps = OrthogonalMazeBuilder::new()
+        .algorithm(Box::new(Prim::new()))
+        .seed(0)
+        .width(WIDTH)
+        .height(HEIGHT)
+        .build_steps()
+        .unwrap();
+
+    App::new()
+        .insert_resource(Generation(steps))
+        .insert_resource(StepTimer(Timer::from_seconds(0.05, TimerMode::Repeating)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins(KnossosPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, carve_next_passage)
+        .run();
+}
+
+/// Passages of the maze that are still to be carved
+#[derive(Resource)]
+struct Generation(CarveSteps);
+
+#[derive(Resource)]
+struct StepTimer(Timer);
+
+/// Tile entities by the coords of their cells
+#[derive(Resource, Default)]
+struct Tiles(HashMap<Coords, Entity>);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((Camera2d, Name::new("Camera")));
+
+    // The maze starts with all the walls in place
+    let mut tiles = Tiles::default();
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let entity = commands
+                .spawn((
+                    CoordsComponent::from((x, y)),
+                    Cell::empty(),
+                    Sprite::from_image(asset_server.load(tile_image(Cell::empty()))),
+                    tile_transform((x, y)),
+                ))
+                .id();
+            tiles.0.insert((x, y), entity);
+        }
+    }
+    commands.insert_resource(tiles);
+}
+
+/// Carves a single passage on every tick of the timer and updates the tiles on both of its sides
+fn carve_next_passage(
+    time: Res<Time>,
+    mut timer: ResMut<StepTimer>,
+    mut generation: ResMut<Generation>,
+    tiles: Res<Tiles>,
+    asset_server: Res<AssetServer>,
+    mut cells: Query<(&mut Cell, &mut Sprite)>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Some(step) = generation.0.next() else {
+        return;
+    };
+
+    for coords in [step.from, step.to] {
+        let cell = generation.0.maze()[coords];
+        if let Ok((mut tile, mut sprite)) = cells.get_mut(tiles.0[&coords]) {
+            *tile = cell;
+            sprite.image = asset_server.load(tile_image(cell));
+        }
+    }
+}
+
+fn tile_image(cell: Cell) -> String {
+    format!("tile_{}.png", cell.to_bits_str())
+}
+
+fn tile_transform((x, y): Coords) -> Transform {
+    Transform::from_xyz(
+        (x as f32 - WIDTH as f32 / 2.) * TILE_SPACING,
+        (HEIGHT as f32 / 2. - y as f32) * TILE_SPACING,
+        0.,
+    )
+    .with_scale(Vec3::from_slice(&[5., 5., 0.1]))
+}
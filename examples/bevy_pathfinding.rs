@@ -4,6 +4,7 @@ use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
 use bevy_knossos::{
     CellSize, CoordsComponent, Goal, KnossosPlugin, Start,
     maze::{self, Cell},
+    neighbor,
     pathfind::MazePath,
 };
 
@@ -115,7 +116,7 @@ fn setup(mut commands: Commands, maze: Res<maze::OrthogonalMaze>, asset_server:
     });
 }
 
-// given a corner direction like 1,1,
+// given a corner as a pair of horizontal and vertical directions,
 // move on each axis and check the direction of the opposite
 // axis. (ex: move on x, then check the y direction) to see
 // if there's a wall, which is then used to determine if there
@@ -123,32 +124,16 @@ fn setup(mut commands: Commands, maze: Res<maze::OrthogonalMaze>, asset_server:
 // ex: a left wall with a top-right corner block.
 fn check_corner(
     pos: (usize, usize),
-    corner: IVec2,
+    (horizontal, vertical): (Cell, Cell),
     cache: &HashMap<(usize, usize), &maze::Cell>,
 ) -> bool {
-    pos.0
-        .checked_add_signed(corner.x as isize)
-        .and_then(|new_x| cache.get(&(new_x, pos.1)))
-        .map(|cell| {
-            !cell.contains(match corner.y {
-                1 => Cell::SOUTH,
-                -1 => Cell::NORTH,
-                _ => unreachable!(""),
-            })
-        })
-        .is_some_and(|exists| exists)
-        || pos
-            .1
-            .checked_add_signed(corner.y as isize)
-            .and_then(|v| cache.get(&(pos.0, v)))
-            .map(|cell| {
-                !cell.contains(match corner.x {
-                    1 => Cell::EAST,
-                    -1 => Cell::WEST,
-                    _ => unreachable!(""),
-                })
-            })
-            .is_some_and(|v| v)
+    let has_wall = |direction: Cell, wall: Cell| {
+        neighbor(pos, direction)
+            .and_then(|next| cache.get(&next))
+            .is_some_and(|cell| !cell.contains(wall))
+    };
+
+    has_wall(horizontal, vertical) || has_wall(vertical, horizontal)
 }
 
 fn cell_to_index(
@@ -165,16 +150,16 @@ fn cell_to_index(
             0b0011 => 309,
             0b0100 => 313,
             0b0101 => {
-                let has_ne_corner = check_corner(position, IVec2::new(1, -1), cache);
+                let has_ne_corner = check_corner(position, (Cell::EAST, Cell::NORTH), cache);
                 if has_ne_corner { 307 } else { 314 }
             }
             0b0110 => {
-                let has_se_corner = check_corner(position, IVec2::new(1, 1), cache);
+                let has_se_corner = check_corner(position, (Cell::EAST, Cell::SOUTH), cache);
                 if has_se_corner { 280 } else { 287 }
             }
             0b0111 => {
-                let has_ne_corner = check_corner(position, IVec2::new(1, -1), cache);
-                let has_se_corner = check_corner(position, IVec2::new(1, 1), cache);
+                let has_ne_corner = check_corner(position, (Cell::EAST, Cell::NORTH), cache);
+                let has_se_corner = check_corner(position, (Cell::EAST, Cell::SOUTH), cache);
                 match (has_ne_corner, has_se_corner) {
                     (true, true) => 310,
                     (true, false) => 390,
@@ -184,16 +169,16 @@ fn cell_to_index(
             }
             0b1000 => 285,
             0b1001 => {
-                let has_nw_corner = check_corner(position, IVec2::new(-1, -1), cache);
+                let has_nw_corner = check_corner(position, (Cell::WEST, Cell::NORTH), cache);
                 if has_nw_corner { 308 } else { 315 }
             }
             0b1010 => {
-                let has_sw_corner = check_corner(position, IVec2::new(-1, 1), cache);
+                let has_sw_corner = check_corner(position, (Cell::WEST, Cell::SOUTH), cache);
                 if has_sw_corner { 281 } else { 288 }
             }
             0b1011 => {
-                let has_nw_corner = check_corner(position, IVec2::new(-1, -1), cache);
-                let has_sw_corner = check_corner(position, IVec2::new(-1, 1), cache);
+                let has_nw_corner = check_corner(position, (Cell::WEST, Cell::NORTH), cache);
+                let has_sw_corner = check_corner(position, (Cell::WEST, Cell::SOUTH), cache);
                 match (has_nw_corner, has_sw_corner) {
                     (true, true) => 311,
                     (true, false) => 391,
@@ -203,8 +188,8 @@ fn cell_to_index(
             }
             0b1100 => 282,
             0b1101 => {
-                let has_ne_corner = check_corner(position, IVec2::new(1, -1), cache);
-                let has_nw_corner = check_corner(position, IVec2::new(-1, -1), cache);
+                let has_ne_corner = check_corner(position, (Cell::EAST, Cell::NORTH), cache);
+                let has_nw_corner = check_corner(position, (Cell::WEST, Cell::NORTH), cache);
                 match (has_ne_corner, has_nw_corner) {
                     (true, true) => 284,
                     (true, false) => 420,
@@ -213,8 +198,8 @@ fn cell_to_index(
                 }
             }
             0b1110 => {
-                let has_se_corner = check_corner(position, IVec2::new(1, 1), cache);
-                let has_sw_corner = check_corner(position, IVec2::new(-1, 1), cache);
+                let has_se_corner = check_corner(position, (Cell::EAST, Cell::SOUTH), cache);
+                let has_sw_corner = check_corner(position, (Cell::WEST, Cell::SOUTH), cache);
                 match (has_se_corner, has_sw_corner) {
                     (true, true) => 283,
                     (true, false) => 393,
@@ -223,10 +208,10 @@ fn cell_to_index(
                 }
             }
             0b1111 => {
-                let has_ne_corner = check_corner(position, IVec2::new(1, -1), cache);
-                let has_nw_corner = check_corner(position, IVec2::new(-1, -1), cache);
-                let has_se_corner = check_corner(position, IVec2::new(1, 1), cache);
-                let has_sw_corner = check_corner(position, IVec2::new(-1, 1), cache);
+                let has_ne_corner = check_corner(position, (Cell::EAST, Cell::NORTH), cache);
+                let has_nw_corner = check_corner(position, (Cell::WEST, Cell::NORTH), cache);
+                let has_se_corner = check_corner(position, (Cell::EAST, Cell::SOUTH), cache);
+                let has_sw_corner = check_corner(position, (Cell::WEST, Cell::SOUTH), cache);
 
                 match (has_ne_corner, has_nw_corner, has_se_corner, has_sw_corner) {
                     (true, true, true, true) => 341,
@@ -85,6 +85,9 @@
 //!
 //! - `pathfinding` (default): Enables pathfinding module plus plugin resources/systems.
 //! - `single_end`: Works with `pathfinding`; only registers single start/goal pathfinding systems.
+//! - `compact`: Stores orthogonal maze cells packed two per byte to halve their memory footprint.
+//! - `serde`: Enables `serde` support for mazes and [`MazeArchive`](maze::MazeArchive) helpers.
+//! - `parallel`: Enables `generate_batch` to generate many mazes at once on `rayon` threads.
 //!
 //! # Algorithms
 //!
@@ -95,6 +98,7 @@
 //!
 //! - [`AldousBroder`](maze::AldousBroder)
 //! - [`BinaryTree`](maze::BinaryTree)
+//! - [`Dungeon`](maze::Dungeon)
 //! - [`Eller`](maze::Eller)
 //! - [`GrowingTree`](maze::GrowingTree)
 //! - [`HuntAndKill`](maze::HuntAndKill)
@@ -103,6 +107,8 @@
 //! - [`RecursiveBacktracking`](maze::RecursiveBacktracking)
 //! - [`RecursiveDivision`](maze::RecursiveDivision)
 //! - [`Sidewinder`](maze::Sidewinder)
+//! - [`Weave`](maze::Weave)
+//! - [`Wilson`](maze::Wilson)
 
 mod utils;
 
@@ -110,7 +116,12 @@ pub mod maze;
 use bevy::app::Plugin;
 use maze::Cell;
 pub use utils::color::Color;
-pub use utils::types::{CellSize, Coords, CoordsComponent, Goal, Start};
+pub use utils::types::{
+    CellSize, Coords, CoordsComponent, Goal, Start, direction_between, neighbor,
+};
+
+/// Module spawning maze tiles as entities from [`KnossosPlugin`]
+pub mod spawn;
 
 #[cfg(feature = "pathfinding")]
 /// Module containing all necessary tooling to pathfind between [`Start`] and [`Goal`]
@@ -135,8 +146,14 @@ impl Plugin for KnossosPlugin {
             app.register_type::<pathfind::MazePath>()
                 .register_type::<pathfind::Algorithm>()
                 .init_resource::<pathfind::Algorithm>()
+                .register_type::<pathfind::DistanceMap>()
                 .init_resource::<pathfind::MazePath>()
-                .add_systems(Update, pathfind::find_path);
+                .init_resource::<pathfind::DistanceMap>()
+                .add_message::<pathfind::PathFound>()
+                .add_systems(
+                    Update,
+                    (pathfind::find_path, pathfind::compute_distance_map),
+                );
 
             #[cfg(not(feature = "single_end"))]
             {
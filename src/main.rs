@@ -2,8 +2,9 @@ use std::fmt;
 use std::str::FromStr;
 
 use bevy_knossos::Color;
-use bevy_knossos::maze::{self, MazeSaveError, formatters};
+use bevy_knossos::maze::{self, MazeSaveError, StreamFormatter, formatters};
 use clap::{Parser, Subcommand, ValueEnum};
+use rand::{SeedableRng, rngs::StdRng};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum Algorithm {
@@ -17,6 +18,25 @@ enum Algorithm {
     RecursiveBacktracking,
     RecursiveDivision,
     Sidewinder,
+    Wilson,
+}
+
+impl Algorithm {
+    fn build(self, bias: maze::Bias, growing_method: maze::Method) -> Box<dyn maze::Algorithm> {
+        match self {
+            Self::AldousBroder => Box::new(maze::AldousBroder),
+            Self::BinaryTree => Box::new(maze::BinaryTree::new(bias)),
+            Self::Eller => Box::new(maze::Eller),
+            Self::GrowingTree => Box::new(maze::GrowingTree::new(growing_method)),
+            Self::HuntAndKill => Box::new(maze::HuntAndKill::new()),
+            Self::Kruskal => Box::new(maze::Kruskal),
+            Self::Prim => Box::new(maze::Prim::new()),
+            Self::RecursiveBacktracking => Box::new(maze::RecursiveBacktracking),
+            Self::RecursiveDivision => Box::new(maze::RecursiveDivision),
+            Self::Sidewinder => Box::new(maze::Sidewinder),
+            Self::Wilson => Box::new(maze::Wilson),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -108,6 +128,79 @@ enum Commands {
             value_enum,
         )]
         growing_method: maze::Method,
+
+        /// Name of a registered or built-in algorithm to use instead of `--algorithm`
+        #[arg(long, value_name = "NAME")]
+        custom_algorithm: Option<String>,
+
+        /// Ratio of dead ends to remove by adding loops, from 0 (a perfect maze) to 1
+        #[arg(long, default_value_t = 0.0, value_parser = parse_ratio)]
+        braid: f64,
+    },
+    /// Converts a saved maze into another format
+    Convert {
+        #[command(subcommand)]
+        output: OutputCommands,
+
+        /// Path to a maze saved as binary, JSON or CSV, chosen by the file extension
+        #[arg(short = 'I', long)]
+        input_path: String,
+    },
+    /// Prints a maze with the shortest path between two cells marked
+    Solve {
+        /// Path to a maze saved as binary, JSON or CSV to solve instead of generating one
+        #[arg(short = 'I', long)]
+        input_path: Option<String>,
+
+        /// Maze generation algorithm
+        #[arg(short = 'A', long, value_enum, default_value_t = Algorithm::RecursiveBacktracking)]
+        algorithm: Algorithm,
+
+        /// Grid height in a number of cells
+        #[arg(short = 'H', long, default_value_t = 10)]
+        height: usize,
+
+        /// Seed value for deterministic generation (must be a valid u64)
+        #[arg(short = 'S', long)]
+        seed: Option<u64>,
+
+        /// Grid width in a number of cells
+        #[arg(short = 'W', long, default_value_t = 10)]
+        width: usize,
+
+        /// Bias to use for the "Binary Tree" algorithm
+        #[arg(
+            long,
+            default_value_t = maze::Bias::NorthEast,
+            require_equals = true,
+            num_args = 0..=1,
+            default_missing_value = "north-east",
+            value_enum,
+        )]
+        bias: maze::Bias,
+
+        /// Growing method to use for the "Growing Tree" algorithm
+        #[arg(
+            long,
+            default_value_t = maze::Method::Newest,
+            require_equals = true,
+            num_args = 0..=1,
+            default_missing_value = "newest",
+            value_enum,
+        )]
+        growing_method: maze::Method,
+
+        /// Start coordinate of the path, the top-left cell by default
+        #[arg(long)]
+        start: Option<Coords>,
+
+        /// Goal coordinate of the path, the bottom-right cell by default
+        #[arg(long)]
+        goal: Option<Coords>,
+
+        /// ASCII character marking the cells of the path
+        #[arg(long, default_value_t = '*')]
+        marker: char,
     },
 }
 
@@ -180,6 +273,12 @@ enum OutputCommands {
         #[arg(long = "wall-color", default_value = "#000000", value_parser = hex_to_rgb)]
         wall_color: Color,
     },
+    /// Save to a binary file that can be loaded back by the `convert` and `solve` commands
+    Binary {
+        /// Output path
+        #[arg(short = 'O', long)]
+        output_path: String,
+    },
 }
 
 fn main() -> Result<(), maze::MazeSaveError> {
@@ -195,18 +294,40 @@ fn main() -> Result<(), maze::MazeSaveError> {
             bias,
             growing_method,
             start_coords,
+            custom_algorithm,
+            braid,
         } => {
-            let algorithm: Box<dyn maze::Algorithm> = match algorithm {
-                Algorithm::AldousBroder => Box::new(maze::AldousBroder),
-                Algorithm::BinaryTree => Box::new(maze::BinaryTree::new(bias)),
-                Algorithm::Eller => Box::new(maze::Eller),
-                Algorithm::GrowingTree => Box::new(maze::GrowingTree::new(growing_method)),
-                Algorithm::HuntAndKill => Box::new(maze::HuntAndKill::new()),
-                Algorithm::Kruskal => Box::new(maze::Kruskal),
-                Algorithm::Prim => Box::new(maze::Prim::new()),
-                Algorithm::RecursiveBacktracking => Box::new(maze::RecursiveBacktracking),
-                Algorithm::RecursiveDivision => Box::new(maze::RecursiveDivision),
-                Algorithm::Sidewinder => Box::new(maze::Sidewinder),
+            // Eller mazes written as ASCII are streamed row by row, so their height isn't limited
+            // by memory
+            if let OutputCommands::Ascii {
+                output_path,
+                output_type,
+            } = &output
+                && algorithm == Algorithm::Eller
+                && custom_algorithm.is_none()
+                && start_coords.is_none()
+                && braid == 0.0
+            {
+                let mut rng =
+                    seed.map_or_else(|| StdRng::from_rng(&mut rand::rng()), StdRng::seed_from_u64);
+                let rows = maze::Eller::rows(width, height, &mut rng);
+                let result = match output_type {
+                    AsciiOutputType::Narrow => {
+                        formatters::AsciiNarrow.save_rows(output_path, width, rows)
+                    }
+                    AsciiOutputType::Broad => {
+                        formatters::AsciiBroad.save_rows(output_path, width, rows)
+                    }
+                };
+                println!("{}", result?);
+                return Ok(());
+            }
+
+            let algorithm = algorithm.build(bias, growing_method);
+            let algorithm = match custom_algorithm {
+                Some(name) => maze::algorithm_by_name(&name)
+                    .ok_or_else(|| MazeSaveError::reason(format!("Unknown algorithm `{name}`")))?,
+                None => algorithm,
             };
 
             let mut builder = start_coords
@@ -216,78 +337,187 @@ fn main() -> Result<(), maze::MazeSaveError> {
             if let Some(seed) = seed {
                 builder = builder.seed(seed);
             }
-            let maze = builder
+            // Braiding continues the RNG stream of the generation, so its choices don't repeat
+            // the ones made while carving
+            let (mut maze, mut rng) = builder
                 .height(height)
                 .width(width)
                 .algorithm(algorithm)
-                .build()
+                .build_with_rng()
                 .map_err(|err| MazeSaveError::reason(err.to_string()))?;
 
-            let result;
-
-            match output {
-                OutputCommands::Ascii {
-                    output_path,
-                    output_type,
-                } => match output_type {
-                    AsciiOutputType::Narrow => {
-                        result = maze.save(output_path.as_str(), formatters::AsciiNarrow);
-                    }
-                    AsciiOutputType::Broad => {
-                        result = maze.save(output_path.as_str(), formatters::AsciiBroad);
-                    }
-                },
-                OutputCommands::GameMap {
-                    output_path,
-                    span,
-                    passage,
-                    wall,
-                    with_start_goal,
-                } => {
-                    result = if with_start_goal {
-                        maze.save(
-                            output_path.as_str(),
-                            maze::GameMap::new()
-                                .span(span)
-                                .passage(passage)
-                                .wall(wall)
-                                .with_start_goal(),
-                        )
-                    } else {
-                        maze.save(
-                            output_path.as_str(),
-                            maze::GameMap::new().span(span).passage(passage).wall(wall),
-                        )
-                    };
-                }
-                OutputCommands::Image {
-                    output_path,
-                    wall_size,
-                    passage_size,
-                    margin,
-                    passage_color,
-                    wall_color,
-                } => {
-                    result = maze.save(
-                        output_path.as_str(),
-                        maze::Image::new()
-                            .wall(wall_size)
-                            .passage(passage_size)
-                            .margin(margin)
-                            .background(passage_color)
-                            .foreground(wall_color),
-                    );
-                }
+            if braid > 0.0 {
+                maze.braid(&mut rng, braid);
             }
 
-            match result {
-                Ok(msg) => {
-                    println!("{msg}");
-                    Ok(())
+            println!("{}", save_maze(&maze, output)?);
+            Ok(())
+        }
+        Commands::Convert { output, input_path } => {
+            let maze = load_maze(&input_path)?;
+            println!("{}", save_maze(&maze, output)?);
+            Ok(())
+        }
+        Commands::Solve {
+            input_path,
+            algorithm,
+            height,
+            width,
+            seed,
+            bias,
+            growing_method,
+            start,
+            goal,
+            marker,
+        } => {
+            let maze = if let Some(input_path) = input_path {
+                load_maze(&input_path)?
+            } else {
+                let mut builder = maze::OrthogonalMazeBuilder::new();
+                if let Some(seed) = seed {
+                    builder = builder.seed(seed);
                 }
-                Err(err) => Err(err),
+                builder
+                    .height(height)
+                    .width(width)
+                    .algorithm(algorithm.build(bias, growing_method))
+                    .build()
+                    .map_err(|err| MazeSaveError::reason(err.to_string()))?
+            };
+
+            let start = start.map_or((0, 0), |coords| (coords.0, coords.1));
+            let goal = goal.map_or_else(
+                || {
+                    (
+                        maze.width().saturating_sub(1),
+                        maze.height().saturating_sub(1),
+                    )
+                },
+                |coords| (coords.0, coords.1),
+            );
+            println!("{}", solve(&maze, start, goal, marker)?);
+            Ok(())
+        }
+    }
+}
+
+/// Saves the maze with a formatter chosen by the output command
+fn save_maze(maze: &maze::OrthogonalMaze, output: OutputCommands) -> Result<String, MazeSaveError> {
+    match output {
+        OutputCommands::Ascii {
+            output_path,
+            output_type,
+        } => match output_type {
+            AsciiOutputType::Narrow => maze.save(output_path.as_str(), formatters::AsciiNarrow),
+            AsciiOutputType::Broad => maze.save(output_path.as_str(), formatters::AsciiBroad),
+        },
+        OutputCommands::GameMap {
+            output_path,
+            span,
+            passage,
+            wall,
+            with_start_goal,
+        } => {
+            if with_start_goal {
+                maze.save(
+                    output_path.as_str(),
+                    maze::GameMap::new()
+                        .span(span)
+                        .passage(passage)
+                        .wall(wall)
+                        .with_start_goal(),
+                )
+            } else {
+                maze.save(
+                    output_path.as_str(),
+                    maze::GameMap::new().span(span).passage(passage).wall(wall),
+                )
             }
         }
+        OutputCommands::Image {
+            output_path,
+            wall_size,
+            passage_size,
+            margin,
+            passage_color,
+            wall_color,
+        } => maze.save(
+            output_path.as_str(),
+            maze::Image::new()
+                .wall(wall_size)
+                .passage(passage_size)
+                .margin(margin)
+                .background(passage_color)
+                .foreground(wall_color),
+        ),
+        OutputCommands::Binary { output_path } => std::fs::write(&output_path, maze.to_bytes()?)
+            .map(|()| format!("Maze was successfully written to a file: {output_path}"))
+            .map_err(|err| MazeSaveError::reason(err.to_string())),
+    }
+}
+
+/// Loads a maze saved as JSON or CSV, if the path has one of these extensions, or as binary
+fn load_maze(path: &str) -> Result<maze::OrthogonalMaze, MazeSaveError> {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(std::ffi::OsStr::to_str);
+    let maze = match extension {
+        Some("json") => load_json(&read_to_string(path)?),
+        Some("csv") => maze::Csv::parse(&read_to_string(path)?),
+        _ => {
+            let bytes =
+                std::fs::read(path).map_err(|err| MazeSaveError::reason(err.to_string()))?;
+            maze::OrthogonalMaze::from_bytes(&bytes)
+        }
+    };
+
+    maze.map_err(|err| MazeSaveError::reason(err.to_string()))
+}
+
+fn read_to_string(path: &str) -> Result<String, MazeSaveError> {
+    std::fs::read_to_string(path).map_err(|err| MazeSaveError::reason(err.to_string()))
+}
+
+#[cfg(feature = "serde")]
+fn load_json(json: &str) -> Result<maze::OrthogonalMaze, maze::MazeLoadError> {
+    maze::OrthogonalMaze::from_json(json)
+}
+
+#[cfg(not(feature = "serde"))]
+fn load_json(_json: &str) -> Result<maze::OrthogonalMaze, maze::MazeLoadError> {
+    Err(maze::MazeLoadError::reason(
+        "Loading mazes from JSON requires the `serde` feature",
+    ))
+}
+
+/// Returns the maze formatted as ASCII with the shortest path from `start` to `goal` marked
+fn solve(
+    maze: &maze::OrthogonalMaze,
+    start: (usize, usize),
+    goal: (usize, usize),
+    marker: char,
+) -> Result<String, MazeSaveError> {
+    let path = maze.solve(start, goal).ok_or_else(|| {
+        MazeSaveError::reason(format!(
+            "Goal {} can't be reached from {}",
+            Coords(goal.0, goal.1),
+            Coords(start.0, start.1)
+        ))
+    })?;
+
+    Ok(maze
+        .format(maze::AsciiBroad.with_path(path).marker(marker))
+        .into_inner())
+}
+
+fn parse_ratio(s: &str) -> Result<f64, String> {
+    let ratio = s.parse::<f64>().map_err(|err| err.to_string())?;
+    if (0.0..=1.0).contains(&ratio) {
+        Ok(ratio)
+    } else {
+        Err(format!(
+            "Expected a ratio between 0 and 1, but got: {ratio}"
+        ))
     }
 }
 
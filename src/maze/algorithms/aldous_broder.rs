@@ -7,9 +7,28 @@ use rand::prelude::*;
 /// This is an easy one to implement. And yet, it is also one of the least intelligent algorithms,
 /// since the latest steps may take so much time that you may not want to wait until it's finished.
 /// It is not even guaranteed to finish if you get really unlucky with the random.
+#[derive(Clone)]
 pub struct AldousBroder;
 
-impl AldousBroder {}
+/// The Aldous-Broder's algorithm starting the walk at a given cell
+///
+/// Created with [`AldousBroder::starting_at`].
+#[derive(Clone)]
+pub struct AldousBroderFrom {
+    start: Coords,
+}
+
+impl AldousBroder {
+    /// Create a new instance of the algorithm that starts the walk at a given cell
+    ///
+    /// Together with a fixed seed, this reproduces the same walk and thus the same maze. The
+    /// builder's [`start_coords`](crate::maze::OrthogonalMazeBuilder::start_coords) take precedence
+    /// over this cell, while a cell outside of the grid or masked off is ignored.
+    #[must_use]
+    pub const fn starting_at(start: Coords) -> AldousBroderFrom {
+        AldousBroderFrom { start }
+    }
+}
 
 /// An implementation of Aldous-Broder's algorithm for generating mazes.
 ///
@@ -24,44 +43,113 @@ impl AldousBroder {}
 /// 3. Repeats step 2 until all vertices have been visited.
 impl Algorithm for AldousBroder {
     fn generate(&mut self, grid: &mut Grid, start_coords: Option<Coords>, rng: &mut StdRng) {
-        let (mut x, mut y) = start_coords.unwrap_or_else(|| get_start_coords(grid, rng));
-
-        let mut remaining = grid.width() * grid.height() - 1; // the number of remaining unvisited cells
+        generate(grid, start_coords, rng);
+    }
 
-        while remaining > 0 {
-            let mut directions = grid.directions().to_vec();
-            directions.shuffle(rng);
+    fn has_start_coords(&self) -> bool {
+        true
+    }
 
-            for dir in directions {
-                let next_cell = grid.get_next_cell_coords((x, y), dir);
-                if next_cell.is_err() {
-                    continue;
-                }
+    fn supports_mask(&self) -> bool {
+        true
+    }
 
-                let (nx, ny) = next_cell.unwrap();
-                if !grid.is_cell_visited((nx, ny)) {
-                    grid.carve_passage((x, y), dir).unwrap();
-                    remaining -= 1;
-                }
+    fn name(&self) -> &'static str {
+        "AldousBroder"
+    }
+}
 
-                x = nx;
-                y = ny;
-                break;
-            }
-        }
+/// An implementation of Aldous-Broder's algorithm starting at a given cell
+///
+/// Works like [`AldousBroder`], except that step 1 chooses the given vertex.
+impl Algorithm for AldousBroderFrom {
+    fn generate(&mut self, grid: &mut Grid, start_coords: Option<Coords>, rng: &mut StdRng) {
+        let start = Some(self.start).filter(|coords| grid.is_active(*coords));
+        generate(grid, start_coords.or(start), rng);
     }
 
     fn has_start_coords(&self) -> bool {
         true
     }
 
+    fn supports_mask(&self) -> bool {
+        true
+    }
+
     fn name(&self) -> &'static str {
         "AldousBroder"
     }
 }
 
+fn generate(grid: &mut Grid, start_coords: Option<Coords>, rng: &mut StdRng) {
+    let (mut x, mut y) = start_coords.unwrap_or_else(|| get_start_coords(grid, rng));
+
+    let mut remaining = grid.active_count().saturating_sub(1); // the number of remaining unvisited cells
+
+    while remaining > 0 {
+        let mut directions = grid.directions().to_vec();
+        directions.shuffle(rng);
+
+        for dir in directions {
+            let next_cell = grid.get_next_cell_coords((x, y), dir);
+            if next_cell.is_err() {
+                continue;
+            }
+
+            let (nx, ny) = next_cell.unwrap();
+            if !grid.is_cell_visited((nx, ny)) {
+                grid.carve_passage((x, y), dir).unwrap();
+                remaining -= 1;
+            }
+
+            x = nx;
+            y = ny;
+            break;
+        }
+    }
+}
+
 fn get_start_coords(grid: &Grid, rng: &mut impl rand::Rng) -> Coords {
+    if grid.is_masked() {
+        return grid.random_active_cell(rng).unwrap_or((0, 0));
+    }
     let y = rng.random_range(0..grid.height());
     let x = rng.random_range(0..grid.width());
     (x, y)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(mut algorithm: impl Algorithm, seed: u64) -> Grid {
+        let mut grid = Grid::new(8, 8);
+        algorithm.generate(&mut grid, None, &mut StdRng::seed_from_u64(seed));
+        grid
+    }
+
+    #[test]
+    fn walk_from_a_fixed_start_is_deterministic() {
+        let grid = generate(AldousBroder::starting_at((3, 5)), 11);
+
+        assert_eq!(generate(AldousBroder::starting_at((3, 5)), 11), grid);
+        assert_ne!(generate(AldousBroder::starting_at((0, 0)), 11), grid);
+
+        // The start cell matches the builder's start coords
+        let mut from_builder = Grid::new(8, 8);
+        AldousBroder.generate(
+            &mut from_builder,
+            Some((3, 5)),
+            &mut StdRng::seed_from_u64(11),
+        );
+        assert_eq!(from_builder, grid);
+    }
+
+    #[test]
+    fn start_outside_of_the_grid_is_ignored() {
+        let grid = generate(AldousBroder::starting_at((8, 0)), 4);
+
+        assert_eq!(grid, generate(AldousBroder, 4));
+        assert_eq!(grid.components().0, 1);
+    }
+}
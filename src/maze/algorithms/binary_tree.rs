@@ -1,6 +1,6 @@
 use super::Algorithm;
 use crate::maze::grid::Grid;
-use crate::maze::grid::cell::Cell;
+use crate::maze::grid::direction::Direction;
 use crate::utils::types::Coords;
 use clap::ValueEnum;
 use rand::prelude::*;
@@ -10,6 +10,7 @@ use rand::prelude::*;
 /// Each bias represents the two of four sides of the maze that will be spanned
 /// by a single corridor.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Bias {
     /// Produces two long corridors on the Northern and Western sides of the maze
     NorthWest,
@@ -30,6 +31,7 @@ pub enum Bias {
 /// a notable bias (routes tend to run diagonally) and long corridors spanning two sides. Still,
 /// this is quite a performant algorithm since it operates without any state at all looking at the
 /// current cell only, without regard for the rest of the cells and rows in the maze.
+#[derive(Clone)]
 pub struct BinaryTree {
     bias: Bias,
 }
@@ -48,41 +50,41 @@ impl BinaryTree {
         Self { bias }
     }
 
-    fn populate_dirs(&self, coords: Coords, grid: &Grid) -> Vec<Cell> {
+    fn populate_dirs(&self, coords: Coords, grid: &Grid) -> Vec<Direction> {
         let mut dirs = vec![];
         let (x, y) = coords;
 
         match self.bias {
             Bias::NorthWest => {
                 if y > 0 {
-                    dirs.push(Cell::NORTH);
+                    dirs.push(Direction::North);
                 }
                 if x > 0 {
-                    dirs.push(Cell::WEST);
+                    dirs.push(Direction::West);
                 }
             }
             Bias::NorthEast => {
                 if y > 0 {
-                    dirs.push(Cell::NORTH);
+                    dirs.push(Direction::North);
                 }
                 if x + 1 < grid.width() {
-                    dirs.push(Cell::EAST);
+                    dirs.push(Direction::East);
                 }
             }
             Bias::SouthWest => {
                 if y + 1 < grid.height() {
-                    dirs.push(Cell::SOUTH);
+                    dirs.push(Direction::South);
                 }
                 if x > 0 {
-                    dirs.push(Cell::WEST);
+                    dirs.push(Direction::West);
                 }
             }
             Bias::SouthEast => {
                 if y + 1 < grid.height() {
-                    dirs.push(Cell::SOUTH);
+                    dirs.push(Direction::South);
                 }
                 if x + 1 < grid.width() {
-                    dirs.push(Cell::EAST);
+                    dirs.push(Direction::East);
                 }
             }
         }
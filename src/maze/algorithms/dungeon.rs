@@ -0,0 +1,210 @@
+use super::{Algorithm, recursive_backtracking::carve_passages_from};
+use crate::maze::grid::{Grid, direction::Direction};
+use crate::utils::arena::{ArenaTree, NodeId};
+use crate::utils::types::Coords;
+use rand::prelude::*;
+
+/// A room placed by the [`Dungeon`] algorithm, given by its top left cell and its size in cells
+pub type Room = (Coords, (usize, usize));
+
+/// The "Dungeon" algorithm for generating roguelike layouts
+///
+/// It places rectangular rooms first, fills the space around them with corridors carved with the
+/// [`RecursiveBacktracking`](super::RecursiveBacktracking) algorithm and finally opens doors
+/// between the rooms and the corridors, until the whole grid is connected.
+#[derive(Clone)]
+pub struct Dungeon {
+    room_attempts: usize,
+    min_room: usize,
+    max_room: usize,
+    rooms: Vec<Room>,
+}
+
+impl Dungeon {
+    /// Create a new instance of the algorithm that tries to place a room `room_attempts` times
+    ///
+    /// The width and height of each room are picked between `min_room` and `max_room` cells. Rooms
+    /// are at least 2 cells wide and tall, and never overlap nor touch each other. Attempts that
+    /// don't fit are dropped, so fewer rooms than attempts may be placed.
+    #[must_use]
+    pub fn new(room_attempts: usize, min_room: usize, max_room: usize) -> Self {
+        let min_room = min_room.max(2);
+        Self {
+            room_attempts,
+            min_room,
+            max_room: max_room.max(min_room),
+            rooms: vec![],
+        }
+    }
+
+    /// Returns the rooms placed by the last generation
+    #[must_use]
+    pub fn rooms(&self) -> &[Room] {
+        &self.rooms
+    }
+
+    fn place_rooms(&mut self, grid: &mut Grid, rng: &mut StdRng) {
+        self.rooms.clear();
+
+        for _ in 0..self.room_attempts {
+            let width = rng.random_range(self.min_room..=self.max_room);
+            let height = rng.random_range(self.min_room..=self.max_room);
+            if width > grid.width() || height > grid.height() {
+                continue;
+            }
+
+            let x = rng.random_range(0..=grid.width() - width);
+            let y = rng.random_range(0..=grid.height() - height);
+            // Rooms keep a cell of space between them, so corridors can run around them
+            let touches = self.rooms.iter().any(|&((rx, ry), (rw, rh))| {
+                x <= rx + rw && rx <= x + width && y <= ry + rh && ry <= y + height
+            });
+            if touches {
+                continue;
+            }
+
+            for cy in y..y + height {
+                for cx in x..x + width {
+                    if cx + 1 < x + width {
+                        grid.carve_passage((cx, cy), Direction::East).unwrap();
+                    }
+                    if cy + 1 < y + height {
+                        grid.carve_passage((cx, cy), Direction::South).unwrap();
+                    }
+                }
+            }
+            self.rooms.push(((x, y), (width, height)));
+        }
+    }
+}
+
+impl Default for Dungeon {
+    fn default() -> Self {
+        Self::new(30, 3, 6)
+    }
+}
+
+/// An implementation of the "Dungeon" algorithm for generating mazes.
+/// Does not support start coords.
+///
+/// It works like this:
+///
+/// 1. Places rooms of random sizes at random positions, skipping the ones that would overlap or
+///    touch the already placed rooms, and carves all the passages within them.
+///
+/// 2. Runs the "Recursive Backtracking" algorithm from every cell outside of the rooms that is
+///    still not visited, filling the space between the rooms with corridors.
+///
+/// 3. Opens doors through random walls between the regions, i.e. rooms and corridors, that are
+///    not connected yet, until all the regions are connected.
+///
+/// # Warn
+///
+/// The `generate` function will warn in case a [`start_coords`] is passed.
+impl Algorithm for Dungeon {
+    fn generate(&mut self, grid: &mut Grid, c: Option<Coords>, rng: &mut StdRng) {
+        if c.is_some() {
+            eprintln!("Algorithm `{}` doesn't suppoer `start_coords`", self.name());
+        }
+
+        self.place_rooms(grid, rng);
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                if !grid.is_cell_visited((x, y)) {
+                    carve_passages_from((x, y), grid, rng);
+                }
+            }
+        }
+
+        connect_regions(grid, rng);
+    }
+
+    fn has_start_coords(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> &'static str {
+        "Dungeon"
+    }
+}
+
+/// Carves a passage through random walls between disconnected regions until the grid is connected
+fn connect_regions(grid: &mut Grid, rng: &mut StdRng) {
+    let (count, labels) = grid.components();
+    let width = grid.width();
+    let label = |(x, y): Coords| labels[y * width + x].unwrap_or(0);
+
+    let mut doors: Vec<(Coords, Direction)> = vec![];
+    for y in 0..grid.height() {
+        for x in 0..width {
+            for dir in [Direction::East, Direction::South] {
+                if let Ok(next) = grid.get_next_cell_coords((x, y), dir)
+                    && label((x, y)) != label(next)
+                {
+                    doors.push(((x, y), dir));
+                }
+            }
+        }
+    }
+    doors.shuffle(rng);
+
+    let mut regions = ArenaTree::new();
+    for _ in 0..count {
+        regions.new_node();
+    }
+
+    let mut merges = 0;
+    for (coords, dir) in doors {
+        if merges + 1 >= count {
+            break;
+        }
+
+        let next = grid.get_next_cell_coords(coords, dir).unwrap();
+        let (from, to) = (NodeId(label(coords)), NodeId(label(next)));
+        if !regions.connected(from, to) {
+            regions.connect(from, to);
+            grid.carve_passage(coords, dir).unwrap();
+            merges += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::grid::cell::Cell;
+
+    #[test]
+    fn rooms_are_open_and_grid_is_connected() {
+        for seed in 0..10 {
+            let mut grid = Grid::new(25, 20);
+            let mut dungeon = Dungeon::new(40, 3, 6);
+            let mut rng = StdRng::seed_from_u64(seed);
+            dungeon.generate(&mut grid, None, &mut rng);
+
+            assert!(!dungeon.rooms().is_empty());
+            for &((x, y), (width, height)) in dungeon.rooms() {
+                assert!((3..=6).contains(&width) && (3..=6).contains(&height));
+                for cy in y..y + height {
+                    for cx in x..x + width {
+                        assert!(cx + 1 == x + width || grid.is_carved((cx, cy), Cell::EAST));
+                        assert!(cy + 1 == y + height || grid.is_carved((cx, cy), Cell::SOUTH));
+                    }
+                }
+            }
+            assert_eq!(grid.components().0, 1);
+        }
+    }
+
+    #[test]
+    fn rooms_larger_than_grid_are_skipped() {
+        let mut grid = Grid::new(4, 4);
+        let mut dungeon = Dungeon::new(5, 5, 8);
+        let mut rng = StdRng::seed_from_u64(1);
+        dungeon.generate(&mut grid, None, &mut rng);
+
+        assert!(dungeon.rooms().is_empty());
+        assert_eq!(grid.components().0, 1);
+    }
+}
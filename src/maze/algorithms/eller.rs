@@ -1,6 +1,6 @@
 use super::{Algorithm, BOOL_TRUE_PROBABILITY};
 use crate::{
-    maze::grid::{Grid, cell::Cell as GridCell},
+    maze::grid::{Grid, cell::Cell as GridCell, direction::Direction},
     utils::types::Coords,
 };
 use rand::prelude::*;
@@ -104,79 +104,115 @@ impl State {
 /// This is one of the best algorithms in terms of space complexity since it
 /// runs over a single row at a time. Moreover, by making a small change,
 /// this one can generate mazes of infinite size in linear time.
+#[derive(Clone)]
 pub struct Eller;
 
 impl Eller {
+    /// Returns an iterator over the rows of a new maze of a given size, generated one row at a
+    /// time
+    ///
+    /// Only the state of the current row is kept in memory, so mazes of any height can be
+    /// generated with constant memory, e.g. to write them with a
+    /// [`StreamFormatter`](crate::maze::formatters::StreamFormatter).
+    pub fn rows<R: Rng>(width: usize, height: usize, rng: &mut R) -> EllerRows<'_, R> {
+        EllerRows {
+            height,
+            row: 0,
+            state: State::new(0, None, width).populate(),
+            cells: vec![GridCell::empty(); width],
+            rng,
+        }
+    }
+}
+
+/// An iterator over the rows of a maze generated by the Eller's algorithm
+///
+/// Each row contains the cells with the passages carved to their neighbors, from west to east.
+pub struct EllerRows<'a, R> {
+    height: usize,
+    row: usize,
+    state: State,
+    cells: Vec<GridCell>,
+    rng: &'a mut R,
+}
+
+impl<R: Rng> EllerRows<'_, R> {
     /// Randomly joins adjacent cells, but only if they are not in the same set
-    #[expect(clippy::unused_self)]
-    fn connect_disjoint_sets(
-        &self,
-        state: &mut State,
-        grid: &mut Grid,
-        is_last_row: bool,
-        rng: &mut impl Rng,
-    ) {
-        for c in 1..state.width {
+    fn connect_disjoint_sets(&mut self, is_last_row: bool) {
+        for c in 1..self.state.width {
             let cell_id = CellId(c);
             let next_cell_id = CellId(c + 1);
 
-            if state.connected(cell_id, next_cell_id)
-                || (!is_last_row && rng.random_bool(BOOL_TRUE_PROBABILITY))
+            if self.state.connected(cell_id, next_cell_id)
+                || (!is_last_row && self.rng.random_bool(BOOL_TRUE_PROBABILITY))
             {
                 continue;
             }
 
-            state.connect(cell_id, next_cell_id);
-            let (x, y) = state.get_cell_coords(cell_id);
-            grid.carve_passage((x, y), GridCell::EAST).unwrap();
+            self.state.connect(cell_id, next_cell_id);
+            let (x, _) = self.state.get_cell_coords(cell_id);
+            self.cells[x].insert(GridCell::EAST);
+            self.cells[x + 1].insert(GridCell::WEST);
         }
     }
 
-    /// For each set, creates at least one vertical connection downward to the next row
-    fn add_vertical_connections(
-        &self,
-        state: &mut State,
-        grid: &mut Grid,
-        is_last_row: bool,
-        rng: &mut impl Rng,
-    ) -> State {
-        let mut next_state = state.next();
-
-        if is_last_row {
-            return next_state.populate();
-        }
-
-        for (set_id, cells) in state.sets() {
-            for cell_id in self.cells_to_connect(cells, rng) {
-                let (x, y) = state.get_cell_coords(cell_id);
-                grid.carve_passage((x, y), GridCell::SOUTH).unwrap();
-                next_state.add(cell_id, set_id, (x, y + 1));
+    /// For each set, creates at least one vertical connection downward to the next row and
+    /// returns the cells of the next row
+    fn add_vertical_connections(&mut self, is_last_row: bool) -> Vec<GridCell> {
+        let mut next_cells = vec![GridCell::empty(); self.state.width];
+        let mut next_state = self.state.next();
+
+        if !is_last_row {
+            for (set_id, cells) in self.state.sets() {
+                for cell_id in cells_to_connect(cells, self.rng) {
+                    let (x, y) = self.state.get_cell_coords(cell_id);
+                    self.cells[x].insert(GridCell::SOUTH);
+                    next_cells[x].insert(GridCell::NORTH);
+                    next_state.add(cell_id, set_id, (x, y + 1));
+                }
             }
         }
 
-        next_state.populate()
+        self.state = next_state.populate();
+        next_cells
     }
+}
 
-    /// Selects random cells to carve vertical passages from
-    #[expect(clippy::unused_self)]
-    fn cells_to_connect(&self, cells: Vec<CellId>, rng: &mut impl Rng) -> Vec<CellId> {
-        let mut cells = cells;
-        cells.shuffle(rng);
+impl<R: Rng> Iterator for EllerRows<'_, R> {
+    type Item = Vec<GridCell>;
 
-        let connect_count = if cells.len() >= 2 {
-            rng.random_range(1..cells.len())
-        } else {
-            1
-        };
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row >= self.height {
+            return None;
+        }
 
-        cells
-            .iter()
-            .take(connect_count)
-            .copied()
-            .collect::<Vec<CellId>>()
+        let is_last_row = self.row + 1 == self.height;
+        self.connect_disjoint_sets(is_last_row);
+        let next_cells = self.add_vertical_connections(is_last_row);
+        self.row += 1;
+
+        Some(std::mem::replace(&mut self.cells, next_cells))
     }
 }
 
+/// Selects random cells to carve vertical passages from
+fn cells_to_connect(cells: Vec<CellId>, rng: &mut impl Rng) -> Vec<CellId> {
+    let mut cells = cells;
+    cells.shuffle(rng);
+
+    let connect_count = if cells.len() >= 2 {
+        rng.random_range(1..cells.len())
+    } else {
+        1
+    };
+
+    cells
+        .iter()
+        .take(connect_count)
+        .copied()
+        .collect::<Vec<CellId>>()
+}
+
 /// An implementation of Eller's algorithm for generating mazes.
 /// Does not support start coords.
 ///
@@ -207,12 +243,16 @@ impl Algorithm for Eller {
         if c.is_some() {
             eprintln!("Algorithm `{}` doesn't suppoer `start_coords`", self.name());
         }
-        let mut state = State::new(0, None, grid.width()).populate();
 
-        for row in 0..grid.height() {
-            let is_last_row = row == grid.height() - 1;
-            self.connect_disjoint_sets(&mut state, grid, is_last_row, rng);
-            state = self.add_vertical_connections(&mut state, grid, is_last_row, rng);
+        let rows = Self::rows(grid.width(), grid.height(), rng);
+        for (y, row) in rows.enumerate() {
+            for (x, cell) in row.into_iter().enumerate() {
+                for direction in [Direction::East, Direction::South] {
+                    if cell.contains(direction.to_cell()) {
+                        grid.carve_passage((x, y), direction).unwrap();
+                    }
+                }
+            }
         }
     }
 
@@ -1,14 +1,16 @@
 use super::Algorithm;
-use crate::maze::grid::{Grid, cell::Cell};
+use crate::maze::grid::{Grid, direction::Direction};
 use crate::utils::types::Coords;
 use clap::ValueEnum;
 use rand::prelude::*;
+use std::sync::Arc;
 
 /// An enumeration over supported cell selection methods for the "Growing Tree" algorithm
 ///
 /// Each method represents the way a new cell is selected causing the "Growing Tree" algorithm
 /// to imitate other algorithms or theirs combinations
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Method {
     /// Selects the most recently added cell, thus imitating the recursive backtracker
     Newest,
@@ -44,9 +46,12 @@ pub enum Method {
 /// it works almost exactly like Prim's algorithm. Another trivial change and you can
 /// combine two or more methods with some probability and generate mazes with mixed attributes.
 ///
-/// You can find plenty of supported methods in the [Method] enum.
+/// You can find plenty of supported methods in the [Method] enum. The selection can also be
+/// biased spatially with [`GrowingTree::with_weight`].
+#[derive(Clone)]
 pub struct GrowingTree {
     method: Method,
+    weight: Option<Arc<dyn Fn(Coords) -> f64 + Send + Sync>>,
 }
 
 impl GrowingTree {
@@ -60,10 +65,43 @@ impl GrowingTree {
     /// ```
     #[must_use]
     pub const fn new(method: Method) -> Self {
-        Self { method }
+        Self {
+            method,
+            weight: None,
+        }
+    }
+
+    /// Create a new instance of the algorithm that selects cells at random, with a probability
+    /// proportional to a weight of their coords
+    ///
+    /// Negative weights are treated as zero, and if all the weights are zero the cells are
+    /// selected uniformly, just like with [`Method::Random`]. For example, a weight that decreases
+    /// with the distance from the center grows corridors there first.
+    ///
+    /// Note that the weight is computed for every cell in the list on every selection, so
+    /// generation takes time proportional to the square of the number of cells in the worst case,
+    /// and the closure should be cheap.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_knossos::maze::GrowingTree;
+    ///
+    /// let algorithm = GrowingTree::with_weight(Box::new(|(x, y)| 1.0 / (1.0 + (x + y) as f64)));
+    /// ```
+    #[must_use]
+    pub fn with_weight(weight: Box<dyn Fn(Coords) -> f64 + Send + Sync>) -> Self {
+        Self {
+            method: Method::Random,
+            weight: Some(Arc::from(weight)),
+        }
     }
 
-    fn choose_index(&self, ceil: usize, rng: &mut impl Rng) -> usize {
+    fn choose_index(&self, cells: &[Coords], rng: &mut impl Rng) -> usize {
+        let ceil = cells.len();
+        if let Some(weight) = &self.weight {
+            return weighted_index(cells, &**weight, rng);
+        }
+
         match self.method {
             Method::Oldest => 0,
             Method::Newest => ceil - 1,
@@ -113,13 +151,18 @@ impl GrowingTree {
 /// 4. Repeats #3 until the C is empty.
 impl Algorithm for GrowingTree {
     fn generate(&mut self, grid: &mut Grid, start_coords: Option<Coords>, rng: &mut StdRng) {
-        let mut directions = [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST];
+        let mut directions = [
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ];
         let mut cells = vec![];
         let start_coords = start_coords.unwrap_or_else(|| get_rand_coords(grid, rng));
         cells.push(start_coords);
 
         while !cells.is_empty() {
-            let mut index = Some(self.choose_index(cells.len(), rng));
+            let mut index = Some(self.choose_index(&cells, rng));
             let coords = cells[index.unwrap_or(0)];
 
             directions.shuffle(rng);
@@ -149,13 +192,80 @@ impl Algorithm for GrowingTree {
         true
     }
 
+    fn supports_mask(&self) -> bool {
+        true
+    }
+
     fn name(&self) -> &'static str {
         "GrowingTree"
     }
 }
 
+fn weighted_index(cells: &[Coords], weight: impl Fn(Coords) -> f64, rng: &mut impl Rng) -> usize {
+    let weights: Vec<f64> = cells.iter().map(|c| weight(*c).max(0.0)).collect();
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 || !total.is_finite() {
+        return rng.random_range(0..cells.len());
+    }
+
+    let mut target = rng.random::<f64>() * total;
+    for (index, weight) in weights.iter().enumerate() {
+        if target < *weight {
+            return index;
+        }
+        target -= weight;
+    }
+    weights.len() - 1
+}
+
 fn get_rand_coords(grid: &Grid, rng: &mut impl Rng) -> Coords {
+    if grid.is_masked() {
+        return grid.random_active_cell(rng).unwrap_or((0, 0));
+    }
     let x = rng.random_range(0..grid.width());
     let y = rng.random_range(0..grid.height());
     (x, y)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the average share of dead ends in mazes generated from a number of seeds
+    fn dead_end_share(mut algorithm: GrowingTree) -> f64 {
+        let mut ends = 0;
+        for seed in 0..20 {
+            let mut grid = Grid::new(20, 20);
+            let mut rng = StdRng::seed_from_u64(seed);
+            algorithm.generate(&mut grid, Some((0, 0)), &mut rng);
+            ends += (0..20)
+                .flat_map(|y| (0..20).map(move |x| (x, y)))
+                .filter(|coords| grid[*coords].is_end_sq())
+                .count();
+        }
+        ends as f64 / (20.0 * 400.0)
+    }
+
+    #[test]
+    fn constant_weight_matches_random_method() {
+        let random = dead_end_share(GrowingTree::new(Method::Random));
+        let weighted = dead_end_share(GrowingTree::with_weight(Box::new(|_| 1.0)));
+        let newest = dead_end_share(GrowingTree::new(Method::Newest));
+
+        assert!((random - weighted).abs() < 0.02, "{random} vs {weighted}");
+        assert!((random - newest).abs() > 0.1, "{random} vs {newest}");
+    }
+
+    #[test]
+    fn weights_bias_selection() {
+        let cells = [(0, 0), (1, 0), (2, 0)];
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..100 {
+            let index = weighted_index(&cells, |(x, _)| if x == 1 { 1.0 } else { -1.0 }, &mut rng);
+            assert_eq!(index, 1);
+        }
+        let index = weighted_index(&cells, |_| 0.0, &mut rng);
+        assert!(index < cells.len());
+    }
+}
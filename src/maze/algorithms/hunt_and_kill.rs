@@ -2,13 +2,36 @@ use super::Algorithm;
 use crate::{maze::grid::Grid, utils::types::Coords};
 use rand::prelude::*;
 
+/// An enumeration over the grid corners the "hunt" phase of the "Hunt & Kill" algorithm can start
+/// scanning from
+///
+/// Since the hunt restarts the walk from the first suitable cell it finds, scanning from a given
+/// corner biases restarts toward it and produces a directional flow of passages.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Corner {
+    /// Scans rows from North to South and cells from West to East
+    #[default]
+    NorthWest,
+
+    /// Scans rows from North to South and cells from East to West
+    NorthEast,
+
+    /// Scans rows from South to North and cells from West to East
+    SouthWest,
+
+    /// Scans rows from South to North and cells from East to West
+    SouthEast,
+}
+
 /// The "Hunt & Kill" algorithm for generating mazes
 ///
 /// This is similar to the recursive backtracker: they both tend to generate long, winding passages
 /// with fewer dead-ends than most of the other algorithms. However, this one differs in that it
 /// will search the grid iteratively, looking for a new blank cell when it encounters a dead-end.
+#[derive(Clone)]
 pub struct HuntAndKill {
     hunt_start_index: usize,
+    start_bias: Corner,
 }
 
 impl HuntAndKill {
@@ -17,9 +40,24 @@ impl HuntAndKill {
     pub const fn new() -> Self {
         Self {
             hunt_start_index: 0,
+            start_bias: Corner::NorthWest,
         }
     }
 
+    /// Sets a corner the "hunt" phase starts scanning from and returns itself
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_knossos::maze::{Corner, HuntAndKill};
+    ///
+    /// let algorithm = HuntAndKill::new().start_bias(Corner::SouthEast);
+    /// ```
+    #[must_use]
+    pub const fn start_bias(mut self, corner: Corner) -> Self {
+        self.start_bias = corner;
+        self
+    }
+
     #[expect(clippy::unused_self)]
     fn walk(&self, coords: Coords, grid: &mut Grid, rng: &mut impl Rng) -> Option<Coords> {
         let mut directions = grid.directions().to_vec();
@@ -37,11 +75,17 @@ impl HuntAndKill {
     }
 
     fn hunt(&mut self, grid: &mut Grid) -> Option<Coords> {
-        for y in self.hunt_start_index..grid.height() {
+        let (width, height) = (grid.width(), grid.height());
+        let from_north = matches!(self.start_bias, Corner::NorthWest | Corner::NorthEast);
+        let from_west = matches!(self.start_bias, Corner::NorthWest | Corner::SouthWest);
+
+        for row in self.hunt_start_index..height {
+            let y = if from_north { row } else { height - 1 - row };
             let mut unvisited_cells_count = 0;
 
-            for x in 0..grid.width() {
-                if grid.is_cell_visited((x, y)) {
+            for col in 0..width {
+                let x = if from_west { col } else { width - 1 - col };
+                if grid.is_cell_visited((x, y)) || !grid.is_active((x, y)) {
                     continue;
                 }
                 unvisited_cells_count += 1;
@@ -57,7 +101,7 @@ impl HuntAndKill {
             }
 
             if unvisited_cells_count == 0 {
-                self.hunt_start_index = y + 1;
+                self.hunt_start_index = row + 1;
             }
         }
         None
@@ -115,12 +159,19 @@ impl Algorithm for HuntAndKill {
         true
     }
 
+    fn supports_mask(&self) -> bool {
+        true
+    }
+
     fn name(&self) -> &'static str {
         "HuntAndKill"
     }
 }
 
 fn get_start_coords(grid: &Grid, rng: &mut impl Rng) -> Coords {
+    if grid.is_masked() {
+        return grid.random_active_cell(rng).unwrap_or((0, 0));
+    }
     let y = rng.random_range(0..grid.height());
     let x = rng.random_range(0..grid.width());
     (x, y)
@@ -134,5 +185,12 @@ mod tests {
     fn default_call() {
         let algo = HuntAndKill::default();
         assert_eq!(0, algo.hunt_start_index);
+        assert_eq!(Corner::NorthWest, algo.start_bias);
+    }
+
+    #[test]
+    fn start_bias_call() {
+        let algo = HuntAndKill::new().start_bias(Corner::SouthEast);
+        assert_eq!(Corner::SouthEast, algo.start_bias);
     }
 }
@@ -2,11 +2,12 @@ use rand::prelude::*;
 
 use super::Algorithm;
 use crate::maze::grid::Grid;
-use crate::maze::grid::cell::Cell;
+use crate::maze::grid::direction::Direction;
 use crate::utils::arena::{ArenaTree, NodeId};
 use crate::utils::types::Coords;
+use std::sync::Arc;
 
-type Edge = (usize, usize, Cell);
+type Edge = (usize, usize, Direction);
 type Edges = Vec<Edge>;
 
 /// The Kruskal's algorithm for generating mazes.
@@ -14,8 +15,39 @@ type Edges = Vec<Edge>;
 /// Kruskal’s algorithm is a method for producing a minimal spanning tree from a weighted graph.
 /// The randomized version of it can be used for generating a rather convincing maze very
 /// effectively.
+#[derive(Clone)]
 pub struct Kruskal;
 
+/// The Kruskal's algorithm picking the edges in the order of their weights
+///
+/// Created with [`Kruskal::weighted`].
+#[derive(Clone)]
+pub struct WeightedKruskal {
+    weight: Arc<dyn Fn(Coords, Coords) -> u32 + Send + Sync>,
+}
+
+impl Kruskal {
+    /// Create a new instance of the algorithm that picks the edges in the order of their weights
+    ///
+    /// The weight of an edge is computed from the coords of the two cells it joins. Edges with a
+    /// lower weight are picked first, so they are more likely to become passages, while edges of
+    /// the same weight are picked in a random order. For example, a lower weight for the edges
+    /// between cells of the same row makes a maze with long horizontal corridors.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_knossos::maze::Kruskal;
+    ///
+    /// let algorithm = Kruskal::weighted(Box::new(|a, b| u32::from(a.1 != b.1)));
+    /// ```
+    #[must_use]
+    pub fn weighted(weight: Box<dyn Fn(Coords, Coords) -> u32 + Send + Sync>) -> WeightedKruskal {
+        WeightedKruskal {
+            weight: Arc::from(weight),
+        }
+    }
+}
+
 /// An implementation of the Kruskal's algorithm for generating mazes.
 /// Does not support start coords.
 ///
@@ -27,46 +59,89 @@ pub struct Kruskal;
 ///    Otherwise, throw that edge away.
 ///
 /// 3. Repeat until there are no more edges left in the set.
+///
 ///  
 /// # Warn
 ///
 /// The `generate` function will warn in case a [`start_coords`] is passed.
 impl Algorithm for Kruskal {
     fn generate(&mut self, grid: &mut Grid, c: Option<Coords>, rng: &mut StdRng) {
-        if c.is_some() {
-            eprintln!("Algorithm `{}` doesn't suppoer `start_coords`", self.name());
-        }
-        let mut arena = populate_arena(grid);
-        let mut edges: Edges = populate_edges(grid);
-        edges.shuffle(rng);
-
-        while !edges.is_empty() {
-            let edge: Option<Edge> = edges.pop();
-            if edge.is_none() {
-                break;
-            }
+        generate(grid, c, None, rng);
+    }
 
-            let (x, y, direction) = edge.unwrap();
-            let (nx, ny) = grid.get_next_cell_coords((x, y), direction).unwrap();
+    fn has_start_coords(&self) -> bool {
+        false
+    }
 
-            let node1 = NodeId(y * grid.width() + x);
-            let node2 = NodeId(ny * grid.width() + nx);
-            if !arena.connected(node1, node2) {
-                arena.connect(node1, node2);
-                grid.carve_passage((x, y), direction).unwrap();
-            }
-        }
+    fn supports_mask(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &'static str {
+        "Kruscal"
+    }
+}
+
+/// An implementation of the weighted Kruskal's algorithm. Does not support start coords.
+///
+/// Works like [`Kruskal`], except that the shuffled edges are sorted by weight before pulling
+/// them out, so ties are still broken at random.
+impl Algorithm for WeightedKruskal {
+    fn generate(&mut self, grid: &mut Grid, c: Option<Coords>, rng: &mut StdRng) {
+        generate(grid, c, Some(self.weight.as_ref()), rng);
     }
 
     fn has_start_coords(&self) -> bool {
         false
     }
 
+    fn supports_mask(&self) -> bool {
+        true
+    }
+
     fn name(&self) -> &'static str {
         "Kruscal"
     }
 }
 
+fn generate(
+    grid: &mut Grid,
+    c: Option<Coords>,
+    weight: Option<&dyn Fn(Coords, Coords) -> u32>,
+    rng: &mut StdRng,
+) {
+    if c.is_some() {
+        eprintln!("Algorithm `Kruscal` doesn't suppoer `start_coords`");
+    }
+    let mut arena = populate_arena(grid);
+    let mut edges: Edges = populate_edges(grid);
+    edges.shuffle(rng);
+    if let Some(weight) = weight {
+        // Edges are popped from the end, so the lightest ones go last
+        edges.sort_by_cached_key(|&(x, y, direction)| {
+            let next = grid.get_next_cell_coords((x, y), direction).unwrap();
+            std::cmp::Reverse(weight((x, y), next))
+        });
+    }
+
+    while !edges.is_empty() {
+        let edge: Option<Edge> = edges.pop();
+        if edge.is_none() {
+            break;
+        }
+
+        let (x, y, direction) = edge.unwrap();
+        let (nx, ny) = grid.get_next_cell_coords((x, y), direction).unwrap();
+
+        let node1 = NodeId(y * grid.width() + x);
+        let node2 = NodeId(ny * grid.width() + nx);
+        if !arena.connected(node1, node2) {
+            arena.connect(node1, node2);
+            grid.carve_passage((x, y), direction).unwrap();
+        }
+    }
+}
+
 fn populate_arena(grid: &Grid) -> ArenaTree {
     let mut arena = ArenaTree::new();
     for _ in 0..grid.width() * grid.height() {
@@ -80,13 +155,48 @@ fn populate_edges(grid: &Grid) -> Edges {
 
     for y in 0..grid.height() {
         for x in 0..grid.width() {
-            if y > 0 {
-                edges.push((x, y, Cell::NORTH));
+            if !grid.is_active((x, y)) {
+                continue;
+            }
+            if y > 0 && grid.is_active((x, y - 1)) {
+                edges.push((x, y, Direction::North));
             }
-            if x > 0 {
-                edges.push((x, y, Cell::WEST));
+            if x > 0 && grid.is_active((x - 1, y)) {
+                edges.push((x, y, Direction::West));
             }
         }
     }
     edges
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the number of passages between cells of the same row in mazes generated from a
+    /// number of seeds
+    fn horizontal_passages(mut algorithm: impl Algorithm) -> usize {
+        let mut count = 0;
+        for seed in 0..20 {
+            let mut grid = Grid::new(15, 15);
+            let mut rng = StdRng::seed_from_u64(seed);
+            algorithm.generate(&mut grid, None, &mut rng);
+            count += (0..15)
+                .flat_map(|y| (0..15).map(move |x| (x, y)))
+                .filter(|coords| grid.is_carved(*coords, Direction::East))
+                .count();
+        }
+        count
+    }
+
+    #[test]
+    fn weights_favor_horizontal_passages() {
+        let random = horizontal_passages(Kruskal);
+        let weighted =
+            horizontal_passages(Kruskal::weighted(Box::new(|a, b| u32::from(a.1 != b.1))));
+
+        // A spanning tree of 225 cells has 224 passages, about a half of them horizontal
+        assert!(random > 20 * 90 && random < 20 * 135, "{random}");
+        assert!(weighted > random * 3 / 2, "{weighted} vs {random}");
+    }
+}
@@ -2,6 +2,7 @@
 
 mod aldous_broder;
 mod binary_tree;
+mod dungeon;
 mod eller;
 mod growing_tree;
 mod hunt_and_kill;
@@ -10,17 +11,22 @@ mod prim;
 mod recursive_backtracking;
 mod recursive_division;
 mod sidewinder;
+mod weave;
+mod wilson;
 
-pub use aldous_broder::AldousBroder;
+pub use aldous_broder::{AldousBroder, AldousBroderFrom};
 pub use binary_tree::{Bias, BinaryTree};
-pub use eller::Eller;
+pub use dungeon::{Dungeon, Room};
+pub use eller::{Eller, EllerRows};
 pub use growing_tree::{GrowingTree, Method};
-pub use hunt_and_kill::HuntAndKill;
-pub use kruskal::Kruskal;
+pub use hunt_and_kill::{Corner, HuntAndKill};
+pub use kruskal::{Kruskal, WeightedKruskal};
 pub use prim::Prim;
 pub use recursive_backtracking::RecursiveBacktracking;
-pub use recursive_division::RecursiveDivision;
+pub use recursive_division::{RecursiveDivision, RecursiveDivisionWithRooms};
 pub use sidewinder::Sidewinder;
+pub use weave::Weave;
+pub use wilson::Wilson;
 
 use crate::{maze::grid::Grid, utils::types::Coords};
 use rand::rngs::StdRng;
@@ -28,7 +34,13 @@ use rand::rngs::StdRng;
 pub(super) const BOOL_TRUE_PROBABILITY: f64 = 0.5;
 
 /// A trait for generating a maze using a selected algorithm
-pub trait Algorithm {
+///
+/// Algorithms must implement `Clone` and own their data, i.e. be `'static`, which lets
+/// [`AlgorithmClone`] clone them behind a `Box<dyn Algorithm>`, so that builders holding boxed
+/// algorithms can be cloned as well. They must also be `Send`, so that clones of a builder can
+/// generate mazes on other threads. Deriving `Clone` is enough for an algorithm that only holds
+/// its options, and [`AlgorithmClone`] is then implemented automatically.
+pub trait Algorithm: AlgorithmClone + Send {
     /// Runs algorithm through the given Grid object, thus mutating the grid and generating a new
     /// maze.
     fn generate(&mut self, grid: &mut Grid, start_coords: Option<Coords>, rng: &mut StdRng);
@@ -36,7 +48,33 @@ pub trait Algorithm {
     /// Verifies if algorithm supports start coords
     fn has_start_coords(&self) -> bool;
 
+    /// Verifies if algorithm supports masks, i.e. never carves into masked-off cells
+    fn supports_mask(&self) -> bool {
+        false
+    }
+
     // Cannot be a const because of dyn-trait compatibility
     /// Algorithm name
     fn name(&self) -> &'static str;
 }
+
+/// A trait for cloning boxed algorithms
+///
+/// It's implemented for every [`Algorithm`] that implements `Clone`, so it never has to be
+/// implemented manually.
+pub trait AlgorithmClone {
+    /// Returns a boxed copy of the algorithm
+    fn clone_box(&self) -> Box<dyn Algorithm>;
+}
+
+impl<T: Algorithm + Clone + 'static> AlgorithmClone for T {
+    fn clone_box(&self) -> Box<dyn Algorithm> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Algorithm> {
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
@@ -1,9 +1,9 @@
 use rand::prelude::*;
-use std::vec;
+use std::{cmp::Reverse, collections::BinaryHeap, sync::Arc, vec};
 
 use super::Algorithm;
 use crate::{
-    maze::grid::{Grid, cell::Cell},
+    maze::grid::{Grid, cell::Cell, direction::Direction, topology::Topology},
     utils::types::Coords,
 };
 
@@ -15,62 +15,112 @@ use crate::{
 /// Mazes generated by Prim’s algorithm share many of the characteristics of those created
 /// via Kruskal’s algorithm, such as having an abundance of short cul-de-sacs which makes
 /// the maze harder to puzzle out at a glance
+///
+/// By default, the simplified version of the algorithm is used, which picks frontier cells
+/// uniformly at random. [`Prim::weighted`] returns the "true" version, which assigns random
+/// weights to all the passages up front and always carves the lightest one next to the maze. Its
+/// mazes grow more unevenly, with long meandering passages among the short cul-de-sacs.
+#[derive(Clone)]
 pub struct Prim {
     frontiers: Vec<Coords>,
+    elevation: Option<Arc<dyn Fn(Coords) -> f64 + Send + Sync>>,
+    weighted: bool,
 }
 
 impl Prim {
     /// Create a new instance of the algorithm with an empty set of the frontier cells
     #[must_use]
     pub const fn new() -> Self {
-        Self { frontiers: vec![] }
+        Self {
+            frontiers: vec![],
+            elevation: None,
+            weighted: false,
+        }
     }
 
-    fn mark(&mut self, coords: Coords, grid: &mut Grid) {
-        grid.mark_cell(coords);
-
-        let (x, y) = coords;
-        self.add_frontier((x + 1, y), grid);
-        self.add_frontier((x, y + 1), grid);
-        if x > 0 {
-            self.add_frontier((x - 1, y), grid);
-        }
-        if y > 0 {
-            self.add_frontier((x, y - 1), grid);
+    /// Create a new instance of the "true" weighted version of the algorithm
+    ///
+    /// Every passage gets a random weight and the lightest passage between the maze and a
+    /// frontier cell is carved on each step, which makes the maze a minimum spanning tree of the
+    /// weighted grid.
+    #[must_use]
+    pub const fn weighted() -> Self {
+        Self {
+            frontiers: vec![],
+            elevation: None,
+            weighted: true,
         }
     }
 
-    fn add_frontier(&mut self, (x, y): Coords, grid: &mut Grid) {
-        if x < grid.width()
-            && y < grid.height()
-            && !grid.is_cell_marked((x, y))
-            && !self.frontiers.contains(&(x, y))
-        {
-            self.frontiers.push((x, y));
+    /// Create a new instance of the algorithm that prefers carving into cells with a lower
+    /// elevation
+    ///
+    /// Instead of picking frontier cells uniformly, each frontier cell is picked with a
+    /// probability proportional to `e^(lowest - elevation)`, where `lowest` is the lowest
+    /// elevation among the current frontier cells. As a result, passages tend to fill the
+    /// valleys of the elevation field first. Elevations are expected to be finite, otherwise the
+    /// frontier cells are no longer picked by their weights.
+    #[must_use]
+    pub fn with_elevation(elevation: Box<dyn Fn(Coords) -> f64 + Send + Sync>) -> Self {
+        Self {
+            frontiers: vec![],
+            elevation: Some(Arc::from(elevation)),
+            weighted: false,
         }
     }
 
-    #[expect(clippy::unused_self)]
-    fn neighbours(&self, (x, y): Coords, grid: &mut Grid) -> Vec<Coords> {
-        let mut neighbours = vec![];
+    fn frontier_index(&self, rng: &mut StdRng) -> usize {
+        let Some(elevation) = &self.elevation else {
+            return rng.random_range(0..self.frontiers.len());
+        };
 
-        if x > 0 && grid.is_cell_marked((x - 1, y)) {
-            neighbours.push((x - 1, y));
-        }
+        let elevations: Vec<f64> = self.frontiers.iter().map(|c| elevation(*c)).collect();
+        let lowest = elevations.iter().copied().fold(f64::INFINITY, f64::min);
+        let weights: Vec<f64> = elevations.iter().map(|e| (lowest - e).exp()).collect();
 
-        if x + 1 < grid.width() && grid.is_cell_marked((x + 1, y)) {
-            neighbours.push((x + 1, y));
+        let mut target = rng.random::<f64>() * weights.iter().sum::<f64>();
+        for (index, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                return index;
+            }
+            target -= weight;
         }
+        weights.len() - 1
+    }
 
-        if y > 0 && grid.is_cell_marked((x, y - 1)) {
-            neighbours.push((x, y - 1));
-        }
+    /// Generates a minimum spanning tree of the grid with random passage weights
+    ///
+    /// Weights are drawn when a passage first joins the maze with its frontier. Each passage is
+    /// pushed only once, so this is the same as weighting all the passages before generating.
+    fn generate_weighted(grid: &mut Grid, start_coords: Coords, rng: &mut StdRng) {
+        let mut passages = BinaryHeap::new();
+        let mut push_passages = |coords: Coords, grid: &mut Grid, passages: &mut BinaryHeap<_>| {
+            grid.mark_cell(coords);
+            for (dir, next) in grid.neighbor_coords(coords) {
+                if !grid.is_cell_marked(next) {
+                    passages.push(Reverse((rng.random::<u64>(), coords, dir, next)));
+                }
+            }
+        };
 
-        if y + 1 < grid.height() && grid.is_cell_marked((x, y + 1)) {
-            neighbours.push((x, y + 1));
+        push_passages(start_coords, grid, &mut passages);
+        while let Some(Reverse((_, from, dir, to))) = passages.pop() {
+            if grid.is_cell_marked(to) {
+                continue;
+            }
+            grid.carve_passage(from, dir).unwrap();
+            push_passages(to, grid, &mut passages);
         }
+    }
+
+    fn mark(&mut self, coords: Coords, grid: &mut Grid) {
+        grid.mark_cell(coords);
 
-        neighbours
+        for (_, next) in neighbors(grid, coords, FRONTIER_ORDER) {
+            if !grid.is_cell_marked(next) && !self.frontiers.contains(&next) {
+                self.frontiers.push(next);
+            }
+        }
     }
 }
 
@@ -102,21 +152,24 @@ impl Default for Prim {
 impl Algorithm for Prim {
     fn generate(&mut self, grid: &mut Grid, start_coords: Option<Coords>, rng: &mut StdRng) {
         let start_coords = start_coords.unwrap_or_else(|| get_rand_coords(grid, rng));
+        if self.weighted {
+            Self::generate_weighted(grid, start_coords, rng);
+            return;
+        }
 
         self.mark(start_coords, grid);
 
         while !self.frontiers.is_empty() {
-            let index = rng.random_range(0..self.frontiers.len());
+            let index = self.frontier_index(rng);
             let coords = self.frontiers.remove(index);
 
-            let neighbours = self.neighbours(coords, grid);
+            let neighbours: Vec<Cell> = neighbors(grid, coords, NEIGHBOUR_ORDER)
+                .into_iter()
+                .filter(|(_, next)| grid.is_cell_marked(*next))
+                .map(|(dir, _)| dir)
+                .collect();
 
-            let index = rng.random_range(0..neighbours.len());
-            let (nx, ny) = neighbours[index];
-
-            let (x, y) = coords;
-
-            let dir = direction(x, y, nx, ny);
+            let dir = neighbours[rng.random_range(0..neighbours.len())];
             grid.carve_passage(coords, dir).unwrap();
             self.mark(coords, grid);
         }
@@ -126,42 +179,117 @@ impl Algorithm for Prim {
         true
     }
 
+    fn supports_mask(&self) -> bool {
+        true
+    }
+
     fn name(&self) -> &'static str {
         "Prim"
     }
 }
 
+/// Order in which the neighbors of a cell added to an orthogonal maze join the frontier
+const FRONTIER_ORDER: [Direction; 4] = [
+    Direction::East,
+    Direction::South,
+    Direction::West,
+    Direction::North,
+];
+/// Order in which the maze neighbors of an orthogonal frontier cell are picked from
+const NEIGHBOUR_ORDER: [Direction; 4] = [
+    Direction::West,
+    Direction::East,
+    Direction::North,
+    Direction::South,
+];
+
+/// Returns the neighbors of a cell, in a given order on an orthogonal grid
+///
+/// The random picks depend on the order of the cells, so orthogonal mazes keep the order of the
+/// original implementation to generate the same maze from the same seed.
+fn neighbors(grid: &Grid, coords: Coords, order: [Direction; 4]) -> Vec<(Cell, Coords)> {
+    if grid.topology() != Topology::Orthogonal {
+        return grid.neighbor_coords(coords);
+    }
+    order
+        .into_iter()
+        .filter_map(|dir| {
+            grid.get_next_cell_coords(coords, dir)
+                .ok()
+                .map(|next| (dir.into(), next))
+        })
+        .collect()
+}
+
 fn get_rand_coords(grid: &Grid, rng: &mut impl Rng) -> Coords {
+    if grid.is_masked() {
+        return grid.random_active_cell(rng).unwrap_or((0, 0));
+    }
     let x = rng.random_range(0..grid.width());
     let y = rng.random_range(0..grid.height());
     (x, y)
 }
 
-fn direction(x: usize, y: usize, nx: usize, ny: usize) -> Cell {
-    if x < nx {
-        return Cell::EAST;
-    }
-    if x > nx {
-        return Cell::WEST;
-    }
-    if y < ny {
-        return Cell::SOUTH;
-    }
-    if y > ny {
-        return Cell::NORTH;
-    }
-
-    unreachable!("The x and y coordinates are never equal to nx and ny")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::maze::formatters::{AsciiNarrow, Formatter};
 
     #[test]
     fn default_call() {
         let algo = Prim::default();
         let v: Vec<Coords> = vec![];
         assert_eq!(v, algo.frontiers);
+        assert!(algo.elevation.is_none());
+        assert!(!algo.weighted);
+    }
+
+    #[test]
+    fn weighted_generates_a_spanning_tree() {
+        for seed in [0, 1, 42] {
+            let mut grid = Grid::new(12, 9);
+            Prim::weighted().generate(&mut grid, None, &mut StdRng::seed_from_u64(seed));
+
+            let passages: usize = (0..9)
+                .flat_map(|y| (0..12).map(move |x| (x, y)))
+                .map(|coords| grid.steps(coords).count())
+                .sum();
+            assert_eq!(grid.reachable_cells((0, 0)).len(), 12 * 9);
+            // A spanning tree has exactly one passage less than cells, each counted from both sides
+            assert_eq!(passages, 2 * (12 * 9 - 1));
+        }
+    }
+
+    #[test]
+    fn seeded_orthogonal_maze_is_stable() {
+        let mut expected = String::new();
+        expected.push_str(" ___________ \n");
+        expected.push_str("|_   _   _| |\n");
+        expected.push_str("| |  _|___  |\n");
+        expected.push_str("|    _| | |_|\n");
+        expected.push_str("|_|_________|\n");
+
+        let mut grid = Grid::new(6, 4);
+        Prim::new().generate(&mut grid, None, &mut StdRng::seed_from_u64(42));
+
+        // Changing the order of the frontier or neighbor cells changes the maze of a given seed
+        assert_eq!(AsciiNarrow.format(&grid).0, expected);
+    }
+
+    #[test]
+    fn elevation_favors_low_cells() {
+        let mut algo = Prim::with_elevation(Box::new(|(x, _)| x as f64));
+        algo.frontiers = vec![(0, 0), (2, 0), (1, 0)];
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let mut picks = [0; 3];
+        for _ in 0..1000 {
+            picks[algo.frontier_index(&mut rng)] += 1;
+        }
+
+        // Weights are 1, e^-2 and e^-1, so the lowest cell is picked most of the time
+        assert!(picks[0] > picks[2]);
+        assert!(picks[2] > picks[1]);
+        assert!(picks[0] > 500);
     }
 }
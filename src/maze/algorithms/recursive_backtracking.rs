@@ -1,18 +1,50 @@
 use super::Algorithm;
-use crate::maze::grid::Grid;
+use crate::maze::grid::{Grid, cell::Cell, polar::PolarGrid};
 use crate::utils::types::Coords;
 use rand::prelude::*;
+use std::collections::HashSet;
 
 /// The "Recursive Backtracking" algorithm for generating mazes
 ///
 /// This algorithm quite effectively creates narrow passages with multiple dead-ends which makes it
 /// easy to get lost, thus eventually making it hard to solve the maze.
 ///
-/// In most cases, this algorithm is fast. However, due to its recursive nature, it requires stack
-/// space proportional to the longest acyclic path, which is, in the worst case, the entire maze. So
-/// for exceptionally large mazes this algorithm can be fairly inefficient.
+/// In most cases, this algorithm is fast. However, it has to remember every cell of the longest
+/// acyclic path, which is, in the worst case, the entire maze. So for exceptionally large mazes this
+/// algorithm requires plenty of memory.
+#[derive(Clone)]
 pub struct RecursiveBacktracking;
 
+impl RecursiveBacktracking {
+    /// Carves passages of a polar grid the same way as of a rectangular one
+    ///
+    /// The backtracking is done with an explicit stack, as the outer rings of a polar grid can
+    /// hold many more cells than the rows of a rectangular one.
+    pub(crate) fn generate_polar(grid: &mut PolarGrid, start_coords: Coords, rng: &mut StdRng) {
+        let mut visited = HashSet::from([start_coords]);
+        let mut stack = vec![start_coords];
+
+        while let Some(&coords) = stack.last() {
+            let unvisited: Vec<Coords> = grid
+                .neighbors(coords)
+                .into_iter()
+                .filter(|next| !visited.contains(next))
+                .collect();
+
+            match unvisited.choose(rng) {
+                Some(&next) => {
+                    grid.link(coords, next);
+                    visited.insert(next);
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+}
+
 /// An implementation of the "Recursive Backtracking" algorithm for generating mazes.
 ///
 /// Here is how it works:
@@ -29,7 +61,9 @@ pub struct RecursiveBacktracking;
 ///    point.
 impl Algorithm for RecursiveBacktracking {
     fn generate(&mut self, grid: &mut Grid, start_coords: Option<Coords>, rng: &mut StdRng) {
-        let start_coords = start_coords.unwrap_or((0, 0));
+        let start_coords = start_coords
+            .or_else(|| grid.first_active_cell())
+            .unwrap_or((0, 0));
         carve_passages_from(start_coords, grid, rng);
     }
 
@@ -37,16 +71,31 @@ impl Algorithm for RecursiveBacktracking {
         true
     }
 
+    fn supports_mask(&self) -> bool {
+        true
+    }
+
     fn name(&self) -> &'static str {
         "RecursiveBacktracking"
     }
 }
 
-fn carve_passages_from(coords: Coords, grid: &mut Grid, rng: &mut impl Rng) {
-    let mut dirs = grid.directions().to_vec();
-    dirs.shuffle(rng);
+/// Carves passages depth-first from a given cell
+///
+/// The recursion is unrolled into an explicit stack of cells, each with its shuffled directions and
+/// the index of the next one to try, so huge mazes don't overflow the call stack. The RNG is used
+/// in the same order as by a recursive implementation, which keeps the mazes of a seed unchanged.
+pub(super) fn carve_passages_from(coords: Coords, grid: &mut Grid, rng: &mut impl Rng) {
+    let mut stack = vec![(coords, shuffled_directions(grid, rng), 0)];
+
+    while let Some((coords, dirs, index)) = stack.last_mut() {
+        let Some(&dir) = dirs.get(*index) else {
+            stack.pop();
+            continue;
+        };
+        *index += 1;
+        let coords = *coords;
 
-    for dir in dirs {
         let Ok(next) = grid.get_next_cell_coords(coords, dir) else {
             continue;
         };
@@ -55,7 +104,36 @@ fn carve_passages_from(coords: Coords, grid: &mut Grid, rng: &mut impl Rng) {
         }
 
         if let Ok(next) = grid.carve_passage(coords, dir) {
-            carve_passages_from(next, grid, rng);
+            stack.push((next, shuffled_directions(grid, rng), 0));
         }
     }
 }
+
+fn shuffled_directions(grid: &Grid, rng: &mut impl Rng) -> Vec<Cell> {
+    let mut dirs = grid.directions().to_vec();
+    dirs.shuffle(rng);
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    #[ignore = "slow in debug builds, run with `cargo test -- --ignored`"]
+    fn huge_maze_does_not_overflow_stack() {
+        let (width, height) = (1000, 1000);
+        let mut grid = Grid::new(width, height);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        RecursiveBacktracking.generate(&mut grid, None, &mut rng);
+
+        // A perfect maze has a passage per cell but the first one, and every passage opens two walls
+        let open_walls: u32 = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|coords| grid[coords].bits().count_ones())
+            .sum();
+        assert_eq!(open_walls as usize, 2 * (width * height - 1));
+    }
+}
@@ -1,6 +1,6 @@
 use super::{Algorithm, BOOL_TRUE_PROBABILITY};
 use crate::{
-    maze::grid::{Grid, cell::Cell},
+    maze::grid::{Grid, direction::Direction},
     utils::types::Coords,
 };
 use rand::prelude::*;
@@ -36,41 +36,99 @@ enum Orientation {
 /// and bottlenecks like a single passage between two sections that effectively divide the entire
 /// maze into two distinct regions, thus making it easy to spot the passage and work backward to a
 /// solution.
+///
+/// Optionally, small subfields may be left undivided as open rooms, see
+/// [`RecursiveDivision::with_rooms`].
+#[derive(Clone)]
 pub struct RecursiveDivision;
 
+/// The "Recursive Division" algorithm leaving small subfields undivided as open rooms, which makes
+/// dungeon-like layouts
+///
+/// Created with [`RecursiveDivision::with_rooms`].
+#[derive(Clone)]
+pub struct RecursiveDivisionWithRooms {
+    min_size: usize,
+    chance: f64,
+}
+
 impl RecursiveDivision {
-    fn divide(grid: &mut Grid, x: usize, y: usize, ax: usize, ay: usize, rng: &mut impl Rng) {
+    /// Create a new instance of the algorithm that leaves open rooms
+    ///
+    /// A subfield that is at most `min_size` cells wide and tall is left undivided, with all the
+    /// passages within it carved, with a probability of `room_chance`. The probability is clamped
+    /// to `[0, 1]`, and `NaN` is treated as `0`.
+    #[must_use]
+    pub const fn with_rooms(min_size: usize, room_chance: f64) -> RecursiveDivisionWithRooms {
+        let chance = if room_chance.is_nan() {
+            0.0
+        } else {
+            room_chance.clamp(0.0, 1.0)
+        };
+        RecursiveDivisionWithRooms { min_size, chance }
+    }
+}
+
+/// State of a division of the grid shared by the recursive calls
+struct Division<'a, R: Rng> {
+    rooms: Option<&'a RecursiveDivisionWithRooms>,
+    rng: &'a mut R,
+}
+
+impl<R: Rng> Division<'_, R> {
+    fn is_room(&mut self, w: usize, h: usize) -> bool {
+        self.rooms.is_some_and(|rooms| {
+            w <= rooms.min_size && h <= rooms.min_size && self.rng.random_bool(rooms.chance)
+        })
+    }
+
+    fn divide(&mut self, grid: &mut Grid, x: usize, y: usize, ax: usize, ay: usize) {
         // Calculate subfield width
         let w = ax - x + 1;
         // Calculate subfield height
         let h = ay - y + 1;
 
+        if w >= 2 && h >= 2 && self.is_room(w, h) {
+            // Carve every passage within the subfield to leave it as an open room
+            for cy in y..=ay {
+                for cx in x..=ax {
+                    if cx < ax {
+                        grid.carve_passage((cx, cy), Direction::East).unwrap();
+                    }
+                    if cy < ay {
+                        grid.carve_passage((cx, cy), Direction::South).unwrap();
+                    }
+                }
+            }
+            return;
+        }
+
         if w < 2 || h < 2 {
             if w > 1 {
                 // Carve passages till the horizontal end of the subfield
                 for cx in x..ax {
-                    grid.carve_passage((cx, y), Cell::EAST).unwrap();
+                    grid.carve_passage((cx, y), Direction::East).unwrap();
                 }
             } else if h > 1 {
                 // Carve passages till the vertical end of the subfield
                 for cy in y..ay {
-                    grid.carve_passage((x, cy), Cell::SOUTH).unwrap();
+                    grid.carve_passage((x, cy), Direction::South).unwrap();
                 }
             }
             return;
         }
 
         // Which way a subfield with the given dimensions ought to be bisected
-        let orientation = choose_orientation(w, h, rng);
+        let orientation = choose_orientation(w, h, self.rng);
 
         // Get X and Y coordinates of a cell where a passage will be carved
-        let px = rng.random_range(x..ax);
-        let py = rng.random_range(y..ay);
+        let px = self.rng.random_range(x..ax);
+        let py = self.rng.random_range(y..ay);
 
         // Define what direction is corresponding to the wall orientation
         let dir = match orientation {
-            Orientation::Horizontal => Cell::SOUTH,
-            Orientation::Vertical => Cell::EAST,
+            Orientation::Horizontal => Direction::South,
+            Orientation::Vertical => Direction::East,
         };
 
         // Carve passage
@@ -80,15 +138,15 @@ impl RecursiveDivision {
         match orientation {
             Orientation::Horizontal => {
                 // Top subfield
-                Self::divide(grid, x, y, ax, py, rng);
+                self.divide(grid, x, y, ax, py);
                 // Bottom subfield
-                Self::divide(grid, x, ny, ax, ay, rng);
+                self.divide(grid, x, ny, ax, ay);
             }
             Orientation::Vertical => {
                 // Left subfield
-                Self::divide(grid, x, y, px, ay, rng);
+                self.divide(grid, x, y, px, ay);
                 // Right subfield
-                Self::divide(grid, nx, y, ax, ay, rng);
+                self.divide(grid, nx, y, ax, ay);
             }
         }
     }
@@ -114,12 +172,26 @@ impl RecursiveDivision {
 /// The `generate` function will warn in case a [`start_coords`] is passed.
 impl Algorithm for RecursiveDivision {
     fn generate(&mut self, grid: &mut Grid, c: Option<Coords>, rng: &mut StdRng) {
-        if c.is_some() {
-            eprintln!("Algorithm `{}` doesn't suppoer `start_coords`", self.name());
-        }
-        let width = grid.width();
-        let height = grid.height();
-        Self::divide(grid, 0, 0, width - 1, height - 1, rng);
+        generate(grid, c, None, rng);
+    }
+
+    fn has_start_coords(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> &'static str {
+        "RecursiveDivision"
+    }
+}
+
+/// An implementation of the "Recursive Division" algorithm with rooms. Does not support start
+/// position.
+///
+/// Works like [`RecursiveDivision`], except that step #2 may leave a small enough field undivided
+/// with all its walls removed.
+impl Algorithm for RecursiveDivisionWithRooms {
+    fn generate(&mut self, grid: &mut Grid, c: Option<Coords>, rng: &mut StdRng) {
+        generate(grid, c, Some(self), rng);
     }
 
     fn has_start_coords(&self) -> bool {
@@ -131,6 +203,20 @@ impl Algorithm for RecursiveDivision {
     }
 }
 
+fn generate(
+    grid: &mut Grid,
+    c: Option<Coords>,
+    rooms: Option<&RecursiveDivisionWithRooms>,
+    rng: &mut StdRng,
+) {
+    if c.is_some() {
+        eprintln!("Algorithm `RecursiveDivision` doesn't suppoer `start_coords`");
+    }
+    let width = grid.width();
+    let height = grid.height();
+    Division { rooms, rng }.divide(grid, 0, 0, width - 1, height - 1);
+}
+
 fn choose_orientation(width: usize, height: usize, rng: &mut impl Rng) -> Orientation {
     if width < height {
         return Orientation::Horizontal;
@@ -146,3 +232,64 @@ fn choose_orientation(width: usize, height: usize, rng: &mut impl Rng) -> Orient
         Orientation::Horizontal
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::grid::cell::Cell;
+
+    /// Returns the number of 2x2 blocks of cells without any walls within them
+    fn open_blocks(grid: &Grid) -> usize {
+        let mut count = 0;
+        for y in 0..grid.height() - 1 {
+            for x in 0..grid.width() - 1 {
+                if grid.is_carved((x, y), Cell::EAST)
+                    && grid.is_carved((x, y), Cell::SOUTH)
+                    && grid.is_carved((x + 1, y + 1), Cell::NORTH)
+                    && grid.is_carved((x + 1, y + 1), Cell::WEST)
+                {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    fn generate(mut algorithm: impl Algorithm, seed: u64) -> Grid {
+        let mut grid = Grid::new(12, 12);
+        let mut rng = StdRng::seed_from_u64(seed);
+        algorithm.generate(&mut grid, None, &mut rng);
+        grid
+    }
+
+    #[test]
+    fn rooms_always_appear_with_chance_one() {
+        for seed in 0..10 {
+            let grid = generate(RecursiveDivision::with_rooms(3, 1.0), seed);
+            assert!(open_blocks(&grid) > 0);
+            assert_eq!(grid.reachable_cells((0, 0)).len(), 144);
+        }
+    }
+
+    #[test]
+    fn rooms_never_appear_with_chance_zero() {
+        for seed in 0..10 {
+            let grid = generate(RecursiveDivision::with_rooms(3, 0.0), seed);
+            assert_eq!(open_blocks(&grid), 0);
+            assert_eq!(open_blocks(&generate(RecursiveDivision, seed)), 0);
+        }
+    }
+
+    #[test]
+    fn room_chance_is_clamped() {
+        for chance in [-1.0, 2.0, f64::NAN] {
+            let grid = generate(RecursiveDivision::with_rooms(3, chance), 0);
+            assert_eq!(grid.reachable_cells((0, 0)).len(), 144);
+        }
+        assert!(open_blocks(&generate(RecursiveDivision::with_rooms(3, 2.0), 0)) > 0);
+        assert_eq!(
+            open_blocks(&generate(RecursiveDivision::with_rooms(3, f64::NAN), 0)),
+            0
+        );
+    }
+}
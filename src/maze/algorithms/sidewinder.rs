@@ -1,6 +1,6 @@
 use super::Algorithm;
 use crate::{
-    maze::grid::{Grid, cell::Cell},
+    maze::grid::{Grid, direction::Direction},
     utils::types::Coords,
 };
 use rand::prelude::*;
@@ -10,6 +10,7 @@ use rand::prelude::*;
 /// It’s closely related to the "Binary Tree" algorithm, but manages to get away with
 /// only one side being spanned by a passage, instead of two. Space- and performance-wise,
 /// the algorithm is quite efficient since it looks at one row at a time.
+#[derive(Clone)]
 pub struct Sidewinder;
 
 /// An implementation of the "Sidewinder" algorithm for generating mazes.
@@ -48,10 +49,10 @@ impl Algorithm for Sidewinder {
                 let carve_east: bool = rng.random();
 
                 if y == 0 || (carve_east && x + 1 < grid.width()) {
-                    grid.carve_passage((x, y), Cell::EAST).ok();
+                    grid.carve_passage((x, y), Direction::East).ok();
                 } else {
                     let rand_x = rng.random_range(run_start..=x);
-                    grid.carve_passage((rand_x, y), Cell::NORTH).ok();
+                    grid.carve_passage((rand_x, y), Direction::North).ok();
                     run_start = x + 1;
                 }
             }
@@ -0,0 +1,116 @@
+use super::Algorithm;
+use crate::maze::grid::{Grid, cell::Cell, overpass::Overpass};
+use crate::utils::types::Coords;
+use rand::prelude::*;
+
+/// The "Weave" algorithm for generating mazes with corridors crossing over and under each other
+///
+/// It's a recursive backtracker that may tunnel under a straight corridor it runs into, as long as
+/// the cell on the other side hasn't been visited yet. Crossings are recorded as
+/// [`Overpass`](crate::maze::Overpass)es, which the pathfinding helpers of a maze take into account.
+///
+/// Only the [`Image`](crate::maze::Image) formatter draws a crossing as a gap in the lower
+/// corridor. The other formatters, as well as the statistics based on open passages, like
+/// [`ends`](crate::maze::OrthogonalMaze::ends), see a crossing as a four-way intersection.
+#[derive(Clone)]
+pub struct Weave {
+    density: f64,
+}
+
+impl Weave {
+    /// Returns a new instance of the algorithm that tunnels under half of the corridors it can
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { density: 0.5 }
+    }
+
+    /// Sets the probability to tunnel under a corridor whenever possible and returns itself
+    ///
+    /// `0.0` generates a regular maze, while `1.0` tunnels at every chance.
+    #[must_use]
+    pub const fn density(mut self, density: f64) -> Self {
+        self.density = density.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Carves a passage from a given cell in a given direction and returns the cell it leads to,
+    /// or `None` if the passage can't be carved
+    fn step(&self, grid: &mut Grid, coords: Coords, dir: Cell, rng: &mut StdRng) -> Option<Coords> {
+        let next = grid.get_next_cell_coords(coords, dir).ok()?;
+        if !grid.is_cell_visited(next) {
+            return grid.carve_passage(coords, dir).ok();
+        }
+
+        // A visited neighbor can only be tunneled under if it's a straight corridor running
+        // across the direction and the cell behind it is still unvisited
+        let over = Overpass::across(dir);
+        let beyond = grid.get_next_cell_coords(next, dir).ok()?;
+        if grid[next] != over.passages()
+            || grid.is_cell_visited(beyond)
+            || !rng.random_bool(self.density)
+        {
+            return None;
+        }
+
+        grid.carve_passage(coords, dir).ok()?;
+        grid.carve_passage(next, dir).ok()?;
+        grid.set_overpass(next, over);
+        Some(beyond)
+    }
+}
+
+impl Default for Weave {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implementation of the "Weave" algorithm for generating mazes.
+///
+/// Here is how it works:
+///
+/// 1. Chooses a starting point in the field.
+///
+/// 2. Randomly chooses a wall at that point and carves a passage through to the adjacent cell, if
+///    it hasn't been visited yet. If it has, but it's a straight corridor running across the
+///    direction with an unvisited cell behind it, it may carve a tunnel under the corridor to that
+///    cell instead. This becomes the new current cell.
+///
+/// 3. If no passage can be carved, backs up to the previous cell and repeats.
+///
+/// 4. The algorithm ends when the process has backed all the way up to the starting point.
+impl Algorithm for Weave {
+    fn generate(&mut self, grid: &mut Grid, start_coords: Option<Coords>, rng: &mut StdRng) {
+        let start_coords = start_coords
+            .or_else(|| grid.first_active_cell())
+            .unwrap_or((0, 0));
+        let mut stack = vec![start_coords];
+
+        while let Some(&coords) = stack.last() {
+            let mut dirs = grid.directions().to_vec();
+            dirs.shuffle(rng);
+
+            match dirs
+                .into_iter()
+                .find_map(|dir| self.step(grid, coords, dir, rng))
+            {
+                Some(next) => stack.push(next),
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    fn has_start_coords(&self) -> bool {
+        true
+    }
+
+    fn supports_mask(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &'static str {
+        "Weave"
+    }
+}
@@ -0,0 +1,82 @@
+use super::Algorithm;
+use crate::maze::grid::{Grid, cell::Cell};
+use crate::utils::types::Coords;
+use rand::prelude::*;
+
+/// The Wilson's algorithm for generating mazes
+///
+/// Like [`AldousBroder`](super::AldousBroder), it generates uniform spanning trees, i.e. all the
+/// possible mazes are equally likely, so the resulting mazes have no bias. Unlike Aldous-Broder,
+/// it is slow at the beginning, when the maze consists of a single cell, and speeds up as the maze
+/// grows.
+#[derive(Clone)]
+pub struct Wilson;
+
+/// An implementation of Wilson's algorithm for generating mazes.
+///
+/// It's based on loop-erased random walks. Here is how it works:
+///
+/// 1. Adds the start cell to the maze.
+///
+/// 2. Chooses any cell that is not in the maze yet and performs a random walk from it until a cell
+///    in the maze is reached. Whenever the walk crosses its own path, the formed loop is erased.
+///
+/// 3. Carves the loop-erased path into the maze.
+///
+/// 4. Repeats steps 2 and 3 until all the cells are added to the maze.
+impl Algorithm for Wilson {
+    fn generate(&mut self, grid: &mut Grid, start_coords: Option<Coords>, rng: &mut StdRng) {
+        let mut cells: Vec<Coords> = (0..grid.height())
+            .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+            .filter(|coords| grid.is_active(*coords))
+            .collect();
+        let Some(start) = start_coords.or_else(|| cells.choose(rng).copied()) else {
+            return;
+        };
+
+        let width = grid.width();
+        let index = |(x, y): Coords| y * width + x;
+        let mut in_maze = vec![false; width * grid.height()];
+        in_maze[index(start)] = true;
+
+        // The direction the walk last left each cell in, so revisiting a cell erases the loop
+        let mut exits: Vec<Option<Cell>> = vec![None; width * grid.height()];
+
+        cells.shuffle(rng);
+        for cell in cells {
+            if in_maze[index(cell)] {
+                continue;
+            }
+
+            let mut current = cell;
+            while !in_maze[index(current)] {
+                let Some((dir, next)) = grid.neighbor_coords(current).choose(rng).copied() else {
+                    break;
+                };
+                exits[index(current)] = Some(dir);
+                current = next;
+            }
+
+            let mut current = cell;
+            while !in_maze[index(current)] {
+                in_maze[index(current)] = true;
+                let Some(dir) = exits[index(current)] else {
+                    break;
+                };
+                current = grid.carve_passage(current, dir).unwrap();
+            }
+        }
+    }
+
+    fn has_start_coords(&self) -> bool {
+        true
+    }
+
+    fn supports_mask(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &'static str {
+        "Wilson"
+    }
+}
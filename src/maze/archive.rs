@@ -0,0 +1,276 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    OrthogonalMaze,
+    config::GenerationConfig,
+    errors::{BuildError, MazeLoadError, MazeSaveError},
+    formatters::JsonMaze,
+    grid::{cell::Cell, overpass::Overpass},
+};
+use crate::utils::types::Coords;
+
+/// A generated maze stored together with the config it was generated from
+///
+/// Archives are saved as JSON, which makes it possible to regenerate the maze from its config or
+/// audit how it was created. The maze is stored with the same schema as the
+/// [`Json`](super::Json) formatter plus the crossings of weave mazes, so archives don't depend on
+/// the enabled features, and it's validated when an archive is loaded.
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(try_from = "ArchiveData", into = "ArchiveData")]
+pub struct MazeArchive {
+    config: GenerationConfig,
+    maze: OrthogonalMaze,
+}
+
+impl MazeArchive {
+    /// Returns a new archive for a given config and maze
+    ///
+    /// The maze is regenerated from the config to make sure the archive can be audited later.
+    /// Only the passages, the mask and the crossings of the mazes are compared, so a maze loaded
+    /// from a file or carrying a [`RenderScale`](super::RenderScale) is accepted as well.
+    ///
+    /// # Errors
+    /// Returns a [`BuildError`] if the config has no seed or doesn't regenerate the maze, e.g.
+    /// because the maze was generated with options a [`GenerationConfig`] can't express
+    pub fn new(config: GenerationConfig, maze: OrthogonalMaze) -> Result<Self, BuildError> {
+        if config.seed.is_none() {
+            return Err(BuildError::reason(
+                "An archived config needs a seed to regenerate the maze",
+            ));
+        }
+        if !same_maze(&config.build()?, &maze) {
+            return Err(BuildError::reason(
+                "The config doesn't regenerate the archived maze",
+            ));
+        }
+
+        Ok(Self { config, maze })
+    }
+
+    /// Returns the parameters the maze was generated with
+    #[must_use]
+    pub const fn config(&self) -> &GenerationConfig {
+        &self.config
+    }
+
+    /// Returns the generated maze
+    #[must_use]
+    pub const fn maze(&self) -> &OrthogonalMaze {
+        &self.maze
+    }
+}
+
+/// Archives are compared by their configs and the passages, masks and crossings of their mazes
+impl PartialEq for MazeArchive {
+    fn eq(&self, other: &Self) -> bool {
+        self.config == other.config && same_maze(&self.maze, &other.maze)
+    }
+}
+
+/// Returns `true` if two mazes have the same passages, mask and crossings
+fn same_maze(maze: &OrthogonalMaze, other: &OrthogonalMaze) -> bool {
+    let (grid, other_grid) = (maze.grid(), other.grid());
+    (maze.width(), maze.height()) == (other.width(), other.height())
+        && maze.iter().eq(other.iter())
+        && grid.mask() == other_grid.mask()
+        && maze
+            .iter()
+            .all(|(coords, _)| grid.overpass(coords) == other_grid.overpass(coords))
+}
+
+/// The JSON schema of an archive
+#[derive(Serialize, Deserialize)]
+struct ArchiveData {
+    config: GenerationConfig,
+    maze: ArchivedMaze,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchivedMaze {
+    #[serde(flatten)]
+    passages: JsonMaze,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    overpasses: Vec<(Coords, Overpass)>,
+}
+
+impl From<MazeArchive> for ArchiveData {
+    fn from(archive: MazeArchive) -> Self {
+        let grid = archive.maze.grid();
+        let overpasses = archive
+            .maze
+            .iter()
+            .filter_map(|(coords, _)| grid.overpass(coords).map(|overpass| (coords, overpass)))
+            .collect();
+
+        Self {
+            config: archive.config,
+            maze: ArchivedMaze {
+                passages: JsonMaze::from_grid(grid),
+                overpasses,
+            },
+        }
+    }
+}
+
+impl TryFrom<ArchiveData> for MazeArchive {
+    type Error = MazeLoadError;
+
+    fn try_from(data: ArchiveData) -> Result<Self, Self::Error> {
+        let mut grid = data.maze.passages.into_grid()?;
+        let crossing = Cell::NORTH | Cell::SOUTH | Cell::EAST | Cell::WEST;
+        for (coords, overpass) in data.maze.overpasses {
+            if !grid.contains(coords) || grid[coords] != crossing {
+                return Err(MazeLoadError::reason(format!(
+                    "Cell {coords:?} isn't a crossing of the maze"
+                )));
+            }
+            grid.set_overpass(coords, overpass);
+        }
+
+        Ok(Self {
+            config: data.config,
+            maze: OrthogonalMaze::from_grid(grid),
+        })
+    }
+}
+
+/// Saves an archive as JSON to a file at the specified path
+///
+/// # Errors
+/// Returns a [`MazeSaveError`] if the archive could not be serialized or written
+pub fn save_archive(archive: &MazeArchive, path: &str) -> Result<String, MazeSaveError> {
+    let json = serde_json::to_string(archive)
+        .map_err(|err| MazeSaveError::reason(format!("Couldn't serialize archive: {err}")))?;
+    fs::write(path, json)
+        .map_err(|err| MazeSaveError::reason(format!("Couldn't write to {path}: {err}")))?;
+
+    Ok(format!(
+        "Maze archive was successfully written to a file: {path}"
+    ))
+}
+
+/// Loads an archive from a JSON file at the specified path
+///
+/// # Errors
+/// Returns a [`MazeSaveError`] if the file could not be read or is not a valid archive
+pub fn load_archive(path: &str) -> Result<MazeArchive, MazeSaveError> {
+    let json = fs::read_to_string(path)
+        .map_err(|err| MazeSaveError::reason(format!("Couldn't read {path}: {err}")))?;
+
+    serde_json::from_str(&json)
+        .map_err(|err| MazeSaveError::reason(format!("Couldn't deserialize archive: {err}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{Method, Weave};
+
+    #[test]
+    fn archive_roundtrip() {
+        let mut config = GenerationConfig::new("GrowingTree");
+        config.seed = Some(42);
+        config.width = 7;
+        config.height = 5;
+        config.growing_method = Method::Random;
+        let archive = MazeArchive::new(config.clone(), config.build().unwrap()).unwrap();
+
+        let path = std::env::temp_dir().join("bevy_knossos_archive_roundtrip.json");
+        let path = path.to_str().unwrap();
+        save_archive(&archive, path).unwrap();
+        let loaded = load_archive(path).unwrap();
+
+        assert_eq!(loaded, archive);
+        assert_eq!(&loaded.config().build().unwrap(), loaded.maze());
+    }
+
+    #[test]
+    fn archive_rejects_mazes_its_config_cannot_regenerate() {
+        let mut config = GenerationConfig::new("Weave");
+        config.seed = Some(3);
+        let dense = config
+            .builder()
+            .unwrap()
+            .algorithm(Box::new(Weave::new().density(1.0)))
+            .build()
+            .unwrap();
+        let wide = config.builder().unwrap().corridor_width(2).build().unwrap();
+
+        assert!(MazeArchive::new(config.clone(), dense).is_err());
+        assert!(MazeArchive::new(config.clone(), wide).is_err());
+        assert!(MazeArchive::new(config.clone(), config.build().unwrap()).is_ok());
+
+        config.seed = None;
+        assert!(MazeArchive::new(config.clone(), config.build().unwrap()).is_err());
+    }
+
+    #[test]
+    fn load_missing_archive() {
+        let err = load_archive("this file does not exist.json").unwrap_err();
+
+        assert!(err.reason.starts_with("Couldn't read"));
+    }
+
+    #[test]
+    fn archive_accepts_loaded_and_scaled_mazes() {
+        let mut config = GenerationConfig::new("Kruskal");
+        config.seed = Some(8);
+        let maze = config.build().unwrap();
+        let loaded = OrthogonalMaze::from_bytes(&maze.to_bytes().unwrap()).unwrap();
+        let scaled = maze.with_render_scale(crate::maze::RenderScale {
+            wall: 1,
+            passage: 2,
+            margin: 3,
+        });
+
+        assert!(MazeArchive::new(config.clone(), loaded).is_ok());
+        assert!(MazeArchive::new(config, scaled).is_ok());
+    }
+
+    #[test]
+    fn archive_json_schema() {
+        let mut config = GenerationConfig::new("Weave");
+        config.seed = Some(4);
+        config.width = 6;
+        config.height = 6;
+        let archive = MazeArchive::new(config.clone(), config.build().unwrap()).unwrap();
+
+        let value = serde_json::to_value(&archive).unwrap();
+        assert_eq!(value["maze"]["width"], 6);
+        assert_eq!(value["maze"]["cells"].as_array().unwrap().len(), 36);
+        assert!(value["maze"].get("cell_statuses").is_none());
+        assert!(!value["maze"]["overpasses"].as_array().unwrap().is_empty());
+
+        let loaded: MazeArchive = serde_json::from_value(value).unwrap();
+        assert_eq!(loaded, archive);
+        assert!(loaded.maze().grid().has_overpasses());
+    }
+
+    #[test]
+    fn load_invalid_archive() {
+        let mut config = GenerationConfig::new("Sidewinder");
+        config.seed = Some(1);
+        config.width = 3;
+        config.height = 2;
+        let archive = MazeArchive::new(config.clone(), config.build().unwrap()).unwrap();
+        let value = serde_json::to_value(&archive).unwrap();
+
+        let mut missing = value.clone();
+        missing["maze"]["cells"].as_array_mut().unwrap().pop();
+        let err = serde_json::from_value::<MazeArchive>(missing).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("must contain 6 cells, but contains 5")
+        );
+
+        let mut crossing = value;
+        crossing["maze"]["overpasses"] = serde_json::json!([[[0, 0], "NorthSouth"]]);
+        let err = serde_json::from_value::<MazeArchive>(crossing).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Cell (0, 0) isn't a crossing of the maze")
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use rayon::prelude::*;
+
+use crate::maze::{OrthogonalMaze, OrthogonalMazeBuilder, errors::BuildError};
+
+/// Generates `count` independent mazes with the parameters of a builder in parallel
+///
+/// The mazes are generated on the global [rayon](https://docs.rs/rayon) thread pool from clones
+/// of the builder. Every maze gets its own seed derived from the seed of the builder and the index
+/// of the maze, so a seeded builder always produces the same batch regardless of the number of
+/// threads, while mazes of the batch differ from each other. If the builder has an RNG set with
+/// [`rng`](OrthogonalMazeBuilder::rng), the base seed is drawn from it instead, and a builder
+/// with neither produces a different batch every time.
+///
+/// # Example
+/// ```
+/// use bevy_knossos::maze::*;
+///
+/// let builder = OrthogonalMazeBuilder::new().algorithm(Box::new(Prim::new())).seed(42);
+///
+/// let mazes = generate_batch(8, &builder).unwrap();
+/// assert_eq!(mazes.len(), 8);
+/// assert_eq!(mazes, generate_batch(8, &builder).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns a [`BuildError`] if any of the mazes cannot be built, for the same reasons as
+/// [`OrthogonalMazeBuilder::build`]
+pub fn generate_batch(
+    count: usize,
+    builder: &OrthogonalMazeBuilder,
+) -> Result<Vec<OrthogonalMaze>, BuildError> {
+    let base_seed = builder.base_seed();
+    // Builders aren't `Sync`, so the clones are made up front and moved to the threads
+    let builders: Vec<OrthogonalMazeBuilder> = (0..count)
+        .map(|index| builder.reseeded(batch_seed(base_seed, index)))
+        .collect();
+
+    builders
+        .into_par_iter()
+        .map(|builder| builder.build())
+        .collect()
+}
+
+/// Returns a seed of the maze at a given index of a batch
+///
+/// The seed is mixed with the `SplitMix64` finalizer, so mazes with neighboring indexes get
+/// unrelated seeds.
+const fn batch_seed(base_seed: u64, index: usize) -> u64 {
+    let mut seed = base_seed.wrapping_add((index as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    seed = (seed ^ (seed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    seed = (seed ^ (seed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    seed ^ (seed >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::maze::{Mask, Sidewinder};
+
+    #[test]
+    fn batch_is_deterministic_with_seed() {
+        let builder = OrthogonalMazeBuilder::new().width(8).height(6).seed(3);
+
+        let mazes = generate_batch(16, &builder).unwrap();
+        let serial: Vec<OrthogonalMaze> = (0..16)
+            .map(|index| builder.clone().seed(batch_seed(3, index)).build().unwrap())
+            .collect();
+
+        assert_eq!(mazes, serial);
+        assert!(mazes.iter().all(OrthogonalMaze::is_valid));
+        assert!(mazes.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn batch_keeps_builder_parameters() {
+        let mut mask = Mask::new(6, 6);
+        mask.set_active((5, 5), false);
+        let builder = OrthogonalMazeBuilder::new()
+            .width(6)
+            .height(6)
+            .mask(mask)
+            .rng(StdRng::seed_from_u64(5));
+
+        let mazes = generate_batch(4, &builder).unwrap();
+
+        assert_eq!(mazes.len(), 4);
+        assert!(mazes.iter().all(|maze| !maze.grid().is_active((5, 5))));
+        assert!(mazes.iter().all(OrthogonalMaze::is_valid));
+    }
+
+    #[test]
+    fn batch_of_an_unbuildable_builder() {
+        let builder = OrthogonalMazeBuilder::new()
+            .algorithm(Box::new(Sidewinder))
+            .start_coords((0, 0));
+
+        assert!(generate_batch(4, &builder).is_err());
+        assert!(
+            generate_batch(0, &OrthogonalMazeBuilder::new())
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use super::grid::cell::Cell;
+use crate::utils::types::Coords;
+
+/// A side of the outer wall of a maze
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Border {
+    /// The top side, with cells ordered from West to East
+    North,
+    /// The bottom side, with cells ordered from West to East
+    South,
+    /// The right side, with cells ordered from North to South
+    East,
+    /// The left side, with cells ordered from North to South
+    West,
+}
+
+impl Border {
+    /// Returns the direction pointing out of the maze through this side
+    #[must_use]
+    pub const fn direction(self) -> Cell {
+        match self {
+            Self::North => Cell::NORTH,
+            Self::South => Cell::SOUTH,
+            Self::East => Cell::EAST,
+            Self::West => Cell::WEST,
+        }
+    }
+
+    /// Returns the coords of the cell at a given offset along this side of a maze with a given
+    /// width and height, or `None` if the offset is past the end of the side
+    #[must_use]
+    pub const fn cell(self, offset: usize, width: usize, height: usize) -> Option<Coords> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        match self {
+            Self::North if offset < width => Some((offset, 0)),
+            Self::South if offset < width => Some((offset, height - 1)),
+            Self::East if offset < height => Some((width - 1, offset)),
+            Self::West if offset < height => Some((0, offset)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn border_cells() {
+        assert_eq!(Border::North.cell(2, 4, 3), Some((2, 0)));
+        assert_eq!(Border::South.cell(2, 4, 3), Some((2, 2)));
+        assert_eq!(Border::East.cell(1, 4, 3), Some((3, 1)));
+        assert_eq!(Border::West.cell(1, 4, 3), Some((0, 1)));
+        assert_eq!(Border::East.cell(3, 4, 3), None);
+        assert_eq!(Border::North.cell(0, 0, 0), None);
+    }
+}
@@ -2,18 +2,50 @@ use rand::SeedableRng;
 use rand::rngs::StdRng;
 
 use crate::maze::OrthogonalMaze;
-use crate::maze::algorithms::{Algorithm, RecursiveBacktracking};
+use crate::maze::algorithms::{Algorithm, Prim, RecursiveBacktracking};
 use crate::utils::types::Coords;
 
-use super::errors::BuildError;
+use super::grid::{Grid, cell::Cell};
+use super::{
+    errors::BuildError,
+    mask::Mask,
+    steps::{CarveSteps, carves_only, record_carves},
+};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Elevation gained per cell of distance from a guide path
+const GUIDE_STEEPNESS: f64 = 2.0;
 
 /// An orthogonal maze builder for constructing a maze step by step
+///
+/// The builder is `Clone` and [`build`](Self::build) takes it by reference, so a configured
+/// builder can be reused to generate several mazes, e.g. to sweep seeds. The algorithm is cloned
+/// with [`AlgorithmClone`](super::AlgorithmClone) and an elevation field is shared between the
+/// clones. A clone of a builder with an RNG set with [`rng`](Self::rng) gets its own RNG seeded
+/// from that one, so clones generate independently of each other, while staying deterministic.
+///
+/// # Example
+/// ```
+/// use bevy_knossos::maze::*;
+///
+/// let builder = OrthogonalMazeBuilder::new().width(5).height(5);
+/// let mazes: Vec<OrthogonalMaze> = (0..3)
+///     .map(|seed| builder.clone().seed(seed).build().unwrap())
+///     .collect();
+///
+/// assert_eq!(mazes[1], builder.clone().seed(1).build().unwrap());
+/// ```
 pub struct OrthogonalMazeBuilder {
     width: usize,
     height: usize,
     algorithm: Box<dyn Algorithm>,
     start_coords: Option<Coords>,
     seed: Option<u64>,
+    rng: Option<Arc<Mutex<StdRng>>>,
+    elevation: Option<Arc<dyn Fn(Coords) -> f64 + Send + Sync>>,
+    guide_path: Option<Vec<Coords>>,
+    corridor_width: usize,
+    mask: Option<Mask>,
 }
 
 impl OrthogonalMazeBuilder {
@@ -26,6 +58,11 @@ impl OrthogonalMazeBuilder {
             algorithm: Box::new(RecursiveBacktracking),
             start_coords: None,
             seed: None,
+            rng: None,
+            elevation: None,
+            guide_path: None,
+            corridor_width: 1,
+            mask: None,
         }
     }
 
@@ -36,6 +73,17 @@ impl OrthogonalMazeBuilder {
         self
     }
 
+    /// Sets a random number generator to be used as-is during generation and returns itself
+    ///
+    /// Takes precedence over [`seed`](Self::seed), which allows sharing a single RNG stream
+    /// across several builds. Every build draws from the stream, so building twice generates
+    /// different mazes. Clones of the builder draw their own RNG from the stream when cloned.
+    #[must_use]
+    pub fn rng(mut self, rng: StdRng) -> Self {
+        self.rng = Some(Arc::new(Mutex::new(rng)));
+        self
+    }
+
     /// Sets a maze width and returns itself
     #[must_use]
     pub const fn width(mut self, width: usize) -> Self {
@@ -57,6 +105,59 @@ impl OrthogonalMazeBuilder {
         self
     }
 
+    /// Sets an elevation field that biases generation towards low-elevation cells and returns
+    /// itself
+    ///
+    /// The maze is then generated with [`Prim::with_elevation`] instead of the configured
+    /// algorithm, so passages form valleys along the low areas of the field. The elevation takes
+    /// precedence over any algorithm set with [`algorithm`](Self::algorithm), whichever is set
+    /// first. Generation stays deterministic for a given seed, and building fails if the field
+    /// isn't finite for every cell, e.g. it's NaN.
+    #[must_use]
+    pub fn elevation(mut self, elevation: Box<dyn Fn(Coords) -> f64 + Send + Sync>) -> Self {
+        self.elevation = Some(Arc::from(elevation));
+        self
+    }
+
+    /// Sets a guide polyline the solution between its first and last points should follow and
+    /// returns itself
+    ///
+    /// The guide doesn't force exact cells. Instead, cells are raised the further they are from
+    /// the guide and the maze is generated with [`Prim::with_elevation`] starting at the first
+    /// point of the guide, unless start coords are given. Combines with
+    /// [`elevation`](Self::elevation) by adding both fields together. Building fails if a point
+    /// of the guide is outside of the maze.
+    #[must_use]
+    pub fn guide_path(mut self, path: Vec<Coords>) -> Self {
+        self.guide_path = Some(path);
+        self
+    }
+
+    /// Sets a corridor width in cells and returns itself
+    ///
+    /// The maze is generated on a coarse `width` x `height` grid first, then each cell is expanded
+    /// into a block of `corridor_width` x `corridor_width` open cells, so the resulting maze is
+    /// `corridor_width` times wider and higher. Start coords refer to the coarse grid. Wide
+    /// corridors contain loops, so such a maze is not a perfect one.
+    #[must_use]
+    pub const fn corridor_width(mut self, corridor_width: usize) -> Self {
+        self.corridor_width = corridor_width;
+        self
+    }
+
+    /// Sets a mask of the cells that take part in the maze and returns itself
+    ///
+    /// The mask must have the same size as the maze and all its active cells must be connected.
+    /// Masked-off cells never get any passages. Algorithms that carve row by row or divide the
+    /// whole field, i.e. [`BinaryTree`](super::BinaryTree), [`Eller`](super::Eller),
+    /// [`RecursiveDivision`](super::RecursiveDivision) and [`Sidewinder`](super::Sidewinder),
+    /// don't support masks.
+    #[must_use]
+    pub fn mask(mut self, mask: Mask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
     /// Sets start coords for arguments that allow start coords
     #[must_use]
     pub fn start_coords(mut self, coord: impl Into<Coords>) -> Self {
@@ -67,35 +168,322 @@ impl OrthogonalMazeBuilder {
     /// Builds a maze and returns a resulting object of the generated orthogonal maze
     ///
     /// # Errors
-    /// Returns a [`BuildError`] if the algorithm does not support start coords
-    pub fn build(mut self) -> Result<OrthogonalMaze, BuildError> {
-        let mut maze = OrthogonalMaze::new(self.width, self.height);
-        let mut rng = self.seed.map_or_else(
-            || {
-                let mut rng = rand::rng();
-                StdRng::from_rng(&mut rng)
-            },
-            StdRng::seed_from_u64,
+    /// Returns a [`BuildError`] if the algorithm does not support start coords, the mask can't
+    /// be used with the algorithm or maze size, the elevation isn't finite for every cell, or a
+    /// guide path point is outside of the maze
+    pub fn build(&self) -> Result<OrthogonalMaze, BuildError> {
+        self.generate_upscaled().map(|(maze, _)| maze)
+    }
+
+    /// Builds a maze and returns it with the RNG in the state left by the generation
+    ///
+    /// Continuing to use the returned RNG, e.g. for [`OrthogonalMaze::braid`], keeps the whole
+    /// pipeline deterministic under a single seed. An RNG set with [`rng`](Self::rng) stays with
+    /// the builder for its next builds, so a new RNG seeded from it is returned instead.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_knossos::maze::*;
+    ///
+    /// let (mut maze, mut rng) = OrthogonalMazeBuilder::new().seed(7).build_with_rng().unwrap();
+    /// maze.braid(&mut rng, 0.5);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [`BuildError`] for the same reasons as [`build`](Self::build)
+    pub fn build_with_rng(&self) -> Result<(OrthogonalMaze, StdRng), BuildError> {
+        let (maze, rng) = self.generate_upscaled()?;
+        let rng = Arc::try_unwrap(rng).map_or_else(
+            |shared| StdRng::from_rng(&mut *shared.lock().unwrap_or_else(PoisonError::into_inner)),
+            |rng| rng.into_inner().unwrap_or_else(PoisonError::into_inner),
         );
+        Ok((maze, rng))
+    }
+
+    /// Builds a maze and returns an iterator over its passages in the order they were carved
+    ///
+    /// The iterator carves the passages into an empty maze one by one, which allows animating the
+    /// generation. Once it's exhausted, its maze is equal to the one returned by
+    /// [`build`](Self::build) for the same parameters, e.g. the same seed.
+    ///
+    /// # Errors
+    /// Returns a [`BuildError`] for the same reasons as [`build`](Self::build), if a corridor
+    /// width is set, or if the algorithm doesn't generate a maze by carving passages only, which
+    /// may be the case for custom algorithms
+    pub fn build_steps(&self) -> Result<CarveSteps, BuildError> {
+        let (maze, steps) = self.generate_recorded()?;
+        let mut blank = OrthogonalMaze::new(self.width, self.height);
+        if let Some(mask) = self.mask.clone() {
+            blank.get_grid_mut().apply_mask(mask);
+        }
+
+        Ok(CarveSteps::new(blank, steps, maze))
+    }
+
+    /// Builds a maze and returns it with its passages in the order they were carved
+    ///
+    /// Each passage is a pair of the coords of the cell it was carved from and the cell it leads
+    /// to, so for perfect mazes the passages are the edges of a spanning tree in the order the
+    /// algorithm added them. The maze is equal to the one returned by [`build`](Self::build) for
+    /// the same parameters.
+    ///
+    /// # Errors
+    /// Returns a [`BuildError`] for the same reasons as [`build_steps`](Self::build_steps)
+    pub fn build_with_trace(&self) -> Result<(OrthogonalMaze, Vec<(Coords, Coords)>), BuildError> {
+        let (maze, steps) = self.generate_recorded()?;
+        let trace = steps
+            .into_iter()
+            .filter_map(|(from, direction)| {
+                let to = maze.grid().get_next_cell_coords(from, direction).ok()?;
+                Some((from, to))
+            })
+            .collect();
+        Ok((maze, trace))
+    }
+
+    /// Generates a maze expanded to the corridor width and returns it with the RNG used for the
+    /// generation
+    fn generate_upscaled(&self) -> Result<(OrthogonalMaze, Arc<Mutex<StdRng>>), BuildError> {
+        let (maze, rng, _) = self.with_rng(self.rng.clone()).generate(false)?;
+        if self.corridor_width > 1 {
+            return Ok((maze.upscale(self.corridor_width), rng));
+        }
+        Ok((maze, rng))
+    }
+
+    /// Generates a maze and returns it with the passages the algorithm carved in order, as the
+    /// carving cell and direction
+    fn generate_recorded(&self) -> Result<(OrthogonalMaze, Vec<(Coords, Cell)>), BuildError> {
+        if self.corridor_width > 1 {
+            return Err(BuildError::reason(
+                "Corridor width isn't supported when building a maze step by step",
+            ));
+        }
+
+        let name = self.algorithm.name();
+        let (maze, _, steps) = self.with_rng(self.rng.clone()).generate(true)?;
+        if !carves_only(&maze, &steps) {
+            return Err(BuildError::reason(format!(
+                "Algorithm `{name}` doesn't generate a maze by carving passages only"
+            )));
+        }
+        Ok((maze, steps))
+    }
+
+    /// Generates a maze on a grid of the builder size and returns it with the RNG used for the
+    /// generation and the passages the algorithm carved, which are recorded only if `record` is
+    /// set
+    fn generate(
+        mut self,
+        record: bool,
+    ) -> Result<(OrthogonalMaze, Arc<Mutex<StdRng>>, Vec<(Coords, Cell)>), BuildError> {
+        let mut maze = OrthogonalMaze::new(self.width, self.height);
+        if let Some(mask) = self.mask.take() {
+            self.validate_mask(&mask)?;
+            maze.get_grid_mut().apply_mask(mask);
+        }
+        let rng = self.rng.take().unwrap_or_else(|| {
+            let rng = self.seed.map_or_else(
+                || {
+                    let mut rng = rand::rng();
+                    StdRng::from_rng(&mut rng)
+                },
+                StdRng::seed_from_u64,
+            );
+            Arc::new(Mutex::new(rng))
+        });
+        let mut start_coords = self.start_coords;
+        let guide = self.guide_path.take().filter(|path| !path.is_empty());
+        if let Some(path) = &guide {
+            if let Some(coords) = path
+                .iter()
+                .find(|(x, y)| *x >= self.width || *y >= self.height)
+            {
+                return Err(BuildError::reason(format!(
+                    "Guide path point {coords:?} is outside of the {}x{} maze",
+                    self.width, self.height
+                )));
+            }
+            start_coords = start_coords.or_else(|| path.first().copied());
+        }
+        let guide = guide.map(|path| self.guide_elevation(&path));
+        let elevation: Option<Box<dyn Fn(Coords) -> f64 + Send + Sync>> =
+            match (self.elevation.take(), guide) {
+                (Some(elevation), Some(guide)) => Some(Box::new(move |c| elevation(c) + guide(c))),
+                (Some(elevation), None) => Some(Box::new(move |c| elevation(c))),
+                (None, guide) => guide,
+            };
+        if let Some(elevation) = elevation {
+            self.validate_elevation(&elevation)?;
+            self.algorithm = Box::new(Prim::with_elevation(elevation));
+        }
         if self.start_coords.is_some() && !self.algorithm.has_start_coords() {
-            Err(BuildError::reason(self.algorithm.name()))
+            Err(BuildError::reason(format!(
+                "Algorithm `{}` doesn't support `start_coords`",
+                self.algorithm.name()
+            )))
         } else {
-            self.algorithm
-                .generate(maze.get_grid_mut(), self.start_coords, &mut rng);
-            Ok(maze)
+            let mut generator = rng.lock().unwrap_or_else(PoisonError::into_inner);
+            let mut generate = || {
+                self.algorithm
+                    .generate(maze.get_grid_mut(), start_coords, &mut generator);
+            };
+            let carves = if record {
+                record_carves(generate).1
+            } else {
+                generate();
+                Vec::new()
+            };
+            drop(generator);
+            Ok((maze, rng, carves))
         }
     }
 }
 
+#[cfg(feature = "parallel")]
+impl OrthogonalMazeBuilder {
+    /// Returns a seed to derive the seeds of a batch from, drawn from the RNG of the builder if
+    /// set, the seed of the builder otherwise, or a random one if neither is set
+    pub(crate) fn base_seed(&self) -> u64 {
+        use rand::prelude::*;
+
+        self.rng.as_ref().map_or_else(
+            || self.seed.unwrap_or_else(|| rand::rng().random()),
+            |rng| rng.lock().unwrap_or_else(PoisonError::into_inner).random(),
+        )
+    }
+
+    /// Returns a clone of the builder generating from a given seed only, without a shared RNG
+    pub(crate) fn reseeded(&self, seed: u64) -> Self {
+        self.with_rng(None).seed(seed)
+    }
+}
+
+impl OrthogonalMazeBuilder {
+    /// Returns a copy of the builder with a given RNG
+    fn with_rng(&self, rng: Option<Arc<Mutex<StdRng>>>) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            algorithm: self.algorithm.clone(),
+            start_coords: self.start_coords,
+            seed: self.seed,
+            rng,
+            elevation: self.elevation.clone(),
+            guide_path: self.guide_path.clone(),
+            corridor_width: self.corridor_width,
+            mask: self.mask.clone(),
+        }
+    }
+
+    /// Returns an elevation field growing with the distance from the segments of a guide path
+    fn guide_elevation(&self, path: &[Coords]) -> Box<dyn Fn(Coords) -> f64 + Send + Sync> {
+        let (width, height) = (self.width, self.height);
+        let mut guide = Vec::new();
+
+        let segments = path.iter().zip(path.iter().skip(1).chain(path.last()));
+        for (&(mut x, mut y), &(to_x, to_y)) in segments {
+            loop {
+                guide.push((x, y));
+                if (x, y) == (to_x, to_y) {
+                    break;
+                }
+                // Steps along the axis with the longest remaining distance to keep near the line
+                if x.abs_diff(to_x) >= y.abs_diff(to_y) {
+                    x = if x < to_x { x + 1 } else { x - 1 };
+                } else {
+                    y = if y < to_y { y + 1 } else { y - 1 };
+                }
+            }
+        }
+
+        // The field ignores walls, so distances are measured on a grid with every passage open
+        let mut open = Grid::new(width, height);
+        for (x, y) in (0..height).flat_map(|y| (0..width).map(move |x| (x, y))) {
+            if x + 1 < width {
+                open.carve_passage((x, y), Cell::EAST).ok();
+            }
+            if y + 1 < height {
+                open.carve_passage((x, y), Cell::SOUTH).ok();
+            }
+        }
+        let mut distances = vec![0; width * height];
+        open.flood_fill_from(guide, |(x, y), distance| {
+            distances[y * width + x] = distance;
+        });
+
+        Box::new(move |(x, y)| {
+            distances
+                .get(y * width + x)
+                .map_or(0.0, |&distance| f64::from(distance) * GUIDE_STEEPNESS)
+        })
+    }
+
+    fn validate_elevation(&self, elevation: &dyn Fn(Coords) -> f64) -> Result<(), BuildError> {
+        let cells = (0..self.height).flat_map(|y| (0..self.width).map(move |x| (x, y)));
+        for coords in cells {
+            let value = elevation(coords);
+            if !value.is_finite() {
+                return Err(BuildError::reason(format!(
+                    "Elevation of cell {coords:?} is {value}, but must be finite"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_mask(&self, mask: &Mask) -> Result<(), BuildError> {
+        if !self.algorithm.supports_mask() {
+            return Err(BuildError::reason(format!(
+                "Algorithm `{}` doesn't support masks",
+                self.algorithm.name()
+            )));
+        }
+        if (mask.width(), mask.height()) != (self.width, self.height) {
+            return Err(BuildError::reason(format!(
+                "Mask size {}x{} doesn't match maze size {}x{}",
+                mask.width(),
+                mask.height(),
+                self.width,
+                self.height
+            )));
+        }
+        if !mask.is_connected() {
+            return Err(BuildError::reason(
+                "Mask is disconnected, so some active cells can't be reached. Make sure every \
+                 active cell is adjacent to another active cell",
+            ));
+        }
+        if let Some(coords) = self.start_coords.filter(|coords| !mask.is_active(*coords)) {
+            return Err(BuildError::reason(format!(
+                "Start coords {coords:?} are masked off"
+            )));
+        }
+        Ok(())
+    }
+}
+
 impl Default for OrthogonalMazeBuilder {
     fn default() -> Self {
         Self::new()
     }
 }
 
+impl Clone for OrthogonalMazeBuilder {
+    fn clone(&self) -> Self {
+        let rng = self.rng.as_ref().map(|rng| {
+            let mut rng = rng.lock().unwrap_or_else(PoisonError::into_inner);
+            Arc::new(Mutex::new(StdRng::from_rng(&mut *rng)))
+        });
+        self.with_rng(rng)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::maze::RecursiveDivision;
+    use crate::maze::{Cell, Kruskal, RecursiveDivision, grid::Grid};
+    use crate::utils::types::direction_between;
+    use rand::Rng;
 
     use super::*;
 
@@ -105,6 +493,402 @@ mod tests {
         assert!(maze.is_valid());
     }
 
+    #[test]
+    fn build_steps_end_with_the_built_maze() {
+        let algorithms: [fn() -> Box<dyn Algorithm>; 2] =
+            [|| Box::new(RecursiveBacktracking), || Box::new(Prim::new())];
+
+        for algorithm in algorithms {
+            let builder = || {
+                OrthogonalMazeBuilder::new()
+                    .width(9)
+                    .height(7)
+                    .seed(3)
+                    .algorithm(algorithm())
+            };
+            let maze = builder().build().unwrap();
+            let mut steps = builder().build_steps().unwrap();
+
+            assert_eq!(steps.len(), 9 * 7 - 1);
+            assert_eq!(steps.maze(), &OrthogonalMaze::new(9, 7));
+            let mut carved = 0;
+            while let Some(step) = steps.next() {
+                carved += 1;
+                assert_eq!(
+                    step.from.0.abs_diff(step.to.0) + step.from.1.abs_diff(step.to.1),
+                    1
+                );
+                assert_eq!(steps.maze().reachable_count(step.to), carved + 1);
+            }
+            assert_eq!(steps.into_maze(), maze);
+        }
+    }
+
+    #[test]
+    fn build_with_trace_matches_build() {
+        let algorithms: [fn() -> Box<dyn Algorithm>; 3] = [
+            || Box::new(RecursiveBacktracking),
+            || Box::new(Kruskal),
+            || Box::new(Prim::new()),
+        ];
+
+        for algorithm in algorithms {
+            let builder = OrthogonalMazeBuilder::new()
+                .width(8)
+                .height(5)
+                .seed(11)
+                .algorithm(algorithm());
+            let (maze, trace) = builder.build_with_trace().unwrap();
+
+            assert_eq!(maze, builder.build().unwrap());
+            assert_eq!(trace.len(), 8 * 5 - 1);
+            let mut replayed = OrthogonalMaze::new(8, 5);
+            for (from, to) in trace {
+                let direction = direction_between(from, to).unwrap();
+                assert_eq!(replayed.carve(from, direction).unwrap(), to);
+            }
+            assert!(replayed.iter().eq(maze.iter()));
+        }
+    }
+
+    #[test]
+    fn build_steps_of_an_algorithm_opening_borders() {
+        #[derive(Clone)]
+        struct Opening;
+        impl Algorithm for Opening {
+            fn generate(&mut self, grid: &mut Grid, _: Option<Coords>, rng: &mut StdRng) {
+                RecursiveBacktracking.generate(grid, None, rng);
+                grid.open_border((0, 0), Cell::WEST);
+            }
+
+            fn has_start_coords(&self) -> bool {
+                false
+            }
+
+            fn name(&self) -> &'static str {
+                "Opening"
+            }
+        }
+
+        let steps = OrthogonalMazeBuilder::new()
+            .algorithm(Box::new(Opening))
+            .build_steps();
+
+        assert_eq!(
+            steps.unwrap_err().to_string(),
+            "Cannot build maze. Reason: Algorithm `Opening` doesn't generate a maze by carving \
+             passages only"
+        );
+        assert!(
+            OrthogonalMazeBuilder::new()
+                .corridor_width(2)
+                .build_steps()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn build_with_identical_rngs() {
+        // `StdRng` is not `Clone`, so two generators with the same state stand in for a clone
+        let maze = OrthogonalMazeBuilder::default()
+            .rng(StdRng::seed_from_u64(42))
+            .build()
+            .unwrap();
+        let other = OrthogonalMazeBuilder::default()
+            .rng(StdRng::seed_from_u64(42))
+            .build()
+            .unwrap();
+
+        assert_eq!(maze, other);
+    }
+
+    #[test]
+    fn build_with_rng_continues_the_seeded_stream() {
+        let builder = OrthogonalMazeBuilder::default().seed(9);
+        let (maze, mut rng) = builder.build_with_rng().unwrap();
+        let (other, mut other_rng) = builder.build_with_rng().unwrap();
+
+        assert_eq!(maze, other);
+        assert_eq!(maze, builder.build().unwrap());
+        assert_eq!(rng.next_u64(), other_rng.next_u64());
+        assert_ne!(rng.next_u64(), StdRng::seed_from_u64(9).next_u64());
+    }
+
+    #[test]
+    fn rng_takes_precedence_over_seed() {
+        let maze = OrthogonalMazeBuilder::default()
+            .seed(1)
+            .rng(StdRng::seed_from_u64(42))
+            .build()
+            .unwrap();
+        let other = OrthogonalMazeBuilder::default().seed(42).build().unwrap();
+
+        assert_eq!(maze, other);
+    }
+
+    #[test]
+    fn builds_draw_only_generation_from_the_rng() {
+        let builder = OrthogonalMazeBuilder::default()
+            .width(6)
+            .height(4)
+            .rng(StdRng::seed_from_u64(42));
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut generate = || {
+            let mut maze = OrthogonalMaze::new(6, 4);
+            RecursiveBacktracking.generate(maze.get_grid_mut(), None, &mut rng);
+            maze
+        };
+
+        assert_eq!(builder.build().unwrap(), generate());
+        assert_eq!(builder.build().unwrap(), generate());
+    }
+
+    #[test]
+    fn builder_is_reusable() {
+        let builder = OrthogonalMazeBuilder::default()
+            .seed(5)
+            .algorithm(Box::new(Prim::new()));
+        let copy = builder.clone();
+        let reseeded = builder.clone().seed(6);
+        let maze = builder.build().unwrap();
+
+        assert_eq!(builder.build().unwrap(), maze);
+        assert_eq!(copy.build().unwrap(), maze);
+        assert_ne!(reseeded.build().unwrap(), maze);
+    }
+
+    #[test]
+    fn clones_get_their_own_rng() {
+        let builder = || OrthogonalMazeBuilder::default().rng(StdRng::seed_from_u64(42));
+        let original = builder();
+        let copy = original.clone();
+        let other = copy.build().unwrap();
+        let maze = original.build().unwrap();
+
+        assert_ne!(maze, other);
+        // A clone generates the same maze whether the original built anything or not
+        let original = builder();
+        let copy = original.clone();
+        original.build().unwrap();
+        original.build().unwrap();
+        assert_eq!(copy.build().unwrap(), other);
+    }
+
+    #[test]
+    fn build_with_elevation() {
+        let build = || {
+            OrthogonalMazeBuilder::default()
+                .seed(3)
+                .elevation(Box::new(|(x, y)| (x + y) as f64))
+                .build()
+                .unwrap()
+        };
+        let maze = build();
+
+        assert!(maze.is_valid());
+        assert_eq!(maze, build());
+    }
+
+    #[test]
+    fn build_with_non_finite_elevation() {
+        let builder = OrthogonalMazeBuilder::default()
+            .width(4)
+            .height(3)
+            .elevation(Box::new(
+                |(x, y)| if (x, y) == (2, 1) { f64::NAN } else { 0.0 },
+            ));
+
+        assert_eq!(
+            builder.build().unwrap_err().reason,
+            "Elevation of cell (2, 1) is NaN, but must be finite"
+        );
+        assert!(
+            builder
+                .elevation(Box::new(|_| f64::INFINITY))
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn build_with_guide_path() {
+        let guide = vec![(0, 0), (11, 0), (11, 5), (0, 6), (0, 11), (11, 11)];
+        let cells = {
+            let builder = OrthogonalMazeBuilder::default().width(12).height(12);
+            let mut distances = Vec::new();
+            let elevation = builder.guide_elevation(&guide);
+            for y in 0..12 {
+                for x in 0..12 {
+                    distances.push(elevation((x, y)) / GUIDE_STEEPNESS);
+                }
+            }
+            distances
+        };
+        let average_distance = |maze: &OrthogonalMaze| {
+            let path = maze.solve((0, 0), (11, 11)).unwrap();
+            path.iter().map(|(x, y)| cells[y * 12 + x]).sum::<f64>() / path.len() as f64
+        };
+        let builder = || {
+            OrthogonalMazeBuilder::default()
+                .width(12)
+                .height(12)
+                .seed(9)
+        };
+
+        let guided = builder().guide_path(guide).build().unwrap();
+        let unguided = builder().algorithm(Box::new(Prim::new())).build().unwrap();
+
+        assert!(guided.is_valid());
+        assert!(average_distance(&guided) < average_distance(&unguided));
+        assert_eq!(cells[5 * 12 + 6], 0.0);
+        assert_eq!(cells[3 * 12 + 5], 2.0);
+    }
+
+    #[test]
+    fn build_with_guide_path_outside_of_the_maze() {
+        let builder = || OrthogonalMazeBuilder::default().width(5).height(4);
+
+        assert_eq!(
+            builder()
+                .guide_path(vec![(5, 0), (0, 0)])
+                .build()
+                .unwrap_err()
+                .reason,
+            "Guide path point (5, 0) is outside of the 5x4 maze"
+        );
+        assert_eq!(
+            builder()
+                .guide_path(vec![(0, 0), (2, 4)])
+                .build()
+                .unwrap_err()
+                .reason,
+            "Guide path point (2, 4) is outside of the 5x4 maze"
+        );
+    }
+
+    #[test]
+    fn build_with_corridor_width() {
+        let maze = OrthogonalMazeBuilder::default()
+            .width(6)
+            .height(4)
+            .seed(5)
+            .corridor_width(2)
+            .build()
+            .unwrap();
+
+        assert_eq!(maze.iter().count(), 12 * 8);
+        for ((x, y), cell) in maze.iter() {
+            assert!(maze.distance_between((0, 0), (x, y)).is_some());
+
+            // A passage out of a block is shared by both cells along the block edge
+            let (block_x, block_y) = (x - x % 2, y - y % 2);
+            for direction in [Cell::EAST, Cell::WEST] {
+                if cell.contains(direction) {
+                    assert!(maze[(x, block_y)].contains(direction));
+                    assert!(maze[(x, block_y + 1)].contains(direction));
+                }
+            }
+            for direction in [Cell::NORTH, Cell::SOUTH] {
+                if cell.contains(direction) {
+                    assert!(maze[(block_x, y)].contains(direction));
+                    assert!(maze[(block_x + 1, y)].contains(direction));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn build_with_mask() {
+        let mut mask = Mask::new(4, 4);
+        mask.set_active((1, 1), false);
+        mask.set_active((2, 1), false);
+
+        let maze = OrthogonalMazeBuilder::default()
+            .width(4)
+            .height(4)
+            .mask(mask)
+            .build()
+            .unwrap();
+
+        assert!(maze.is_valid());
+        assert_eq!(maze.active_cell_count(), 14);
+        assert_eq!(maze[(1, 1)], Cell::empty());
+        assert_eq!(maze[(2, 1)], Cell::empty());
+    }
+
+    #[test]
+    fn build_with_ascii_mask_for_all_supported_algorithms() {
+        use crate::maze::{AldousBroder, GrowingTree, HuntAndKill, Method, Weave, Wilson};
+
+        let mask = Mask::from_ascii(
+            "..####..\n\
+             .######.\n\
+             ###..###\n\
+             ###..###\n\
+             .######.\n\
+             ..####..",
+        );
+        let algorithms: [fn() -> Box<dyn Algorithm>; 9] = [
+            || Box::new(AldousBroder),
+            || Box::new(GrowingTree::new(Method::Random)),
+            || Box::new(HuntAndKill::new()),
+            || Box::new(Kruskal),
+            || Box::new(Prim::new()),
+            || Box::new(Prim::weighted()),
+            || Box::new(RecursiveBacktracking),
+            || Box::new(Weave::new()),
+            || Box::new(Wilson),
+        ];
+
+        for algorithm in algorithms {
+            for seed in [0, 1, 42] {
+                let maze = OrthogonalMazeBuilder::default()
+                    .width(8)
+                    .height(6)
+                    .seed(seed)
+                    .mask(mask.clone())
+                    .algorithm(algorithm())
+                    .build()
+                    .unwrap();
+
+                assert!(maze.is_valid());
+                for (coords, cell) in maze.iter() {
+                    assert_eq!(mask.is_active(coords), !cell.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn checkerboard_mask_is_disconnected() {
+        let error = OrthogonalMazeBuilder::default()
+            .width(4)
+            .height(4)
+            .mask(Mask::checkerboard(4, 4))
+            .build()
+            .unwrap_err();
+
+        assert!(error.to_string().contains("Mask is disconnected"));
+    }
+
+    #[test]
+    fn invalid_mask() {
+        let build = |builder: OrthogonalMazeBuilder| builder.build().unwrap_err().to_string();
+
+        assert_eq!(
+            build(OrthogonalMazeBuilder::default().mask(Mask::new(2, 2))),
+            "Cannot build maze. Reason: Mask size 2x2 doesn't match maze size 10x10"
+        );
+        assert_eq!(
+            build(
+                OrthogonalMazeBuilder::default()
+                    .algorithm(Box::new(RecursiveDivision))
+                    .mask(Mask::new(10, 10))
+            ),
+            "Cannot build maze. Reason: Algorithm `RecursiveDivision` doesn't support masks"
+        );
+    }
+
     #[test]
     fn no_start_coord_support() {
         let maze_err = OrthogonalMazeBuilder::default()
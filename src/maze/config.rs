@@ -0,0 +1,145 @@
+use crate::maze::algorithms::{
+    AldousBroder, Algorithm, Bias, BinaryTree, Dungeon, Eller, GrowingTree, HuntAndKill, Kruskal,
+    Method, Prim, RecursiveBacktracking, RecursiveDivision, Sidewinder, Weave, Wilson,
+};
+use crate::maze::{OrthogonalMaze, OrthogonalMazeBuilder};
+use crate::utils::types::Coords;
+
+use super::{errors::BuildError, registry::registered_algorithm};
+
+/// A full set of parameters used to generate an orthogonal maze
+///
+/// Unlike [`OrthogonalMazeBuilder`], the config is plain data, so it can be stored next to a
+/// generated maze and used later to regenerate or audit it. The algorithm is referenced by its
+/// [`Algorithm::name`], while `bias` and `growing_method` are only used by the
+/// [`BinaryTree`] and [`GrowingTree`] algorithms respectively.
+///
+/// A config only describes a subset of what the builder can do: the built-in algorithms with
+/// their default options, apart from the bias and the growing method, plus the size, the seed and
+/// the start coords. Other algorithm options, like [`Weave::density`],
+/// [`Prim::weighted`](Prim::weighted) or [`RecursiveDivision::with_rooms`], and builder options,
+/// like masks, corridor widths, elevation, guide paths or braiding, can't be expressed, so a maze
+/// generated with them isn't regenerated by its config.
+/// [`MazeArchive::new`](crate::maze::MazeArchive::new) rejects such mazes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenerationConfig {
+    /// Name of the algorithm used to generate a maze
+    pub algorithm: String,
+    /// Seed value for deterministic generation. A random maze is generated when `None`
+    pub seed: Option<u64>,
+    /// Maze width in a number of cells
+    pub width: usize,
+    /// Maze height in a number of cells
+    pub height: usize,
+    /// Start coords for algorithms that support them
+    pub start_coords: Option<Coords>,
+    /// Bias to use for the "Binary Tree" algorithm
+    pub bias: Bias,
+    /// Growing method to use for the "Growing Tree" algorithm
+    pub growing_method: Method,
+}
+
+impl GenerationConfig {
+    /// Returns a new config for a given algorithm name with the default builder parameters
+    #[must_use]
+    pub fn new(algorithm: impl Into<String>) -> Self {
+        Self {
+            algorithm: algorithm.into(),
+            seed: None,
+            width: 10,
+            height: 10,
+            start_coords: None,
+            bias: Bias::NorthEast,
+            growing_method: Method::Newest,
+        }
+    }
+
+    /// Returns an instance of the configured algorithm or `None` if the name is unknown
+    ///
+    /// Built-in algorithms get their default options, except for the configured bias and growing
+    /// method.
+    ///
+    /// Names that don't match any built-in algorithm are looked up among the algorithms
+    /// registered with [`register_algorithm`](crate::maze::register_algorithm).
+    #[must_use]
+    pub fn algorithm(&self) -> Option<Box<dyn Algorithm>> {
+        let algorithm: Box<dyn Algorithm> = match self.algorithm.as_str() {
+            "AldousBroder" => Box::new(AldousBroder),
+            "BinaryTree" => Box::new(BinaryTree::new(self.bias)),
+            "Dungeon" => Box::new(Dungeon::default()),
+            "Eller" => Box::new(Eller),
+            "GrowingTree" => Box::new(GrowingTree::new(self.growing_method)),
+            "HuntAndKill" => Box::new(HuntAndKill::new()),
+            "Kruskal" | "Kruscal" => Box::new(Kruskal),
+            "Prim" => Box::new(Prim::new()),
+            "RecursiveBacktracking" => Box::new(RecursiveBacktracking),
+            "RecursiveDivision" => Box::new(RecursiveDivision),
+            "Sidewinder" => Box::new(Sidewinder),
+            "Weave" => Box::new(Weave::new()),
+            "Wilson" => Box::new(Wilson),
+            name => return registered_algorithm(name),
+        };
+        Some(algorithm)
+    }
+
+    /// Returns a builder with all the parameters of this config applied
+    ///
+    /// # Errors
+    /// Returns a [`BuildError`] if the algorithm name is unknown
+    pub fn builder(&self) -> Result<OrthogonalMazeBuilder, BuildError> {
+        let algorithm = self
+            .algorithm()
+            .ok_or_else(|| BuildError::reason(format!("Unknown algorithm `{}`", self.algorithm)))?;
+
+        let mut builder = OrthogonalMazeBuilder::new()
+            .width(self.width)
+            .height(self.height)
+            .algorithm(algorithm);
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed);
+        }
+        if let Some(coords) = self.start_coords {
+            builder = builder.start_coords(coords);
+        }
+
+        Ok(builder)
+    }
+
+    /// Generates a maze with the parameters of this config
+    ///
+    /// # Errors
+    /// Returns a [`BuildError`] if the algorithm name is unknown or the maze cannot be built
+    pub fn build(&self) -> Result<OrthogonalMaze, BuildError> {
+        self.builder()?.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_is_deterministic_with_seed() {
+        let mut config = GenerationConfig::new("BinaryTree");
+        config.seed = Some(7);
+        config.bias = Bias::SouthWest;
+
+        let maze = config.build().unwrap();
+        let expected = OrthogonalMazeBuilder::new()
+            .seed(7)
+            .algorithm(Box::new(BinaryTree::new(Bias::SouthWest)))
+            .build()
+            .unwrap();
+
+        assert_eq!(maze, expected);
+    }
+
+    #[test]
+    fn unknown_algorithm() {
+        let config = GenerationConfig::new("Nope");
+
+        assert!(config.algorithm().is_none());
+        assert!(config.build().is_err());
+    }
+}
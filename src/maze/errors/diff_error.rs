@@ -0,0 +1,42 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A maze diff error
+///
+/// Represents two mazes of different sizes, which can't be compared cell by cell
+pub struct MazeDiffError {
+    /// Width and height of the maze being compared
+    pub dimensions: (usize, usize),
+    /// Width and height of the maze it's compared with
+    pub other_dimensions: (usize, usize),
+}
+
+/// An implementation of [fmt::Display](fmt::Display) trait
+impl fmt::Display for MazeDiffError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (width, height) = self.dimensions;
+        let (other_width, other_height) = self.other_dimensions;
+        write!(
+            f,
+            "Cannot diff mazes. Reason: a {width}x{height} maze can't be compared with a {other_width}x{other_height} one"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = MazeDiffError {
+            dimensions: (4, 4),
+            other_dimensions: (5, 3),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Cannot diff mazes. Reason: a 4x4 maze can't be compared with a 5x3 one"
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use std::fmt;
+
+#[derive(Debug, Clone)]
+/// A maze load error
+///
+/// Represents a custom error when a maze cannot be loaded from its serialized representation
+pub struct MazeLoadError {
+    /// A reason why a maze cannot be loaded
+    pub reason: String,
+}
+
+impl MazeLoadError {
+    /// Defines reason why was not able to load maze
+    pub fn reason(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+        }
+    }
+}
+
+/// An implementation of [fmt::Display](fmt::Display) trait
+impl fmt::Display for MazeLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cannot load maze. Reason: {}", self.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = MazeLoadError::reason("It's a fake reason");
+
+        assert_eq!(
+            error.to_string(),
+            "Cannot load maze. Reason: It's a fake reason"
+        );
+    }
+}
@@ -1,7 +1,15 @@
 mod builder_error;
+mod diff_error;
+mod load_error;
+mod path_error;
 mod save_error;
+mod start_goal_error;
 mod transit_error;
 
 pub use builder_error::BuildError;
+pub use diff_error::MazeDiffError;
+pub use load_error::MazeLoadError;
+pub use path_error::PathError;
 pub use save_error::MazeSaveError;
+pub use start_goal_error::StartGoalError;
 pub use transit_error::TransitError;
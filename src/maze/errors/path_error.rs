@@ -0,0 +1,76 @@
+use crate::utils::types::Coords;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A path validation error
+///
+/// Represents the first illegal step of a path through a maze. Steps are indexes into the path.
+pub enum PathError {
+    /// The path doesn't contain any cells
+    Empty,
+    /// A cell of the path lies outside the maze
+    OutOfBounds {
+        /// Index of the cell in the path
+        step: usize,
+        /// Coords of the cell
+        coords: Coords,
+    },
+    /// Two consecutive cells of the path are not neighbors
+    NotAdjacent {
+        /// Index of the second cell in the path
+        step: usize,
+        /// Coords of the previous cell
+        from: Coords,
+        /// Coords of the cell
+        to: Coords,
+    },
+    /// Two consecutive cells of the path are separated by a wall
+    Blocked {
+        /// Index of the second cell in the path
+        step: usize,
+        /// Coords of the previous cell
+        from: Coords,
+        /// Coords of the cell
+        to: Coords,
+    },
+}
+
+/// An implementation of [fmt::Display](fmt::Display) trait
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Invalid path. Reason: the path is empty"),
+            Self::OutOfBounds { step, coords } => write!(
+                f,
+                "Invalid path. Reason: step {step} to {coords:?} is out of the maze bounds"
+            ),
+            Self::NotAdjacent { step, from, to } => write!(
+                f,
+                "Invalid path. Reason: step {step} from {from:?} to {to:?} skips cells"
+            ),
+            Self::Blocked { step, from, to } => write!(
+                f,
+                "Invalid path. Reason: step {step} from {from:?} to {to:?} goes through a wall"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = PathError::Blocked {
+            step: 2,
+            from: (0, 1),
+            to: (1, 1),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid path. Reason: step 2 from (0, 1) to (1, 1) goes through a wall"
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use crate::utils::types::Coords;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A start and goal placement error
+///
+/// Represents a start or a goal that can't be placed at a cell of a map
+pub struct StartGoalError {
+    /// Coords of the cell the start or the goal is placed at
+    pub coords: Coords,
+    /// Reason why the cell can't be used
+    pub reason: String,
+}
+
+impl StartGoalError {
+    /// Returns an error of placing the start or the goal at given coords for a given reason
+    pub fn reason(reason: impl Into<String>, coords: Coords) -> Self {
+        Self {
+            reason: reason.into(),
+            coords,
+        }
+    }
+}
+
+/// An implementation of [fmt::Display](fmt::Display) trait
+impl fmt::Display for StartGoalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (x, y) = self.coords;
+        write!(
+            f,
+            "Cannot place start and goal. Reason: {}. Coords: x = {x}, y = {y}",
+            self.reason
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = StartGoalError::reason("Both are at the same cell", (2, 3));
+
+        assert_eq!(
+            error.to_string(),
+            "Cannot place start and goal. Reason: Both are at the same cell. Coords: x = 2, y = 3"
+        );
+    }
+}
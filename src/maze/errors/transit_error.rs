@@ -1,13 +1,17 @@
 use crate::utils::types::Coords;
 use std::fmt;
 
+/// An error of moving from a cell of a maze to its neighbor
 #[derive(Debug, Clone)]
 pub struct TransitError {
+    /// Coords of the cell the move started from
     pub coords: Coords,
+    /// Reason why the move failed
     pub reason: String,
 }
 
 impl TransitError {
+    /// Returns an error of moving from given coords for a given reason
     pub fn reason(reason: impl Into<String>, coords: Coords) -> Self {
         Self {
             reason: reason.into(),
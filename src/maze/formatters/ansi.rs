@@ -0,0 +1,298 @@
+use crate::maze::{
+    formatters::{Block, Formatter, StringWrapper, block_at},
+    grid::Grid,
+};
+use crate::utils::color::Color;
+use crate::utils::types::Coords;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// Escape sequence that resets the terminal colors
+const RESET: &str = "\x1b[0m";
+/// Color of the passages farthest from the origin of a distance field
+const HEAT_COLOR: Color = Color::RGB(220, 40, 40);
+
+/// A formatter to emit the maze as text colored with ANSI escape sequences for printing to a
+/// terminal
+///
+/// Every cell, wall and wall corner is a block of two characters, so a maze of `width` x `height`
+/// cells takes `2 * width + 1` blocks per line and `2 * height + 1` lines. Blocks are painted with
+/// 24-bit background colors, which most modern terminals support. Passages can be colored as a
+/// heat map of a distance field and a solution path can be drawn on top of them. With colors
+/// disabled, walls are drawn with `#`, the solution path with `*` and other passages stay blank.
+///
+/// # Example
+/// ```
+/// use bevy_knossos::maze::*;
+///
+/// let maze = OrthogonalMazeBuilder::new().width(5).height(5).seed(1).build().unwrap();
+/// let distances = maze
+///     .iter()
+///     .filter_map(|(coords, _)| Some((coords, maze.distance_between((0, 0), coords)?)))
+///     .collect();
+/// println!("{}", maze.format(AnsiColor::new().distance_field(distances)).into_inner());
+/// ```
+pub struct AnsiColor {
+    wall_color: Color,
+    passage_color: Color,
+    distance_field: Option<HashMap<Coords, u32>>,
+    solution: Option<(Vec<Coords>, Color)>,
+    colored: bool,
+}
+
+impl AnsiColor {
+    /// Returns a new instance of an [`AnsiColor`] formatter with black walls and white passages
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            wall_color: Color::RGB(0, 0, 0),
+            passage_color: Color::RGB(250, 250, 250),
+            distance_field: None,
+            solution: None,
+            colored: true,
+        }
+    }
+
+    /// Sets a wall color and returns itself
+    #[must_use]
+    pub const fn wall_color(mut self, color: Color) -> Self {
+        self.wall_color = color;
+        self
+    }
+
+    /// Sets a passage color and returns itself
+    #[must_use]
+    pub const fn passage_color(mut self, color: Color) -> Self {
+        self.passage_color = color;
+        self
+    }
+
+    /// Colors the passages as a heat map of given distances and returns itself
+    ///
+    /// The color of a cell fades from the passage color at the distance of 0 to red at the
+    /// largest distance of the field. Cells missing from the field keep the passage color.
+    #[must_use]
+    pub fn distance_field(mut self, distances: HashMap<Coords, u32>) -> Self {
+        self.distance_field = Some(distances);
+        self
+    }
+
+    /// Paints the cells of the `path` and the passages between consecutive ones with a given
+    /// color on top of the other passage colors and returns itself
+    #[must_use]
+    pub fn with_solution(mut self, path: Vec<Coords>, color: Color) -> Self {
+        self.solution = Some((path, color));
+        self
+    }
+
+    /// Disables colors, so the maze is emitted as plain ASCII, and returns itself
+    #[must_use]
+    pub const fn plain(mut self) -> Self {
+        self.colored = false;
+        self
+    }
+
+    /// Returns the color of a passage block that belongs to a given cell
+    fn cell_color(&self, coords: Coords, max_distance: u32) -> Color {
+        let distance = self
+            .distance_field
+            .as_ref()
+            .and_then(|distances| distances.get(&coords));
+        match distance {
+            Some(&distance) if max_distance > 0 => {
+                blend(self.passage_color, HEAT_COLOR, distance, max_distance)
+            }
+            _ => self.passage_color,
+        }
+    }
+}
+
+impl Default for AnsiColor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implementation of an ANSI color formatter
+impl Formatter<StringWrapper> for AnsiColor {
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        let max_distance = self
+            .distance_field
+            .as_ref()
+            .and_then(|distances| distances.values().max().copied())
+            .unwrap_or_default();
+        let (path, path_steps) = self.solution.as_ref().map_or_else(
+            || (HashSet::new(), HashSet::new()),
+            |(path, _)| {
+                let steps = path
+                    .windows(2)
+                    .flat_map(|step| [(step[0], step[1]), (step[1], step[0])])
+                    .collect();
+                (path.iter().copied().collect(), steps)
+            },
+        );
+
+        let mut output = String::new();
+        for block_y in 0..=grid.height() * 2 {
+            let mut current = None;
+            for block_x in 0..=grid.width() * 2 {
+                let block = block_at(grid, block_x, block_y);
+                let on_path = matches!(block, Block::Passage(coords, next)
+                    if path.contains(&coords)
+                        && next.is_none_or(|next| path_steps.contains(&(coords, next))));
+                if !self.colored {
+                    output.push_str(match block {
+                        Block::Wall => "##",
+                        Block::Passage(..) if on_path => "**",
+                        Block::Passage(..) => "  ",
+                    });
+                    continue;
+                }
+
+                let color = match (block, &self.solution) {
+                    (Block::Wall, _) => self.wall_color,
+                    (Block::Passage(..), Some((_, color))) if on_path => *color,
+                    (Block::Passage(coords, _), _) => self.cell_color(coords, max_distance),
+                };
+                if current != Some(color) {
+                    let Color::RGB(r, g, b) = color;
+                    write!(output, "\x1b[48;2;{r};{g};{b}m").unwrap();
+                    current = Some(color);
+                }
+                output.push_str("  ");
+            }
+            if self.colored {
+                output.push_str(RESET);
+            }
+            output.push('\n');
+        }
+
+        StringWrapper(output)
+    }
+}
+
+/// Returns a color between `from` and `to` in proportion to `value` out of `max`
+fn blend(from: Color, to: Color, value: u32, max: u32) -> Color {
+    let (value, max) = (u64::from(value.min(max)), u64::from(max));
+    let channel = |from: u8, to: u8| {
+        let mixed = (u64::from(from) * (max - value) + u64::from(to) * value) / max;
+        u8::try_from(mixed).unwrap_or(u8::MAX)
+    };
+    let (Color::RGB(r1, g1, b1), Color::RGB(r2, g2, b2)) = (from, to);
+    Color::RGB(channel(r1, r2), channel(g1, g2), channel(b1, b2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::grid::cell::Cell;
+
+    /// Asserts that every escape sequence of a line sets an RGB background or resets the colors
+    fn assert_well_formed(line: &str) {
+        assert!(line.ends_with(RESET), "{line:?}");
+        for sequence in line.split('\x1b').skip(1) {
+            let (code, _) = sequence[1..].split_once('m').unwrap();
+            assert!(sequence.starts_with('['), "{sequence:?}");
+            if code == "0" {
+                continue;
+            }
+            let parts: Vec<&str> = code.split(';').collect();
+            assert_eq!(parts[..2], ["48", "2"], "{code:?}");
+            assert_eq!(parts.len(), 5, "{code:?}");
+            assert!(parts[2..].iter().all(|part| part.parse::<u8>().is_ok()));
+        }
+    }
+
+    #[test]
+    fn format_plain() {
+        let mut expected = String::new();
+        expected.push_str("##########\n");
+        expected.push_str("##      ##\n");
+        expected.push_str("######  ##\n");
+        expected.push_str("##      ##\n");
+        expected.push_str("##########\n");
+
+        let actual = AnsiColor::new().plain().format(&generate_maze()).0;
+
+        assert_eq!(actual, expected);
+        assert!(actual.is_ascii());
+    }
+
+    #[test]
+    fn format_plain_with_solution() {
+        let actual = AnsiColor::new()
+            .with_solution(vec![(1, 1), (0, 1)], Color::RGB(0, 0, 255))
+            .plain()
+            .format(&generate_maze())
+            .0;
+        let lines: Vec<&str> = actual.lines().collect();
+
+        assert_eq!(lines[3], "##******##");
+        assert_eq!(lines[1], "##      ##");
+    }
+
+    #[test]
+    fn blend_large_distances() {
+        let from = Color::RGB(250, 250, 250);
+
+        assert_eq!(blend(from, HEAT_COLOR, u32::MAX, u32::MAX), HEAT_COLOR);
+        assert_eq!(blend(from, HEAT_COLOR, 0, u32::MAX), from);
+        assert_eq!(
+            blend(from, HEAT_COLOR, u32::MAX / 2, u32::MAX),
+            Color::RGB(235, 145, 145)
+        );
+    }
+
+    #[test]
+    fn escape_sequences_are_well_formed() {
+        let grid = generate_maze();
+        let mut distances = HashMap::new();
+        grid.flood_fill((0, 0), |coords, distance| {
+            distances.insert(coords, distance);
+        });
+        let formatter = AnsiColor::new()
+            .wall_color(Color::RGB(10, 20, 30))
+            .distance_field(distances)
+            .with_solution(vec![(1, 1), (0, 1)], Color::RGB(0, 0, 255));
+        let actual = formatter.format(&grid).0;
+        let lines: Vec<&str> = actual.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        lines.iter().copied().for_each(assert_well_formed);
+        assert_eq!(lines[0], "\x1b[48;2;10;20;30m          \x1b[0m");
+        // The heat map fades from the passage color at the start to red at the far end, which is
+        // covered by the path
+        assert_eq!(
+            lines[1],
+            "\x1b[48;2;10;20;30m  \x1b[48;2;250;250;250m    \x1b[48;2;240;180;180m  \
+             \x1b[48;2;10;20;30m  \x1b[0m"
+        );
+        assert_eq!(
+            lines[2],
+            "\x1b[48;2;10;20;30m      \x1b[48;2;240;180;180m  \x1b[48;2;10;20;30m  \x1b[0m"
+        );
+        assert_eq!(
+            lines[3],
+            "\x1b[48;2;10;20;30m  \x1b[48;2;0;0;255m      \x1b[48;2;10;20;30m  \x1b[0m"
+        );
+    }
+
+    /// Returns a 2x2 maze shaped like a hook:
+    ///
+    /// ```no_test
+    /// +---+---+
+    /// |       |
+    /// +---+   +
+    /// |       |
+    /// +---+---+
+    /// ```
+    fn generate_maze() -> Grid {
+        let mut grid = Grid::new(2, 2);
+
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::WEST).unwrap();
+
+        grid
+    }
+}
@@ -1,8 +1,10 @@
 use crate::maze::grid::cell::Cell;
 use crate::maze::{formatters::Formatter, grid::Grid};
-use std::fmt::Write;
+use crate::utils::types::Coords;
+use std::collections::{HashMap, HashSet};
+use std::io;
 
-use super::StringWrapper;
+use super::{StreamFormatter, StringWrapper, stream_grid};
 
 /// A formatter to emit the maze as ASCII with narrow passages
 ///
@@ -34,43 +36,314 @@ pub struct AsciiNarrow;
 /// ```
 pub struct AsciiBroad;
 
+impl AsciiBroad {
+    /// Returns a formatter that scales the cells by a given span
+    ///
+    /// See [`StyledAsciiBroad::span`].
+    #[must_use]
+    pub fn span(self, span: usize) -> StyledAsciiBroad {
+        StyledAsciiBroad::new().span(span)
+    }
+
+    /// Returns a formatter that renders only the given cells and covers the rest with fog
+    ///
+    /// See [`StyledAsciiBroad::reveal`].
+    #[must_use]
+    pub fn reveal(self, cells: &HashSet<Coords>) -> StyledAsciiBroad {
+        StyledAsciiBroad::new().reveal(cells)
+    }
+
+    /// Returns a formatter that writes a given label inside each of the given cells
+    ///
+    /// See [`StyledAsciiBroad::with_labels`].
+    #[must_use]
+    pub fn with_labels(self, labels: HashMap<Coords, String>) -> StyledAsciiBroad {
+        StyledAsciiBroad::new().with_labels(labels)
+    }
+
+    /// Returns a formatter that marks the cells of the shortest path between two cells
+    ///
+    /// See [`StyledAsciiBroad::with_solution`].
+    #[must_use]
+    pub fn with_solution(self, start: Coords, goal: Coords) -> StyledAsciiBroad {
+        StyledAsciiBroad::new().with_solution(start, goal)
+    }
+
+    /// Returns a formatter that marks the cells of a given path
+    ///
+    /// See [`StyledAsciiBroad::with_path`].
+    #[must_use]
+    pub fn with_path(self, path: Vec<Coords>) -> StyledAsciiBroad {
+        StyledAsciiBroad::new().with_path(path)
+    }
+}
+
+/// A broad ASCII formatter with wider cells, solution markers, labels or fog over unrevealed
+/// cells, while walls stay intact
+///
+/// Use [`AsciiBroad::span`], [`AsciiBroad::reveal`], [`AsciiBroad::with_solution`],
+/// [`AsciiBroad::with_path`] or [`AsciiBroad::with_labels`] to create one. The layers combine,
+/// so a maze can be rendered e.g. with a span, a solution and labels at once. A label takes
+/// precedence over the marker inside a cell, while fog covers both. Narrow ASCII is not
+/// supported, since a narrow cell is a single character that also draws its southern wall.
+///
+/// # Example:
+///
+/// ```no_test
+/// +---+---+---+---+
+/// | * |###########|
+/// +   +---+#######+
+/// | *   *  #######|
+/// +---+   +#######+
+/// |###############|
+/// +###############+
+/// |###############|
+/// +---+---+---+---+
+/// ```
+pub struct StyledAsciiBroad {
+    span: usize,
+    solution: Option<Solution>,
+    marker: char,
+    labels: HashMap<Coords, String>,
+    revealed: Option<HashSet<Coords>>,
+    fog: char,
+}
+
+enum Solution {
+    Between(Coords, Coords),
+    Path(Vec<Coords>),
+}
+
+impl StyledAsciiBroad {
+    /// Returns a formatter rendering the same maze as [`AsciiBroad`]
+    fn new() -> Self {
+        Self {
+            span: 1,
+            solution: None,
+            marker: '*',
+            labels: HashMap::new(),
+            revealed: None,
+            fog: '#',
+        }
+    }
+
+    /// Scales the cells by a given span and returns itself
+    ///
+    /// A cell with a span of `n` is `4n - 1` characters wide and `2n - 1` lines tall, so its
+    /// interior always has a middle line, where markers and labels are written. A span of 1
+    /// renders the same maze as [`AsciiBroad`], while a span of 0 is treated as 1.
+    #[must_use]
+    pub fn span(mut self, span: usize) -> Self {
+        self.span = span.max(1);
+        self
+    }
+
+    /// Renders only the given cells, covers the rest with fog and returns itself
+    #[must_use]
+    pub fn reveal(mut self, cells: &HashSet<Coords>) -> Self {
+        self.revealed = Some(cells.clone());
+        self
+    }
+
+    /// Sets a character used to cover unrevealed cells and returns itself
+    #[must_use]
+    pub const fn fog(mut self, fog: char) -> Self {
+        self.fog = fog;
+        self
+    }
+
+    /// Marks the cells of the shortest path between two cells and returns itself
+    ///
+    /// The path is computed when the maze is formatted. If `goal` can't be reached, no cells are
+    /// marked.
+    #[must_use]
+    pub fn with_solution(mut self, start: Coords, goal: Coords) -> Self {
+        self.solution = Some(Solution::Between(start, goal));
+        self
+    }
+
+    /// Marks the cells of a given path and returns itself
+    #[must_use]
+    pub fn with_path(mut self, path: Vec<Coords>) -> Self {
+        self.solution = Some(Solution::Path(path));
+        self
+    }
+
+    /// Sets a character used to mark the cells of the path and returns itself
+    #[must_use]
+    pub const fn marker(mut self, marker: char) -> Self {
+        self.marker = marker;
+        self
+    }
+
+    /// Writes a given label inside each of the given cells and returns itself
+    ///
+    /// Labels are centered and truncated to the width of a cell interior, i.e. 3 characters for
+    /// a span of 1. Cells without a label stay blank.
+    #[must_use]
+    pub fn with_labels(mut self, labels: HashMap<Coords, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Returns the number of characters inside a cell and the number of lines it takes
+    const fn cell_size(&self) -> (usize, usize) {
+        (4 * self.span - 1, 2 * self.span - 1)
+    }
+
+    fn path(&self, grid: &Grid) -> HashSet<Coords> {
+        match &self.solution {
+            Some(Solution::Between(start, goal)) => grid
+                .shortest_path(*start, *goal)
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+            Some(Solution::Path(path)) => path.iter().copied().collect(),
+            None => HashSet::new(),
+        }
+    }
+
+    /// Returns the middle line inside a cell
+    fn interior(&self, path: &HashSet<Coords>, coords: Coords) -> String {
+        let (cell_width, _) = self.cell_size();
+        let marker = if path.contains(&coords) {
+            self.marker
+        } else {
+            ' '
+        };
+        self.labels.get(&coords).map_or_else(
+            || format!("{marker:^cell_width$}"),
+            |label| {
+                let label: String = label.chars().take(cell_width).collect();
+                format!("{label:^cell_width$}")
+            },
+        )
+    }
+
+    fn is_fogged(&self, (width, height): (usize, usize), (x, y): Coords) -> bool {
+        x < width
+            && y < height
+            && self
+                .revealed
+                .as_ref()
+                .is_some_and(|revealed| !revealed.contains(&(x, y)))
+    }
+
+    /// Writes the top border of a maze above its first row, left open above the cells with
+    /// northern openings
+    fn write_top(&self, row: &[Cell], writer: &mut dyn io::Write) -> io::Result<()> {
+        let (cell_width, _) = self.cell_size();
+        let line = row.iter().fold("+".to_string(), |mut line, cell| {
+            let wall = if cell.contains(Cell::NORTH) { ' ' } else { '-' };
+            line.extend(std::iter::repeat_n(wall, cell_width));
+            line.push('+');
+            line
+        });
+        writeln!(writer, "{line}")
+    }
+
+    /// Writes a row of cells at a given `y` of a maze of a given size, with the middle line
+    /// inside each cell given by `interior`
+    fn write_row(
+        &self,
+        row: &[Cell],
+        (y, size): (usize, (usize, usize)),
+        interior: impl Fn(usize) -> String,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        let (cell_width, cell_height) = self.cell_size();
+        let fogged = |coords| self.is_fogged(size, coords);
+        let fog = self.fog.to_string().repeat(cell_width);
+        let blank = " ".repeat(cell_width);
+        let mut lines = vec![west_border(row).to_string(); cell_height];
+        let mut bottom_line = "+".to_string();
+
+        for (x, cell) in row.iter().enumerate() {
+            let covered = fogged((x, y));
+            // Boundaries between two fogged cells are covered as well, so unexplored areas look
+            // like solid blocks. The outer border always stays visible
+            let east = if covered && fogged((x + 1, y)) {
+                self.fog
+            } else if cell.contains(Cell::EAST) {
+                ' '
+            } else {
+                '|'
+            };
+            for (line_y, line) in lines.iter_mut().enumerate() {
+                if covered {
+                    line.push_str(&fog);
+                } else if line_y == self.span - 1 {
+                    line.push_str(&interior(x));
+                } else {
+                    line.push_str(&blank);
+                }
+                line.push(east);
+            }
+
+            if covered && fogged((x, y + 1)) {
+                bottom_line.push_str(&fog);
+            } else if cell.contains(Cell::SOUTH) {
+                bottom_line.push_str(&blank);
+            } else {
+                bottom_line.push_str(&"-".repeat(cell_width));
+            }
+            bottom_line.push(
+                if covered && fogged((x + 1, y)) && fogged((x, y + 1)) && fogged((x + 1, y + 1)) {
+                    self.fog
+                } else {
+                    '+'
+                },
+            );
+        }
+
+        for line in lines {
+            writeln!(writer, "{line}")?;
+        }
+        writeln!(writer, "{bottom_line}")
+    }
+}
+
 /// An implementation of a narrow ASCII formatter
 impl Formatter<StringWrapper> for AsciiNarrow {
     /// Converts a given grid into ASCII characters and returns an [`StringWrapper`] over that image
     fn format(&self, grid: &Grid) -> StringWrapper {
-        let mut result = String::new();
+        StringWrapper(stream_grid(self, grid))
+    }
+}
 
-        let top_border = "_".repeat(grid.width() * 2 - 1);
+/// An implementation of a narrow ASCII formatter writing a maze row by row
+impl StreamFormatter for AsciiNarrow {
+    fn write_header(&self, width: usize, writer: &mut dyn io::Write) -> io::Result<()> {
+        let top_border = "_".repeat(width * 2 - 1);
+        writeln!(writer, " {top_border} ")
+    }
 
-        writeln!(result, " {top_border} ").unwrap();
+    fn write_top(&self, row: &[Cell], writer: &mut dyn io::Write) -> io::Result<()> {
+        let top_border: Vec<&str> = row
+            .iter()
+            .map(|cell| if cell.contains(Cell::NORTH) { " " } else { "_" })
+            .collect();
+        writeln!(writer, " {} ", top_border.join("_"))
+    }
 
-        for y in 0..grid.height() {
-            write!(result, "|").unwrap();
+    fn write_row(&self, row: &[Cell], writer: &mut dyn io::Write) -> io::Result<()> {
+        let mut line = String::from(west_border(row));
 
-            for x in 0..grid.width() {
-                if grid.is_carved((x, y), Cell::SOUTH) {
-                    write!(result, " ").unwrap();
-                } else {
-                    write!(result, "_").unwrap();
-                }
+        for (x, cell) in row.iter().enumerate() {
+            line.push(if cell.contains(Cell::SOUTH) { ' ' } else { '_' });
 
-                if grid.is_carved((x, y), Cell::EAST) {
-                    if grid.is_carved((x, y), Cell::SOUTH)
-                        || grid.is_carved((x + 1, y), Cell::SOUTH)
-                    {
-                        write!(result, " ").unwrap();
-                    } else {
-                        write!(result, "_").unwrap();
-                    }
-                } else {
-                    write!(result, "|").unwrap();
-                }
+            if cell.contains(Cell::EAST) {
+                let south_open = cell.contains(Cell::SOUTH)
+                    || row
+                        .get(x + 1)
+                        .is_some_and(|next| next.contains(Cell::SOUTH));
+                line.push(if south_open { ' ' } else { '_' });
+            } else {
+                line.push('|');
             }
-
-            writeln!(result).unwrap();
         }
 
-        StringWrapper(result)
+        writeln!(writer, "{line}")
     }
 }
 
@@ -78,39 +351,59 @@ impl Formatter<StringWrapper> for AsciiNarrow {
 impl Formatter<StringWrapper> for AsciiBroad {
     /// Converts a given grid into ASCII characters and returns an [`StringWrapper`] over that image
     fn format(&self, grid: &Grid) -> StringWrapper {
-        let mut output = format!("+{}\n", "---+".to_string().repeat(grid.width()));
+        StringWrapper(stream_grid(self, grid))
+    }
+}
 
-        for y in 0..grid.height() {
-            let mut top_line = "|".to_string();
-            let mut bottom_line = "+".to_string();
+/// An implementation of a broad ASCII formatter writing a maze row by row
+impl StreamFormatter for AsciiBroad {
+    fn write_header(&self, width: usize, writer: &mut dyn io::Write) -> io::Result<()> {
+        writeln!(writer, "+{}", "---+".repeat(width))
+    }
 
-            for x in 0..grid.width() {
-                top_line.push_str("   ");
-                let east_boundary = if grid.is_carved((x, y), Cell::EAST) {
-                    " "
-                } else {
-                    "|"
-                };
-                top_line.push_str(east_boundary);
+    fn write_top(&self, row: &[Cell], writer: &mut dyn io::Write) -> io::Result<()> {
+        StyledAsciiBroad::new().write_top(row, writer)
+    }
 
-                let south_boundary = if grid.is_carved((x, y), Cell::SOUTH) {
-                    "   "
-                } else {
-                    "---"
-                };
-                bottom_line.push_str(south_boundary);
-                bottom_line.push('+');
-
-                if x == grid.width() - 1 {
-                    output.push_str(&top_line);
-                    output.push('\n');
-                    output.push_str(&bottom_line);
-                    output.push('\n');
-                }
-            }
+    fn write_row(&self, row: &[Cell], writer: &mut dyn io::Write) -> io::Result<()> {
+        // A plain maze has no fog, so neither the row nor the size of the maze matter
+        StyledAsciiBroad::new().write_row(row, (0, (0, 0)), |_| "   ".to_string(), writer)
+    }
+}
+
+/// An implementation of a broad ASCII formatter with layers drawn inside the cells
+impl Formatter<StringWrapper> for StyledAsciiBroad {
+    /// Converts a given grid into ASCII characters and returns an [`StringWrapper`] over that image
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        let path = self.path(grid);
+        let size = (grid.width(), grid.height());
+        let first_row: Vec<Cell> = (0..grid.width())
+            .map(|x| grid.get((x, 0)).copied().unwrap_or(Cell::empty()))
+            .collect();
+        let mut output = Vec::new();
+
+        self.write_top(&first_row, &mut output).unwrap();
+        for y in 0..grid.height() {
+            let row: Vec<Cell> = (0..grid.width()).map(|x| grid[(x, y)]).collect();
+            self.write_row(
+                &row,
+                (y, size),
+                |x| self.interior(&path, (x, y)),
+                &mut output,
+            )
+            .unwrap();
         }
 
-        StringWrapper(output)
+        StringWrapper(String::from_utf8(output).unwrap())
+    }
+}
+
+/// Returns the western border of a row, left open for an opening in the outer wall
+fn west_border(row: &[Cell]) -> &'static str {
+    if row.first().is_some_and(|cell| cell.contains(Cell::WEST)) {
+        " "
+    } else {
+        "|"
     }
 }
 
@@ -134,6 +427,100 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn format_openings() {
+        let mut grid = generate_maze();
+        assert!(grid.open_border((1, 0), Cell::NORTH));
+        assert!(grid.open_border((0, 2), Cell::WEST));
+        assert!(grid.open_border((3, 3), Cell::EAST));
+        assert!(grid.open_border((2, 3), Cell::SOUTH));
+
+        let narrow = AsciiNarrow.format(&grid).0;
+        let lines: Vec<&str> = narrow.lines().collect();
+        assert_eq!(lines[0], " __ ____ ");
+        assert_eq!(lines[3], "   _____|");
+        assert_eq!(lines[4], "|___   __");
+
+        let broad = AsciiBroad.format(&grid).0;
+        let lines: Vec<&str> = broad.lines().collect();
+        assert_eq!(lines[0], "+---+   +---+---+");
+        assert!(lines[5].starts_with(' '));
+        assert!(lines[7].ends_with("    "));
+        assert_eq!(lines[8], "+---+---+   +---+");
+        let all: HashSet<Coords> = (0..4).flat_map(|y| (0..4).map(move |x| (x, y))).collect();
+        assert_eq!(AsciiBroad.reveal(&all).format(&grid).0, broad);
+    }
+
+    #[test]
+    fn format_broad_with_solution() {
+        let grid = generate_maze();
+        let actual = AsciiBroad.with_solution((0, 0), (3, 3)).format(&grid).0;
+
+        let mut expected = String::new();
+        expected.push_str("+---+---+---+---+\n");
+        expected.push_str("| * |           |\n");
+        expected.push_str("+   +---+---+   +\n");
+        expected.push_str("| *   *     |   |\n");
+        expected.push_str("+---+   +---+   +\n");
+        expected.push_str("| *   *         |\n");
+        expected.push_str("+   +---+---+---+\n");
+        expected.push_str("| *   *   *   * |\n");
+        expected.push_str("+---+---+---+---+\n");
+
+        assert_eq!(actual, expected);
+        // Walls stay untouched
+        assert_eq!(actual.replace('*', " "), AsciiBroad.format(&grid).0);
+    }
+
+    #[test]
+    fn format_broad_with_labels() {
+        let grid = generate_maze();
+        let labels = HashMap::from([
+            ((0, 0), "0".to_string()),
+            ((1, 1), "12".to_string()),
+            ((2, 1), "K".to_string()),
+            ((2, 2), "1234".to_string()),
+        ]);
+        let actual = AsciiBroad.with_labels(labels).format(&grid).0;
+        let lines: Vec<&str> = actual.lines().collect();
+
+        assert_eq!(lines[1], "| 0 |           |");
+        assert_eq!(lines[3], "|    12   K |   |");
+        assert_eq!(lines[5], "|        123    |");
+        // Walls stay untouched
+        let blank = AsciiBroad.with_labels(HashMap::new()).format(&grid).0;
+        assert_eq!(blank, AsciiBroad.format(&grid).0);
+        assert_eq!(actual.len(), blank.len());
+    }
+
+    #[test]
+    fn format_broad_with_solution_and_labels() {
+        let grid = generate_maze();
+        let labels = HashMap::from([((0, 0), "S".to_string()), ((3, 3), "G".to_string())]);
+        let actual = AsciiBroad
+            .with_solution((0, 0), (3, 3))
+            .with_labels(labels)
+            .format(&grid)
+            .0;
+        let lines: Vec<&str> = actual.lines().collect();
+
+        assert_eq!(lines[1], "| S |           |");
+        assert_eq!(lines[7], "| *   *   *   G |");
+    }
+
+    #[test]
+    fn format_broad_with_precomputed_path() {
+        let grid = generate_maze();
+        let actual = AsciiBroad
+            .with_path(vec![(1, 1), (2, 1)])
+            .marker('o')
+            .format(&grid)
+            .0;
+
+        assert_eq!(actual.matches('o').count(), 2);
+        assert!(actual.contains("|     o   o |   |\n"));
+    }
+
     #[test]
     fn format_broad() {
         let mut expected = String::new();
@@ -154,6 +541,73 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn format_broad_with_span() {
+        let grid = generate_maze();
+
+        assert_eq!(
+            AsciiBroad.span(1).format(&grid).0,
+            AsciiBroad.format(&grid).0
+        );
+        assert_eq!(
+            AsciiBroad.span(0).format(&grid).0,
+            AsciiBroad.format(&grid).0
+        );
+
+        let actual = AsciiBroad.span(2).format(&grid).0;
+        let lines: Vec<&str> = actual.lines().collect();
+        assert_eq!(lines[0], "+-------+-------+-------+-------+");
+        for line in &lines[1..4] {
+            assert_eq!(*line, "|       |                       |");
+        }
+        assert_eq!(lines[4], "+       +-------+-------+       +");
+        assert_eq!(lines.len(), 4 * 4 + 1);
+        assert!(lines.iter().all(|line| line.chars().count() == 4 * 8 + 1));
+    }
+
+    #[test]
+    fn format_broad_with_span_solution_and_fog() {
+        let grid = generate_maze();
+        let revealed = HashSet::from([(0, 0), (0, 1), (1, 1)]);
+        let actual = AsciiBroad
+            .span(2)
+            .with_solution((0, 0), (3, 3))
+            .reveal(&revealed)
+            .fog('~')
+            .format(&grid)
+            .0;
+        let lines: Vec<&str> = actual.lines().collect();
+
+        assert_eq!(lines[0], "+-------+-------+-------+-------+");
+        assert_eq!(lines[1], "|       |~~~~~~~~~~~~~~~~~~~~~~~|");
+        assert_eq!(lines[2], "|   *   |~~~~~~~~~~~~~~~~~~~~~~~|");
+        assert_eq!(lines[4], "+       +-------+~~~~~~~~~~~~~~~+");
+        assert_eq!(lines[6], "|   *       *    ~~~~~~~~~~~~~~~|");
+        assert_eq!(lines[8], "+-------+       +~~~~~~~~~~~~~~~+");
+        assert_eq!(lines[16], "+-------+-------+-------+-------+");
+    }
+
+    #[test]
+    fn format_broad_with_fog() {
+        let mut expected = String::new();
+        expected.push_str("+---+---+---+---+\n");
+        expected.push_str("|   |###########|\n");
+        expected.push_str("+   +---+#######+\n");
+        expected.push_str("|        #######|\n");
+        expected.push_str("+---+   +#######+\n");
+        expected.push_str("|###############|\n");
+        expected.push_str("+###############+\n");
+        expected.push_str("|###############|\n");
+        expected.push_str("+---+---+---+---+\n");
+
+        let revealed = HashSet::from([(0, 0), (0, 1), (1, 1)]);
+        let formatter = AsciiBroad.reveal(&revealed);
+        let grid = generate_maze();
+        let actual = formatter.format(&grid).0;
+
+        assert_eq!(actual, expected);
+    }
+
     fn generate_maze() -> Grid {
         let mut grid = Grid::new(4, 4);
 
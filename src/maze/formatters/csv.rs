@@ -0,0 +1,178 @@
+use crate::maze::{
+    OrthogonalMaze,
+    errors::MazeLoadError,
+    formatters::{Formatter, StringWrapper},
+    grid::{Grid, cell::Cell},
+};
+use std::fmt::Write;
+
+use super::grid_from_cells;
+
+const HEADER: &str = "x,y,north,south,east,west";
+const DIRECTIONS: [Cell; 4] = [Cell::NORTH, Cell::SOUTH, Cell::EAST, Cell::WEST];
+
+/// A formatter to emit the maze as CSV for inspection in spreadsheets and simple data pipelines
+///
+/// Every cell takes a single row, where `1` marks a wall and `0` marks a passage. The rows are
+/// ordered from west to east and from north to south. The output can be loaded back with
+/// [`Csv::parse`].
+///
+/// # Example:
+///
+/// ```no_test
+/// x,y,north,south,east,west
+/// 0,0,1,1,0,1
+/// 1,0,1,1,1,0
+/// ```
+pub struct Csv;
+
+impl Csv {
+    /// Returns a maze loaded from the CSV emitted by the [`Csv`] formatter
+    ///
+    /// The maze size is taken from the largest cell coordinates.
+    ///
+    /// # Errors
+    /// Returns a [`MazeLoadError`] if the header or a row is malformed, the maze is too large, a
+    /// cell coordinate is missing or present more than once, or the walls of neighboring cells
+    /// don't match
+    pub fn parse(csv: &str) -> Result<OrthogonalMaze, MazeLoadError> {
+        let mut lines = csv.lines().map(str::trim).filter(|line| !line.is_empty());
+        if lines.next() != Some(HEADER) {
+            return Err(MazeLoadError::reason(format!(
+                "CSV header must be `{HEADER}`"
+            )));
+        }
+
+        let cells = lines
+            .enumerate()
+            .map(|(idx, line)| parse_row(line).ok_or_else(|| invalid_row(idx + 1, line)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let size = |coord: usize| {
+            coord.checked_add(1).ok_or_else(|| {
+                MazeLoadError::reason(format!("Cell coordinate {coord} is too large"))
+            })
+        };
+        let width = cells
+            .iter()
+            .map(|((x, _), _)| *x)
+            .max()
+            .map_or(Ok(0), size)?;
+        let height = cells
+            .iter()
+            .map(|((_, y), _)| *y)
+            .max()
+            .map_or(Ok(0), size)?;
+
+        grid_from_cells(width, height, cells).map(OrthogonalMaze::from_grid)
+    }
+}
+
+fn parse_row(line: &str) -> Option<((usize, usize), Cell)> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    let [x, y, walls @ ..] = fields.as_slice() else {
+        return None;
+    };
+    if walls.len() != DIRECTIONS.len() {
+        return None;
+    }
+
+    let mut cell = Cell::empty();
+    for (wall, direction) in walls.iter().zip(DIRECTIONS) {
+        match *wall {
+            "0" => cell.insert(direction),
+            "1" => {}
+            _ => return None,
+        }
+    }
+
+    Some(((x.parse().ok()?, y.parse().ok()?), cell))
+}
+
+fn invalid_row(row: usize, line: &str) -> MazeLoadError {
+    MazeLoadError::reason(format!("Invalid CSV row {row}: `{line}`"))
+}
+
+/// An implementation of a CSV formatter
+impl Formatter<StringWrapper> for Csv {
+    /// Converts a given grid into CSV rows and returns an [`StringWrapper`] over that string
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        let mut output = format!("{HEADER}\n");
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                let _ = write!(output, "{x},{y}");
+                for direction in DIRECTIONS {
+                    let wall = u8::from(!grid.is_carved((x, y), direction));
+                    let _ = write!(output, ",{wall}");
+                }
+                output.push('\n');
+            }
+        }
+
+        StringWrapper(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_and_parse() {
+        let mut grid = Grid::new(3, 2);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::EAST).unwrap();
+        grid.carve_passage((2, 1), Cell::NORTH).unwrap();
+        grid.carve_passage((0, 1), Cell::NORTH).unwrap();
+
+        let csv = Csv.format(&grid).into_inner();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "x,y,north,south,east,west");
+        assert_eq!(lines.len() - 1, 3 * 2);
+        assert_eq!(lines[1], "0,0,1,0,0,1");
+        assert_eq!(lines[2], "1,0,1,0,1,0");
+        assert_eq!(lines[6], "2,1,0,1,1,0");
+
+        let maze = Csv::parse(&csv).unwrap();
+        assert_eq!(maze.grid(), &grid);
+    }
+
+    #[test]
+    fn parse_invalid_csv() {
+        assert_eq!(
+            Csv::parse("x,y\n0,0,1,1,1,1").unwrap_err().reason,
+            "CSV header must be `x,y,north,south,east,west`"
+        );
+        assert_eq!(
+            Csv::parse("x,y,north,south,east,west\n0,0,1,1,1,2")
+                .unwrap_err()
+                .reason,
+            "Invalid CSV row 1: `0,0,1,1,1,2`"
+        );
+        assert_eq!(
+            Csv::parse("x,y,north,south,east,west\n1,0,1,1,1,1")
+                .unwrap_err()
+                .reason,
+            "A 2x1 maze must contain 2 cells, but contains 1"
+        );
+        assert_eq!(
+            Csv::parse("x,y,north,south,east,west\n0,0,1,1,0,1\n1,0,1,1,1,1")
+                .unwrap_err()
+                .reason,
+            "Walls of cell (0, 0) don't match its neighbors"
+        );
+        assert_eq!(
+            Csv::parse(&format!("{HEADER}\n{},0,1,1,1,1", usize::MAX))
+                .unwrap_err()
+                .reason,
+            format!("Cell coordinate {} is too large", usize::MAX)
+        );
+        assert!(
+            Csv::parse(&format!("{HEADER}\n999999999,999999999,1,1,1,1"))
+                .unwrap_err()
+                .reason
+                .contains("exceeds the limit")
+        );
+    }
+}
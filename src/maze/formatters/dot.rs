@@ -0,0 +1,122 @@
+use crate::maze::{
+    formatters::{Formatter, StringWrapper},
+    grid::{Grid, cell::Cell},
+};
+use std::fmt::Write;
+
+/// A formatter to emit the maze as a [GraphViz](https://graphviz.org) DOT graph
+///
+/// Every cell becomes a node named `c_x_y` and every carved passage becomes an undirected edge,
+/// which makes it easy to visualize the spanning tree of a maze or to find the loops added by
+/// braiding with graph tooling.
+///
+/// # Example:
+///
+/// ```no_test
+/// graph maze {
+///   c_0_0 [pos="0,0!"];
+///   c_1_0 [pos="1,0!"];
+///   c_0_0 -- c_1_0;
+/// }
+/// ```
+pub struct Dot {
+    positions: bool,
+}
+
+impl Dot {
+    /// Returns a new instance of a [`Dot`] formatter without node positions
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { positions: false }
+    }
+
+    /// Sets whether nodes get `pos` attributes and returns itself
+    ///
+    /// With positions, the `neato` layout engine places the nodes on a grid matching the maze. The
+    /// Y axis of `GraphViz` points up, so the rows are flipped to keep the north side on top.
+    #[must_use]
+    pub const fn positions(mut self, positions: bool) -> Self {
+        self.positions = positions;
+        self
+    }
+}
+
+impl Default for Dot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implementation of a DOT formatter
+impl Formatter<StringWrapper> for Dot {
+    /// Converts a given grid into a DOT graph and returns an [`StringWrapper`] over that string
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        let mut output = String::from("graph maze {\n");
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                if self.positions {
+                    let py = grid.height() - 1 - y;
+                    let _ = writeln!(output, "  c_{x}_{y} [pos=\"{x},{py}!\"];");
+                } else {
+                    let _ = writeln!(output, "  c_{x}_{y};");
+                }
+            }
+        }
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                // Openings in the outer wall don't lead to another node
+                if x + 1 < grid.width() && grid.is_carved((x, y), Cell::EAST) {
+                    let _ = writeln!(output, "  c_{x}_{y} -- c_{}_{y};", x + 1);
+                }
+                if y + 1 < grid.height() && grid.is_carved((x, y), Cell::SOUTH) {
+                    let _ = writeln!(output, "  c_{x}_{y} -- c_{x}_{};", y + 1);
+                }
+            }
+        }
+
+        output.push_str("}\n");
+        StringWrapper(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_grid() -> Grid {
+        let mut grid = Grid::new(3, 2);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::EAST).unwrap();
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((2, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::EAST).unwrap();
+        grid.carve_passage((1, 1), Cell::WEST).unwrap();
+        grid
+    }
+
+    #[test]
+    fn edges_match_carved_passages() {
+        let grid = generate_grid();
+        let dot = Dot::new().format(&grid).into_inner();
+
+        let edges: Vec<&str> = dot.lines().filter(|line| line.contains("--")).collect();
+        assert_eq!(edges.len(), 6);
+        assert!(edges.contains(&"  c_0_0 -- c_1_0;"));
+        assert!(edges.contains(&"  c_0_1 -- c_1_1;"));
+        assert!(edges.contains(&"  c_2_0 -- c_2_1;"));
+        assert!(dot.starts_with("graph maze {\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn nodes_with_positions() {
+        let grid = generate_grid();
+        let dot = Dot::new().positions(true).format(&grid).into_inner();
+
+        assert!(dot.contains("  c_0_0 [pos=\"0,1!\"];\n"));
+        assert!(dot.contains("  c_2_1 [pos=\"2,0!\"];\n"));
+        assert!(!Dot::new().format(&grid).into_inner().contains("pos="));
+    }
+}
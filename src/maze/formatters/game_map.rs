@@ -1,10 +1,13 @@
 use crate::{
     maze::{
+        OrthogonalMaze,
+        errors::StartGoalError,
         formatters::Formatter,
         grid::{Grid, cell::Cell},
     },
     utils::{rand::RandPositions, types::Coords},
 };
+use std::collections::HashMap;
 use std::fmt::Write;
 
 use super::StringWrapper;
@@ -14,6 +17,7 @@ pub struct NoStartGoal;
 pub struct WithStartGoal {
     start: char,
     goal: char,
+    cells: Option<(Coords, Coords)>,
 }
 
 impl ExtraState for NoStartGoal {}
@@ -26,7 +30,7 @@ impl ExtraState for WithStartGoal {}
 ///
 /// By default, it generates a self-contained map without predefined start and exit points. However, it also offers
 /// the option to randomly place the start and goal points along the map borders, ensuring a viable path between the
-/// two points.
+/// two points, or to place them inside given cells of the maze.
 ///
 /// # Examples:
 ///
@@ -64,8 +68,10 @@ pub struct GameMap<S: ExtraState> {
 
 struct GameMapState {
     span: usize,
+    explicit_span: bool,
     wall: char,
     passage: char,
+    markers: HashMap<Coords, char>,
 }
 
 /// An implementation of a formatter without predefined start and exit points
@@ -76,8 +82,10 @@ impl GameMap<NoStartGoal> {
         Self {
             state: Box::new(GameMapState {
                 span: 2,
+                explicit_span: false,
                 wall: '#',
                 passage: '.',
+                markers: HashMap::new(),
             }),
             extra: NoStartGoal,
         }
@@ -91,14 +99,54 @@ impl GameMap<NoStartGoal> {
             extra: WithStartGoal {
                 start: 'S',
                 goal: 'G',
+                cells: None,
             },
         }
     }
 
+    /// Returns a new instance of a [`GameMap`] formatter of a new type that places the start and
+    /// goal characters inside given cells of a maze
+    ///
+    /// Each character takes the top left corner of the passage area of its cell, shifted to the
+    /// center of that area for spans above 2, so it never replaces a wall.
+    ///
+    /// # Errors
+    /// Returns a [`StartGoalError`] if the start and the goal are placed at the same cell, a cell
+    /// is outside of the maze or masked off, or the span leaves no passage area to place them in
+    pub fn start_goal_at(
+        self,
+        maze: &OrthogonalMaze,
+        start: Coords,
+        goal: Coords,
+    ) -> Result<GameMap<WithStartGoal>, StartGoalError> {
+        if start == goal {
+            return Err(StartGoalError::reason("Both are at the same cell", start));
+        }
+        for coords in [start, goal] {
+            if !maze.grid().is_active(coords) {
+                return Err(StartGoalError::reason(
+                    "The cell is outside of the maze or masked off",
+                    coords,
+                ));
+            }
+        }
+        if self.state.span(maze.grid()) == 0 {
+            return Err(StartGoalError::reason(
+                "A span of 0 leaves only walls",
+                start,
+            ));
+        }
+
+        let mut map = self.with_start_goal();
+        map.extra.cells = Some((start, goal));
+        Ok(map)
+    }
+
     /// Sets a span (a distance between two walls) and returns itself
     #[must_use]
     pub fn span(mut self, span: usize) -> Self {
         self.state.span = span;
+        self.state.explicit_span = true;
         self
     }
 
@@ -115,9 +163,23 @@ impl GameMap<NoStartGoal> {
         self.state.passage = passage;
         self
     }
+
+    /// Sets item markers, e.g. pickups or spawn points, to draw inside given cells and returns
+    /// itself
+    ///
+    /// Markers are placed like the start and goal of [`start_goal_at`](Self::start_goal_at), so
+    /// they never replace a wall. The start and goal take precedence over markers in the same
+    /// cell, while markers in cells outside of the maze, or any markers with a span of 0, are
+    /// skipped.
+    #[must_use]
+    pub fn markers(mut self, markers: HashMap<Coords, char>) -> Self {
+        self.state.markers = markers;
+        self
+    }
 }
 
-/// An implementation of a formatter with the predefined start and goal points randomly placed along the map borders
+/// An implementation of a formatter with the predefined start and goal points either randomly placed along the map
+/// borders or placed at given cells
 impl GameMap<WithStartGoal> {
     /// Sets a goal character and returns itself
     #[must_use]
@@ -193,6 +255,25 @@ impl GameMap<WithStartGoal> {
     }
 }
 
+impl GameMapState {
+    /// Returns the span set explicitly or taken from the render scale of a grid
+    const fn span(&self, grid: &Grid) -> usize {
+        match grid.render_scale() {
+            Some(scale) if !self.explicit_span => scale.passage,
+            _ => self.span,
+        }
+    }
+
+    /// Draws the markers into the map of a grid, where `span` includes the place for a wall
+    fn place_markers(&self, map: &mut [char], grid: &Grid, span: usize) {
+        for (&coords, &marker) in &self.markers {
+            if let Some(index) = cell_index(grid, coords, span) {
+                map[index] = marker;
+            }
+        }
+    }
+}
+
 impl Default for GameMap<NoStartGoal> {
     fn default() -> Self {
         Self::new()
@@ -206,7 +287,7 @@ impl Formatter<StringWrapper> for GameMap<NoStartGoal> {
         let mut map = vec![];
 
         // Span (width of a passage) + 1 (place for a wall)
-        let span = self.state.span + 1;
+        let span = self.state.span(grid) + 1;
 
         let map_rows = grid.height() * span + 1;
         let map_cols = grid.width() * span + 1;
@@ -261,6 +342,8 @@ impl Formatter<StringWrapper> for GameMap<NoStartGoal> {
             }
         }
 
+        self.state.place_markers(&mut map, grid, span);
+
         // Write map to string
         let string_map = write_map(&map, map_cols);
 
@@ -275,7 +358,7 @@ impl Formatter<StringWrapper> for GameMap<WithStartGoal> {
         let mut map = vec![];
 
         // Span (width of a passage) + 1 (place for a wall)
-        let span = self.state.span + 1;
+        let span = self.state.span(grid) + 1;
 
         let map_rows = grid.height() * span + 1;
         let map_cols = grid.width() * span + 1;
@@ -330,11 +413,22 @@ impl Formatter<StringWrapper> for GameMap<WithStartGoal> {
             }
         }
 
-        // Get random start and goal points
-        let (start_idx, goal_idx) =
-            self.get_random_start_and_goal_positions(&map, map_cols, map_rows);
-        map[start_idx] = self.extra.start;
-        map[goal_idx] = self.extra.goal;
+        let (start_idx, goal_idx) = if let Some((start, goal)) = self.extra.cells {
+            (cell_index(grid, start, span), cell_index(grid, goal, span))
+        } else {
+            // Get random start and goal points
+            let (start_idx, goal_idx) =
+                self.get_random_start_and_goal_positions(&map, map_cols, map_rows);
+            (Some(start_idx), Some(goal_idx))
+        };
+
+        self.state.place_markers(&mut map, grid, span);
+        if let Some(start_idx) = start_idx {
+            map[start_idx] = self.extra.start;
+        }
+        if let Some(goal_idx) = goal_idx {
+            map[goal_idx] = self.extra.goal;
+        }
 
         // Write map to string
         let string_map = write_map(&map, map_cols);
@@ -343,6 +437,20 @@ impl Formatter<StringWrapper> for GameMap<WithStartGoal> {
     }
 }
 
+/// Returns the index of the map character in the middle of the passage area of a cell, where
+/// `span` includes the place for a wall, or `None` if the cell is outside of the grid or there's no
+/// passage area
+const fn cell_index(grid: &Grid, (x, y): Coords, span: usize) -> Option<usize> {
+    if x >= grid.width() || y >= grid.height() || span < 2 {
+        return None;
+    }
+
+    // The passage area is `span - 1` characters wide and follows a wall
+    let offset = 1 + (span - 2) / 2;
+    let map_cols = grid.width() * span + 1;
+    Some((y * span + offset) * map_cols + x * span + offset)
+}
+
 fn bottom_right_neighbour_exists(cx: usize, cy: usize, grid: &Grid) -> bool {
     if cy + 1 >= grid.width() || cx + 1 >= grid.height() {
         return false;
@@ -384,6 +492,7 @@ fn iter_neighbors((row, col): Coords, cols: usize, rows: usize) -> impl Iterator
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::maze::Mask;
 
     #[test]
     fn new_call() {
@@ -523,6 +632,126 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn format_with_start_and_goal_at_cells() {
+        let mut expected = String::new();
+        expected.push_str("#########\n");
+        expected.push_str("#S#.....#\n");
+        expected.push_str("#.#####.#\n");
+        expected.push_str("#.....#.#\n");
+        expected.push_str("###.###.#\n");
+        expected.push_str("#.......#\n");
+        expected.push_str("#.#######\n");
+        expected.push_str("#....G..#\n");
+        expected.push_str("#########\n");
+
+        let maze = OrthogonalMaze::from_grid(generate_maze());
+        let formatter = GameMap::new()
+            .span(1)
+            .start_goal_at(&maze, (0, 0), (2, 3))
+            .unwrap();
+        let actual = maze.format(formatter).0;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn start_and_goal_at_cells_land_in_the_middle_of_passages() {
+        let maze = OrthogonalMaze::from_grid(generate_maze());
+        let formatter = GameMap::new()
+            .span(3)
+            .start_goal_at(&maze, (1, 2), (3, 0))
+            .unwrap()
+            .start('@');
+        let actual = maze.format(formatter).0;
+        let lines: Vec<&str> = actual.lines().collect();
+
+        // A cell with a span of 3 takes 4 characters, its passage area starts right after a wall
+        assert_eq!(lines[2 * 4 + 2].chars().nth(4 + 2), Some('@'));
+        assert_eq!(lines[2].chars().nth(3 * 4 + 2), Some('G'));
+        assert_eq!(actual.matches('@').count(), 1);
+    }
+
+    #[test]
+    fn format_with_markers() {
+        let markers = HashMap::from([((2, 2), '$'), ((0, 3), 'k'), ((9, 9), '!')]);
+        let formatter = GameMap::new().span(3).markers(markers);
+        let actual = formatter.format(&generate_maze()).0;
+        let lines: Vec<&str> = actual.lines().collect();
+
+        // The passage area of (2, 2) spans rows and columns 9 to 11 of the map
+        assert_eq!(lines[10].chars().nth(10), Some('$'));
+        assert_eq!(lines[14].chars().nth(2), Some('k'));
+        assert_eq!(actual.matches(['$', 'k']).count(), 2);
+        assert!(!actual.contains('!'));
+        // Only passages are replaced
+        let plain = GameMap::new().span(3).format(&generate_maze()).0;
+        assert_eq!(plain.matches('#').count(), actual.matches('#').count());
+    }
+
+    #[test]
+    fn start_and_goal_take_precedence_over_markers() {
+        let maze = OrthogonalMaze::from_grid(generate_maze());
+        let formatter = GameMap::new()
+            .span(1)
+            .markers(HashMap::from([((0, 0), '$'), ((1, 0), 'k')]))
+            .start_goal_at(&maze, (0, 0), (3, 3))
+            .unwrap();
+        let actual = maze.format(formatter).0;
+
+        assert!(actual.starts_with("#########\n#S#k....#\n"));
+        assert!(!actual.contains('$'));
+    }
+
+    #[test]
+    fn start_and_goal_at_the_same_cell() {
+        let maze = OrthogonalMaze::from_grid(generate_maze());
+        let result = GameMap::new().start_goal_at(&maze, (1, 1), (1, 1));
+
+        assert_eq!(
+            result.err(),
+            Some(StartGoalError::reason("Both are at the same cell", (1, 1)))
+        );
+    }
+
+    #[test]
+    fn start_and_goal_outside_of_the_maze() {
+        let mut grid = generate_maze();
+        let mut mask = Mask::new(4, 4);
+        mask.set_active((2, 2), false);
+        grid.apply_mask(mask);
+        let maze = OrthogonalMaze::from_grid(grid);
+        let error = |start, goal| {
+            GameMap::new()
+                .start_goal_at(&maze, start, goal)
+                .err()
+                .map(|err| err.coords)
+        };
+
+        assert_eq!(error((0, 0), (7, 7)), Some((7, 7)));
+        assert_eq!(error((4, 0), (0, 0)), Some((4, 0)));
+        assert_eq!(error((0, 0), (2, 2)), Some((2, 2)));
+        assert_eq!(error((0, 0), (3, 3)), None);
+    }
+
+    #[test]
+    fn start_goal_and_markers_need_a_passage_area() {
+        let maze = OrthogonalMaze::from_grid(generate_maze());
+        let result = GameMap::new().span(0).start_goal_at(&maze, (0, 0), (3, 3));
+        let markers = GameMap::new()
+            .span(0)
+            .markers(HashMap::from([((2, 2), '$')]));
+
+        assert_eq!(
+            result.err(),
+            Some(StartGoalError::reason(
+                "A span of 0 leaves only walls",
+                (0, 0)
+            ))
+        );
+        assert!(!maze.format(markers).0.contains('$'));
+    }
+
     fn generate_maze() -> Grid {
         let mut grid = Grid::new(4, 4);
 
@@ -0,0 +1,238 @@
+use crate::maze::{
+    formatters::{Formatter, ImageWrapper},
+    grid::{Grid, cell::Cell, topology::Topology},
+};
+use crate::utils::color::Color;
+use image::RgbImage;
+
+/// Walls of a hexagon in the order of its corners, starting from the upper right one
+const SIDES: [Cell; 6] = [
+    Cell::EAST,
+    Cell::SOUTH_EAST,
+    Cell::SOUTH_WEST,
+    Cell::WEST,
+    Cell::NORTH_WEST,
+    Cell::NORTH_EAST,
+];
+
+/// An image formatter for hexagonal mazes
+///
+/// Cells are drawn as pointy-top hexagons, with every odd row shifted by half a cell to the east,
+/// matching the layout of a [`HexMaze`](crate::maze::HexMaze). Only hex grids are supported, grids
+/// of other topologies are rendered as a blank image.
+#[derive(Clone)]
+pub struct HexImage {
+    cell_size: usize,
+    wall_width: usize,
+    margin: usize,
+    background_color: Color,
+    foreground_color: Color,
+}
+
+impl HexImage {
+    /// Returns a new instance of a [`HexImage`] formatter with a default settings
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            cell_size: 20,
+            wall_width: 4,
+            margin: 20,
+            background_color: Color::RGB(250, 250, 250),
+            foreground_color: Color::RGB(0, 0, 0),
+        }
+    }
+
+    /// Sets a cell size, i.e. the distance from the center of a hexagon to its corners, and
+    /// returns itself
+    #[must_use]
+    pub const fn cell(mut self, size: usize) -> Self {
+        self.cell_size = size;
+        self
+    }
+
+    /// Sets a wall width and returns itself
+    #[must_use]
+    pub const fn wall(mut self, width: usize) -> Self {
+        self.wall_width = width;
+        self
+    }
+
+    /// Sets a margin and returns itself
+    #[must_use]
+    pub const fn margin(mut self, value: usize) -> Self {
+        self.margin = value;
+        self
+    }
+
+    /// Sets a background color and returns itself
+    #[must_use]
+    pub const fn background(mut self, color: Color) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    /// Sets a foreground color and returns itself
+    #[must_use]
+    pub const fn foreground(mut self, color: Color) -> Self {
+        self.foreground_color = color;
+        self
+    }
+
+    /// Returns the pixel coords of the center of a cell
+    fn center(&self, (x, y): (usize, usize)) -> (f64, f64) {
+        let size = self.cell_size as f64;
+        let shift = if y % 2 == 1 { 0.5 } else { 0.0 };
+        let cx = 3f64
+            .sqrt()
+            .mul_add(size * (x as f64 + shift + 0.5), self.margin as f64);
+        let cy = (1.5 * size).mul_add(y as f64, size + self.margin as f64);
+        (cx, cy)
+    }
+
+    /// Returns the pixel coords of the corners of a cell, starting from the upper right one and
+    /// going clockwise
+    fn corners(&self, coords: (usize, usize)) -> [(f64, f64); 6] {
+        let (cx, cy) = self.center(coords);
+        let size = self.cell_size as f64;
+        std::array::from_fn(|index| {
+            let angle = 60f64.mul_add(index as f64, -30.0).to_radians();
+            (size.mul_add(angle.cos(), cx), size.mul_add(angle.sin(), cy))
+        })
+    }
+
+    fn sizes(&self, grid: &Grid) -> (u32, u32) {
+        let size = self.cell_size as f64;
+        let shift = if grid.height() > 1 { 0.5 } else { 0.0 };
+        let width = 3f64.sqrt() * size * (grid.width() as f64 + shift);
+        let height = if grid.height() == 0 {
+            0.0
+        } else {
+            (1.5 * size).mul_add(grid.height() as f64 - 1.0, 2.0 * size)
+        };
+
+        let margin = self.margin * 2;
+        (
+            (width.ceil() as usize + margin) as u32,
+            (height.ceil() as usize + margin) as u32,
+        )
+    }
+
+    fn draw_line(&self, image: &mut RgbImage, from: (f64, f64), to: (f64, f64)) {
+        let pixel = match self.foreground_color {
+            Color::RGB(r, g, b) => image::Rgb([r, g, b]),
+        };
+        let radius = self.wall_width as f64 / 2.0;
+        let steps = (to.0 - from.0)
+            .abs()
+            .max((to.1 - from.1).abs())
+            .ceil()
+            .max(1.0);
+
+        for step in 0..=steps as usize {
+            let t = step as f64 / steps;
+            let x = (to.0 - from.0).mul_add(t, from.0);
+            let y = (to.1 - from.1).mul_add(t, from.1);
+
+            let left = (x - radius).floor().max(0.0) as u32;
+            let top = (y - radius).floor().max(0.0) as u32;
+            let right = ((x + radius).ceil() as u32).min(image.width());
+            let bottom = ((y + radius).ceil() as u32).min(image.height());
+            for py in top..bottom {
+                for px in left..right {
+                    let (dx, dy) = (f64::from(px) + 0.5 - x, f64::from(py) + 0.5 - y);
+                    if dx.hypot(dy) <= radius {
+                        image.put_pixel(px, py, pixel);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Default for HexImage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implementation of a formatter
+impl Formatter<ImageWrapper> for HexImage {
+    /// Converts a given hex grid into an image and returns an [`ImageWrapper`] over that image
+    fn format(&self, grid: &Grid) -> ImageWrapper {
+        let (width, height) = self.sizes(grid);
+        let background = match self.background_color {
+            Color::RGB(r, g, b) => image::Rgb([r, g, b]),
+        };
+        let mut image = RgbImage::from_pixel(width, height, background);
+
+        if grid.topology() != Topology::HexOddR {
+            return ImageWrapper(image);
+        }
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                let corners = self.corners((x, y));
+                for (index, side) in SIDES.iter().enumerate() {
+                    if !grid.is_carved((x, y), *side) {
+                        self.draw_line(&mut image, corners[index], corners[(index + 1) % 6]);
+                    }
+                }
+            }
+        }
+
+        ImageWrapper(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_call_default_params() {
+        let formatter = HexImage::new();
+        assert_eq!(20, formatter.cell_size);
+        assert_eq!(4, formatter.wall_width);
+        assert_eq!(20, formatter.margin);
+    }
+
+    #[test]
+    fn walls_are_drawn_only_where_not_carved() {
+        let mut grid = Grid::new_hex(2, 2);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((0, 0), Cell::SOUTH_EAST).unwrap();
+        grid.carve_passage((0, 1), Cell::EAST).unwrap();
+
+        let formatter = HexImage::new().cell(20).wall(2).margin(10);
+        let image = formatter.format(&grid).into_inner();
+        let pixel = |(x, y): (f64, f64)| *image.get_pixel(x as u32, y as u32);
+        let midpoint = |coords, index: usize| {
+            let corners = formatter.corners(coords);
+            let (a, b) = (corners[index], corners[(index + 1) % 6]);
+            (f64::midpoint(a.0, b.0), f64::midpoint(a.1, b.1))
+        };
+
+        let foreground = image::Rgb([0, 0, 0]);
+        let background = image::Rgb([250, 250, 250]);
+        assert_eq!(pixel(formatter.center((0, 0))), background);
+        // East side of (0, 0) is open, west side is a wall
+        assert_eq!(pixel(midpoint((0, 0), 0)), background);
+        assert_eq!(pixel(midpoint((0, 0), 3)), foreground);
+        // South-east side of (0, 0) leads to (0, 1) on the shifted odd row
+        assert_eq!(pixel(midpoint((0, 0), 1)), background);
+        assert_eq!(pixel(midpoint((1, 0), 2)), foreground);
+        assert_eq!(pixel(midpoint((0, 1), 0)), background);
+        assert_eq!(pixel(midpoint((1, 1), 0)), foreground);
+    }
+
+    #[test]
+    fn orthogonal_grid_renders_blank() {
+        let grid = Grid::new(2, 2);
+        let image = HexImage::new().format(&grid).into_inner();
+        assert!(
+            image
+                .pixels()
+                .all(|pixel| *pixel == image::Rgb([250, 250, 250]))
+        );
+    }
+}
@@ -1,18 +1,37 @@
 use crate::maze::grid::cell::Cell;
-use crate::maze::{formatters::Formatter, grid::Grid};
+use crate::maze::grid::overpass::Overpass;
+use crate::maze::{OrthogonalMaze, formatters::Formatter, grid::Grid};
 use crate::utils::color::Color;
 use crate::utils::types::Coords;
 use image::{ImageBuffer, RgbImage};
+use std::collections::HashSet;
 
-use super::ImageWrapper;
+use super::font::{GLYPH_HEIGHT, GLYPH_WIDTH, glyph};
+use super::{ExplicitSizes, ImageWrapper, RenderScale};
+
+/// Size of a single font pixel of legend labels in image pixels
+const LEGEND_FONT_SCALE: usize = 4;
+/// Height of a legend swatch and its label in pixels
+const LEGEND_ENTRY_HEIGHT: usize = GLYPH_HEIGHT * LEGEND_FONT_SCALE;
+/// Space around and between legend entries in pixels
+const LEGEND_SPACING: usize = 10;
 
 /// An Image formatter for a generated maze
+#[derive(Clone)]
 pub struct Image {
     wall_width: usize,
     passage_width: usize,
     margin: usize,
     background_color: Color,
     foreground_color: Color,
+    passage_texture: Option<RgbImage>,
+    thin_wall_width: Option<usize>,
+    revealed: Option<HashSet<Coords>>,
+    fog_color: Color,
+    legend: Vec<(Color, String)>,
+    dead_end_color: Option<Color>,
+    solution: Option<(Vec<Coords>, Color)>,
+    explicit_sizes: ExplicitSizes,
 }
 
 impl Image {
@@ -24,7 +43,15 @@ impl Image {
             passage_width: 40,
             background_color: Color::RGB(250, 250, 250),
             foreground_color: Color::RGB(0, 0, 0),
+            passage_texture: None,
             margin: 50,
+            thin_wall_width: None,
+            revealed: None,
+            fog_color: Color::RGB(64, 64, 64),
+            legend: Vec::new(),
+            dead_end_color: None,
+            solution: None,
+            explicit_sizes: ExplicitSizes::NONE,
         }
     }
 
@@ -32,6 +59,7 @@ impl Image {
     #[must_use]
     pub const fn wall(mut self, width: usize) -> Self {
         self.wall_width = width;
+        self.explicit_sizes.wall = true;
         self
     }
 
@@ -39,6 +67,7 @@ impl Image {
     #[must_use]
     pub const fn passage(mut self, width: usize) -> Self {
         self.passage_width = width;
+        self.explicit_sizes.passage = true;
         self
     }
 
@@ -56,13 +85,173 @@ impl Image {
         self
     }
 
+    /// Fills passages with a given texture instead of the background color and returns itself
+    ///
+    /// The texture is tiled across the maze starting from its top left corner, so textures smaller
+    /// than a cell repeat within it, while larger ones are cropped by the walls. The margin keeps
+    /// the background color. An empty texture is ignored.
+    #[must_use]
+    pub fn passage_texture(mut self, texture: RgbImage) -> Self {
+        self.passage_texture = Some(texture);
+        self
+    }
+
     /// Sets a margin (a distance between a maze and the image borders) and returns itself
     #[must_use]
     pub const fn margin(mut self, value: usize) -> Self {
         self.margin = value;
+        self.explicit_sizes.margin = true;
+        self
+    }
+
+    /// Renders walls as thin lines of a given width instead of solid blocks and returns itself
+    ///
+    /// Each wall is drawn as a line running through the middle of the wall area, so passages
+    /// become the dominant filled area of the image. Useful when the maze is meant to be printed.
+    #[must_use]
+    pub const fn thin_walls(mut self, line_width: usize) -> Self {
+        self.thin_wall_width = Some(line_width);
+        self
+    }
+
+    /// Renders only the given cells and covers the rest of the maze with fog and returns itself
+    ///
+    /// Useful for exploration games, where the map is revealed progressively as the player
+    /// discovers new cells.
+    #[must_use]
+    pub fn reveal(mut self, cells: &HashSet<Coords>) -> Self {
+        self.revealed = Some(cells.clone());
+        self
+    }
+
+    /// Sets a color used to cover unrevealed cells and returns itself
+    #[must_use]
+    pub const fn fog(mut self, color: Color) -> Self {
+        self.fog_color = color;
+        self
+    }
+
+    /// Adds a legend strip below the maze with a color swatch and a label for each entry and
+    /// returns itself
+    ///
+    /// Labels are drawn in the foreground color with a built-in bitmap font, which supports
+    /// latin letters, digits and a few punctuation characters. The image grows to fit the legend.
+    #[must_use]
+    pub fn with_legend(mut self, entries: Vec<(Color, String)>) -> Self {
+        self.legend = entries;
+        self
+    }
+
+    /// Fills the passages of dead ends, i.e. cells with three walls, with a given color instead of
+    /// the background color and returns itself
+    #[must_use]
+    pub const fn highlight_dead_ends(mut self, color: Color) -> Self {
+        self.dead_end_color = Some(color);
+        self
+    }
+
+    /// Draws a solution line through the centers of the `path` cells in a given color on top of
+    /// the passages and returns itself
+    ///
+    /// The line is a third of the passage width thick. Consecutive cells that aren't neighbors are
+    /// marked, but not connected, and an empty path draws nothing.
+    #[must_use]
+    pub fn with_solution(mut self, path: Vec<Coords>, color: Color) -> Self {
+        self.solution = Some((path, color));
         self
     }
 
+    /// Renders a maze and splits it into pages of `page_width` x `page_height` cells for printing
+    ///
+    /// Returns the column and row of each page along with its image, in row-major order. Every
+    /// page overlaps its neighbors by the margin size and has crop marks at the corners of its
+    /// own area, so printed sheets can be aligned and taped together.
+    #[must_use]
+    pub fn tiled_pages(
+        &self,
+        maze: &OrthogonalMaze,
+        page_width: usize,
+        page_height: usize,
+    ) -> Vec<(usize, usize, RgbImage)> {
+        let grid = maze.grid();
+        let (page_width, page_height) = (page_width.max(1), page_height.max(1));
+        let full = self.format(grid).into_inner();
+        let inner_cell_width = self.cell_width() - self.wall_width;
+
+        // Returns the pixel range of a page along one axis, without and with the overlap
+        let page_range = |page: usize, size: usize, cells: usize, image_size: u32| {
+            let start = self.margin + page * size * inner_cell_width;
+            let end =
+                self.margin + ((page + 1) * size).min(cells) * inner_cell_width + self.wall_width;
+            let from = start.saturating_sub(self.margin);
+            let to = (end + self.margin).min(image_size as usize);
+            (start, end, from, to)
+        };
+
+        let columns = grid.width().div_ceil(page_width);
+        let rows = grid.height().div_ceil(page_height);
+        let mut pages = Vec::with_capacity(columns * rows);
+
+        for row in 0..rows {
+            let (top, bottom, from_y, to_y) =
+                page_range(row, page_height, grid.height(), full.height());
+            for column in 0..columns {
+                let (left, right, from_x, to_x) =
+                    page_range(column, page_width, grid.width(), full.width());
+
+                let mut page = image::imageops::crop_imm(
+                    &full,
+                    from_x as u32,
+                    from_y as u32,
+                    (to_x - from_x) as u32,
+                    (to_y - from_y) as u32,
+                )
+                .to_image();
+                self.draw_crop_marks(
+                    &mut page,
+                    (left - from_x, top - from_y),
+                    (right - from_x, bottom - from_y),
+                );
+                pages.push((column, row, page));
+            }
+        }
+
+        pages
+    }
+
+    /// Draws lines that extend the edges of a page area outwards from its corners
+    fn draw_crop_marks(&self, page: &mut RgbImage, top_left: Coords, bottom_right: Coords) {
+        let (left, top) = top_left;
+        let (right, bottom) = bottom_right;
+        let (width, height) = (page.width() as usize, page.height() as usize);
+
+        for x in [left, right.saturating_sub(1)] {
+            Self::fill_rect(page, (x, 0), (x + 1, top), self.foreground_color);
+            Self::fill_rect(page, (x, bottom), (x + 1, height), self.foreground_color);
+        }
+        for y in [top, bottom.saturating_sub(1)] {
+            Self::fill_rect(page, (0, y), (left, y + 1), self.foreground_color);
+            Self::fill_rect(page, (right, y), (width, y + 1), self.foreground_color);
+        }
+    }
+
+    /// Returns a copy of the formatter with the sizes that weren't set explicitly taken from a
+    /// given scale
+    fn scaled(&self, scale: RenderScale) -> Self {
+        let mut image = self.clone();
+        if !self.explicit_sizes.wall {
+            image.wall_width = scale.wall;
+        }
+        if !self.explicit_sizes.passage {
+            image.passage_width = scale.passage;
+        }
+        if !self.explicit_sizes.margin {
+            image.margin = scale.margin;
+        }
+        image.explicit_sizes = ExplicitSizes::ALL;
+        image
+    }
+
     const fn cell_width(&self) -> usize {
         self.wall_width * 2 + self.passage_width
     }
@@ -81,6 +270,59 @@ impl Image {
         (image_width, image_height)
     }
 
+    fn legend_sizes(&self) -> (usize, usize) {
+        if self.legend.is_empty() {
+            return (0, 0);
+        }
+
+        let longest_label = self
+            .legend
+            .iter()
+            .map(|(_, label)| label.chars().count())
+            .max()
+            .unwrap_or(0);
+        let width = LEGEND_ENTRY_HEIGHT
+            + LEGEND_SPACING
+            + longest_label * (GLYPH_WIDTH + 1) * LEGEND_FONT_SCALE
+            + LEGEND_SPACING * 2;
+        let height = self.legend.len() * (LEGEND_ENTRY_HEIGHT + LEGEND_SPACING) + LEGEND_SPACING;
+
+        (width, height)
+    }
+
+    fn draw_legend(&self, image: &mut RgbImage, top: usize) {
+        for (index, (color, label)) in self.legend.iter().enumerate() {
+            let left = LEGEND_SPACING;
+            let entry_top = top + index * (LEGEND_ENTRY_HEIGHT + LEGEND_SPACING);
+            Self::fill_rect(
+                image,
+                (left, entry_top),
+                (left + LEGEND_ENTRY_HEIGHT, entry_top + LEGEND_ENTRY_HEIGHT),
+                *color,
+            );
+
+            let text_left = left + LEGEND_ENTRY_HEIGHT + LEGEND_SPACING;
+            for (char_index, c) in label.chars().enumerate() {
+                let char_left = text_left + char_index * (GLYPH_WIDTH + 1) * LEGEND_FONT_SCALE;
+                for (row, bits) in glyph(c).iter().enumerate() {
+                    for column in 0..GLYPH_WIDTH {
+                        if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                            continue;
+                        }
+                        let x = char_left + column * LEGEND_FONT_SCALE;
+                        let y = entry_top + row * LEGEND_FONT_SCALE;
+                        Self::fill_rect(
+                            image,
+                            (x, y),
+                            (x + LEGEND_FONT_SCALE, y + LEGEND_FONT_SCALE),
+                            self.foreground_color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     fn fill_background(&self, image: &mut RgbImage) {
         for (_, _, pixel) in image.enumerate_pixels_mut() {
             *pixel = match self.background_color {
@@ -89,10 +331,244 @@ impl Image {
         }
     }
 
+    /// Tiles the passage texture over the maze area, so walls drawn afterwards cover it
+    fn fill_texture(&self, image: &mut RgbImage, texture: &RgbImage, maze_size: Coords) {
+        let (width, height) = maze_size;
+        if texture.width() == 0 || texture.height() == 0 {
+            return;
+        }
+
+        for py in self.margin..height - self.margin {
+            for px in self.margin..width - self.margin {
+                let tx = (px - self.margin) as u32 % texture.width();
+                let ty = (py - self.margin) as u32 % texture.height();
+                *image.get_pixel_mut(px as u32, py as u32) = *texture.get_pixel(tx, ty);
+            }
+        }
+    }
+
     fn draw_maze(&self, image: &mut RgbImage, grid: &Grid) {
+        if let Some(color) = self.dead_end_color {
+            self.fill_dead_ends(image, grid, color);
+        }
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                self.draw_any_cell((x, y), grid, image);
+            }
+        }
+        self.draw_overpasses(image, grid);
+        self.fill_masked_cells(image, grid);
+    }
+
+    fn draw_solution(&self, image: &mut RgbImage, path: &[Coords], color: Color) {
+        let inner_cell_width = self.cell_width() - self.wall_width;
+        let thickness = (self.passage_width / 3).max(1);
+        let center = |(x, y): Coords| {
+            let offset = self.margin + self.wall_width + self.passage_width / 2;
+            (
+                x * inner_cell_width + offset - thickness / 2,
+                y * inner_cell_width + offset - thickness / 2,
+            )
+        };
+
+        for &coords in path {
+            let (x, y) = center(coords);
+            Self::fill_rect(image, (x, y), (x + thickness, y + thickness), color);
+        }
+        for segment in path.windows(2) {
+            let (from, to) = (segment[0], segment[1]);
+            if from.0.abs_diff(to.0) + from.1.abs_diff(to.1) != 1 {
+                continue;
+            }
+            let ((from_x, from_y), (to_x, to_y)) = (center(from), center(to));
+            Self::fill_rect(
+                image,
+                (from_x.min(to_x), from_y.min(to_y)),
+                (from_x.max(to_x) + thickness, from_y.max(to_y) + thickness),
+                color,
+            );
+        }
+    }
+
+    /// Closes the sides of the corridors on top at the crossings of a weave maze, so the
+    /// corridors tunneling under them look interrupted
+    fn draw_overpasses(&self, image: &mut RgbImage, grid: &Grid) {
+        let inner_cell_width = self.cell_width() - self.wall_width;
+
         for y in 0..grid.height() {
             for x in 0..grid.width() {
-                self.draw_cell((x, y), grid, image);
+                let Some(overpass) = grid.overpass((x, y)) else {
+                    continue;
+                };
+
+                let (left, top, right, bottom, side) = self.thin_wall_width.map_or_else(
+                    || {
+                        let cell_x = x * inner_cell_width + self.margin;
+                        let cell_y = y * inner_cell_width + self.margin;
+                        let cell_width = self.cell_width();
+                        let (right, bottom) = (cell_x + cell_width, cell_y + cell_width);
+                        (cell_x, cell_y, right, bottom, self.wall_width)
+                    },
+                    |line_width| {
+                        let (left, top, right, bottom) = self.thin_cell_bounds((x, y), line_width);
+                        (left, top, right, bottom, line_width)
+                    },
+                );
+                let sides = match overpass {
+                    Overpass::NorthSouth => [
+                        ((left, top), (left + side, bottom)),
+                        ((right - side, top), (right, bottom)),
+                    ],
+                    Overpass::EastWest => [
+                        ((left, top), (right, top + side)),
+                        ((left, bottom - side), (right, bottom)),
+                    ],
+                };
+
+                for (from, to) in sides {
+                    Self::fill_rect(image, from, to, self.foreground_color);
+                }
+            }
+        }
+    }
+
+    fn fill_masked_cells(&self, image: &mut RgbImage, grid: &Grid) {
+        let inner_cell_width = self.cell_width() - self.wall_width;
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                if grid.is_active((x, y)) {
+                    continue;
+                }
+                let cell_x = x * inner_cell_width + self.margin;
+                let cell_y = y * inner_cell_width + self.margin;
+                let to = (cell_x + self.cell_width(), cell_y + self.cell_width());
+                Self::fill_rect(image, (cell_x, cell_y), to, self.foreground_color);
+            }
+        }
+    }
+
+    fn fill_dead_ends(&self, image: &mut RgbImage, grid: &Grid, color: Color) {
+        let inner_cell_width = self.cell_width() - self.wall_width;
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                if grid[(x, y)].walls_count_sq() != 3 {
+                    continue;
+                }
+                // Walls are drawn over the filled area afterwards, so filling the whole space
+                // between the walls is enough
+                let cell_x = x * inner_cell_width + self.margin;
+                let cell_y = y * inner_cell_width + self.margin;
+                let from = (cell_x + self.wall_width, cell_y + self.wall_width);
+                let to = (cell_x + inner_cell_width, cell_y + inner_cell_width);
+                Self::fill_rect(image, from, to, color);
+            }
+        }
+    }
+
+    fn draw_any_cell(&self, coords: Coords, grid: &Grid, image: &mut RgbImage) {
+        if let Some(line_width) = self.thin_wall_width {
+            self.draw_thin_cell(coords, grid, image, line_width);
+        } else {
+            self.draw_cell(coords, grid, image);
+        }
+    }
+
+    fn draw_fog(&self, image: &mut RgbImage, grid: &Grid, revealed: &HashSet<Coords>) {
+        let inner_cell_width = self.cell_width() - self.wall_width;
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                if revealed.contains(&(x, y)) {
+                    continue;
+                }
+                let cell_x = x * inner_cell_width + self.margin;
+                let cell_y = y * inner_cell_width + self.margin;
+                let to = (cell_x + self.cell_width(), cell_y + self.cell_width());
+                Self::fill_rect(image, (cell_x, cell_y), to, self.fog_color);
+            }
+        }
+
+        // Fog covers the walls shared with revealed cells as well, so these cells are redrawn to
+        // keep their walls complete
+        for &coords in revealed {
+            if grid.contains(coords) {
+                self.draw_any_cell(coords, grid, image);
+            }
+        }
+    }
+
+    /// Returns the left, top, right and bottom bounds of the wall lines of a cell in thin walls mode
+    const fn thin_cell_bounds(
+        &self,
+        coords: Coords,
+        line_width: usize,
+    ) -> (usize, usize, usize, usize) {
+        let (x_coord, y_coord) = coords;
+        let inner_cell_width = self.cell_width() - self.wall_width;
+        let cell_x = x_coord * inner_cell_width + self.margin;
+        let cell_y = y_coord * inner_cell_width + self.margin;
+
+        // Lines run through the middle of the wall areas and are stretched by half a line on both
+        // ends, so the lines of two adjacent walls always join in the corners
+        let half_wall = self.wall_width / 2;
+        let half_line = line_width / 2;
+        let left = (cell_x + half_wall).saturating_sub(half_line);
+        let top = (cell_y + half_wall).saturating_sub(half_line);
+        let right = left + inner_cell_width + line_width;
+        let bottom = top + inner_cell_width + line_width;
+
+        (left, top, right, bottom)
+    }
+
+    fn draw_thin_cell(&self, coords: Coords, grid: &Grid, image: &mut RgbImage, line_width: usize) {
+        let (left, top, right, bottom) = self.thin_cell_bounds(coords, line_width);
+
+        if !grid.is_carved(coords, Cell::NORTH) {
+            Self::fill_rect(
+                image,
+                (left, top),
+                (right, top + line_width),
+                self.foreground_color,
+            );
+        }
+        if !grid.is_carved(coords, Cell::SOUTH) {
+            Self::fill_rect(
+                image,
+                (left, bottom - line_width),
+                (right, bottom),
+                self.foreground_color,
+            );
+        }
+        if !grid.is_carved(coords, Cell::WEST) {
+            Self::fill_rect(
+                image,
+                (left, top),
+                (left + line_width, bottom),
+                self.foreground_color,
+            );
+        }
+        if !grid.is_carved(coords, Cell::EAST) {
+            Self::fill_rect(
+                image,
+                (right - line_width, top),
+                (right, bottom),
+                self.foreground_color,
+            );
+        }
+    }
+
+    fn fill_rect(image: &mut RgbImage, from: Coords, to: Coords, color: Color) {
+        let to_x = to.0.min(image.width() as usize);
+        let to_y = to.1.min(image.height() as usize);
+
+        for py in from.1..to_y {
+            for px in from.0..to_x {
+                *image.get_pixel_mut(px as u32, py as u32) = match color {
+                    Color::RGB(r, g, b) => image::Rgb([r, g, b]),
+                }
             }
         }
     }
@@ -220,8 +696,18 @@ impl Image {
 
             for py in cell_y..=cell_bottom_y {
                 for px in cell_x..=cell_right_x {
-                    if (is_rightmost_cell && px > cell_right_x - self.wall_width)
-                        || (is_bottommost_cell && py > cell_bottom_y - self.wall_width)
+                    // Openings in the outer wall leave a gap as wide as the passage
+                    let east_opening = grid.is_carved(coords, Cell::EAST)
+                        && py >= cell_y + self.wall_width
+                        && py <= cell_y + inner_cell_width;
+                    let south_opening = grid.is_carved(coords, Cell::SOUTH)
+                        && px >= cell_x + self.wall_width
+                        && px <= cell_x + inner_cell_width;
+
+                    if (is_rightmost_cell && px > cell_right_x - self.wall_width && !east_opening)
+                        || (is_bottommost_cell
+                            && py > cell_bottom_y - self.wall_width
+                            && !south_opening)
                     {
                         *image.get_pixel_mut(px as u32, py as u32) = match self.foreground_color {
                             Color::RGB(r, g, b) => image::Rgb([r, g, b]),
@@ -243,11 +729,30 @@ impl Default for Image {
 impl Formatter<ImageWrapper> for Image {
     /// Converts a given grid into an image and returns an [`ImageWrapper`] over that image
     fn format(&self, grid: &Grid) -> ImageWrapper {
-        let (width, height) = self.sizes(grid);
+        if let Some(scale) = grid.render_scale()
+            && !self.explicit_sizes.all()
+        {
+            return self.scaled(scale).format(grid);
+        }
+
+        let (maze_width, maze_height) = self.sizes(grid);
+        let (legend_width, legend_height) = self.legend_sizes();
+        let width = maze_width.max(legend_width);
+        let height = maze_height + legend_height;
         let mut image: RgbImage = ImageBuffer::new(width as u32, height as u32);
 
         self.fill_background(&mut image);
+        if let Some(texture) = &self.passage_texture {
+            self.fill_texture(&mut image, texture, (maze_width, maze_height));
+        }
         self.draw_maze(&mut image, grid);
+        if let Some((path, color)) = &self.solution {
+            self.draw_solution(&mut image, path, *color);
+        }
+        if let Some(revealed) = &self.revealed {
+            self.draw_fog(&mut image, grid, revealed);
+        }
+        self.draw_legend(&mut image, maze_height);
 
         ImageWrapper(image)
     }
@@ -269,6 +774,12 @@ mod tests {
         assert_eq!(Color::RGB(250, 250, 250), image.background_color);
         assert_eq!(Color::RGB(0, 0, 0), image.foreground_color);
         assert_eq!(50, image.margin);
+        assert_eq!(None, image.thin_wall_width);
+        assert_eq!(None, image.revealed);
+        assert_eq!(Color::RGB(64, 64, 64), image.fog_color);
+        assert!(image.legend.is_empty());
+        assert_eq!(None, image.dead_end_color);
+        assert_eq!(None, image.solution);
     }
 
     #[test]
@@ -308,6 +819,348 @@ mod tests {
         assert_eq!(actual.as_bytes(), expected.as_bytes());
     }
 
+    #[test]
+    fn thin_walls_use_less_ink() {
+        let grid = generate_maze();
+        let count_walls = |image: RgbImage| {
+            image
+                .pixels()
+                .filter(|pixel| **pixel == image::Rgb([0, 0, 0]))
+                .count()
+        };
+
+        let solid = Image::new().wall(10).passage(30).margin(5).format(&grid);
+        let thin = Image::new()
+            .wall(10)
+            .passage(30)
+            .margin(5)
+            .thin_walls(2)
+            .format(&grid);
+
+        let solid_walls = count_walls(solid.into_inner());
+        let thin_walls = count_walls(thin.into_inner());
+
+        assert!(thin_walls > 0);
+        assert!(thin_walls * 3 < solid_walls);
+    }
+
+    #[test]
+    fn unrevealed_cells_render_as_fog() {
+        let grid = generate_maze();
+        let revealed = HashSet::from([(0, 0), (0, 1), (1, 1)]);
+        let image = Image::new()
+            .wall(10)
+            .passage(30)
+            .margin(5)
+            .fog(Color::RGB(10, 20, 30))
+            .reveal(&revealed)
+            .format(&grid)
+            .into_inner();
+
+        let fog = image::Rgb([10, 20, 30]);
+        let background = image::Rgb([250, 250, 250]);
+        // A cell passage starts after the margin and the wall, so its center is at 40 * n + 30
+        let center = |(x, y): Coords| *image.get_pixel(40 * x as u32 + 30, 40 * y as u32 + 30);
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let expected = if revealed.contains(&(x, y)) {
+                    background
+                } else {
+                    fog
+                };
+                assert_eq!(center((x, y)), expected);
+            }
+        }
+        // The walls of revealed cells stay visible even next to fogged cells
+        assert_eq!(*image.get_pixel(5 + 40 + 5, 30), image::Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn dead_ends_are_highlighted() {
+        let grid = generate_maze();
+        let image = Image::new()
+            .wall(10)
+            .passage(30)
+            .margin(5)
+            .highlight_dead_ends(Color::RGB(200, 0, 0))
+            .format(&grid)
+            .into_inner();
+
+        let highlight = image::Rgb([200, 0, 0]);
+        let background = image::Rgb([250, 250, 250]);
+        let dead_ends = [(0, 0), (1, 0), (2, 1), (3, 3)];
+        let center = |(x, y): Coords| *image.get_pixel(40 * x as u32 + 30, 40 * y as u32 + 30);
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let expected = if dead_ends.contains(&(x, y)) {
+                    highlight
+                } else {
+                    background
+                };
+                assert_eq!(center((x, y)), expected);
+            }
+        }
+        // Walls of dead ends keep the foreground color
+        assert_eq!(*image.get_pixel(5 + 40 + 5, 30), image::Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn solution_is_drawn_through_cell_centers() {
+        let grid = generate_maze();
+        let formatter = || Image::new().wall(10).passage(30).margin(5);
+        let red = Color::RGB(200, 0, 0);
+        let image = formatter()
+            .with_solution(vec![(0, 0), (0, 1), (1, 1)], red)
+            .format(&grid)
+            .into_inner();
+
+        let solution = image::Rgb([200, 0, 0]);
+        assert_eq!(*image.get_pixel(30, 30), solution);
+        assert_eq!(*image.get_pixel(30, 70), solution);
+        assert_eq!(*image.get_pixel(70, 70), solution);
+        // The line crosses the open passage between the cells
+        assert_eq!(*image.get_pixel(30, 50), solution);
+        assert_eq!(*image.get_pixel(50, 70), solution);
+        // The line is a third of the passage thick
+        assert_eq!(*image.get_pixel(30 + 6, 50), image::Rgb([250, 250, 250]));
+        // Unrelated walls keep the foreground color
+        assert_eq!(*image.get_pixel(50, 30), image::Rgb([0, 0, 0]));
+
+        let empty = formatter()
+            .with_solution(Vec::new(), red)
+            .format(&grid)
+            .into_inner();
+        assert_eq!(empty, formatter().format(&grid).into_inner());
+    }
+
+    #[test]
+    fn solution_skips_gaps() {
+        let grid = generate_maze();
+        let image = Image::new()
+            .wall(10)
+            .passage(30)
+            .margin(5)
+            .with_solution(vec![(0, 0), (2, 0)], Color::RGB(200, 0, 0))
+            .format(&grid)
+            .into_inner();
+
+        let solution = image::Rgb([200, 0, 0]);
+        assert_eq!(*image.get_pixel(30, 30), solution);
+        assert_eq!(*image.get_pixel(110, 30), solution);
+        assert_eq!(*image.get_pixel(70, 30), image::Rgb([250, 250, 250]));
+    }
+
+    #[test]
+    fn overpasses_leave_a_gap_in_the_lower_corridor() {
+        let mut grid = Grid::new(3, 3);
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_passage((0, 1), Cell::EAST).unwrap();
+        grid.carve_passage((1, 1), Cell::EAST).unwrap();
+        let formatter = || Image::new().wall(10).passage(30).margin(5);
+        let crossroads = formatter().format(&grid).into_inner();
+
+        grid.set_overpass((1, 1), Overpass::NorthSouth);
+        let image = formatter().format(&grid).into_inner();
+
+        let foreground = image::Rgb([0, 0, 0]);
+        let background = image::Rgb([250, 250, 250]);
+        // The west and east sides of the crossing are open without an overpass
+        assert_eq!(*crossroads.get_pixel(50, 70), background);
+        assert_eq!(*crossroads.get_pixel(90, 70), background);
+        // The north-south corridor on top runs uninterrupted, while the east-west one doesn't
+        assert_eq!(*image.get_pixel(50, 70), foreground);
+        assert_eq!(*image.get_pixel(90, 70), foreground);
+        assert_eq!(*image.get_pixel(70, 50), background);
+        assert_eq!(*image.get_pixel(70, 90), background);
+
+        let thin = formatter().thin_walls(2).format(&grid).into_inner();
+        assert_eq!(*thin.get_pixel(50, 70), foreground);
+        assert_eq!(*thin.get_pixel(70, 50), background);
+    }
+
+    #[test]
+    fn openings_leave_a_gap_in_the_outer_wall() {
+        let mut grid = Grid::new(3, 3);
+        let formatter = || Image::new().wall(10).passage(30).margin(5);
+        let closed = formatter().format(&grid).into_inner();
+
+        assert!(grid.open_border((1, 0), Cell::NORTH));
+        assert!(grid.open_border((0, 1), Cell::WEST));
+        assert!(grid.open_border((2, 2), Cell::EAST));
+        assert!(grid.open_border((1, 2), Cell::SOUTH));
+        let image = formatter().format(&grid).into_inner();
+
+        let foreground = image::Rgb([0, 0, 0]);
+        let background = image::Rgb([250, 250, 250]);
+        for (x, y) in [(70, 10), (10, 70), (130, 110), (70, 130)] {
+            assert_eq!(*closed.get_pixel(x, y), foreground);
+            assert_eq!(*image.get_pixel(x, y), background);
+        }
+        // Corners next to an opening stay in place
+        assert_eq!(*image.get_pixel(130, 130), foreground);
+        assert_eq!(*image.get_pixel(130, 90), foreground);
+    }
+
+    #[test]
+    fn single_pixel_texture_is_a_solid_fill() {
+        let grid = generate_maze();
+        let formatter = || Image::new().wall(10).passage(30).margin(5);
+        let solid = formatter()
+            .background(Color::RGB(200, 10, 10))
+            .format(&grid)
+            .into_inner();
+        let texture = RgbImage::from_pixel(1, 1, image::Rgb([200, 10, 10]));
+        let textured = formatter()
+            .passage_texture(texture)
+            .format(&grid)
+            .into_inner();
+
+        assert_eq!(textured.dimensions(), solid.dimensions());
+        for (x, y, pixel) in textured.enumerate_pixels() {
+            let in_margin = x < 5 || y < 5 || x >= solid.width() - 5 || y >= solid.height() - 5;
+            if in_margin {
+                assert_eq!(*pixel, image::Rgb([250, 250, 250]));
+            } else {
+                assert_eq!(pixel, solid.get_pixel(x, y), "({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn texture_is_tiled_and_cropped() {
+        let grid = generate_maze();
+        let (red, blue) = (image::Rgb([255, 0, 0]), image::Rgb([0, 0, 255]));
+        let small = RgbImage::from_fn(2, 1, |x, _| if x == 0 { red } else { blue });
+        let image = Image::new()
+            .wall(10)
+            .passage(30)
+            .margin(5)
+            .passage_texture(small)
+            .format(&grid)
+            .into_inner();
+
+        // Passage pixels alternate with every column, while walls keep the foreground color
+        assert_eq!(*image.get_pixel(5 + 20, 30), red);
+        assert_eq!(*image.get_pixel(5 + 21, 30), blue);
+        assert_eq!(*image.get_pixel(7, 30), image::Rgb([0, 0, 0]));
+
+        let large = RgbImage::from_fn(400, 400, |x, y| image::Rgb([x as u8, y as u8, 0]));
+        let image = Image::new()
+            .wall(10)
+            .passage(30)
+            .margin(5)
+            .passage_texture(large)
+            .format(&grid)
+            .into_inner();
+        assert_eq!(*image.get_pixel(5 + 20, 5 + 25), image::Rgb([20, 25, 0]));
+        assert_eq!(*image.get_pixel(5 + 60, 5 + 25), image::Rgb([60, 25, 0]));
+    }
+
+    #[test]
+    fn masked_cells_render_solid() {
+        let mut grid = generate_maze();
+        let mut mask = crate::maze::Mask::new(4, 4);
+        mask.set_active((3, 3), false);
+        grid.apply_mask(mask);
+
+        let image = Image::new()
+            .wall(10)
+            .passage(30)
+            .margin(5)
+            .format(&grid)
+            .into_inner();
+        let center = |(x, y): Coords| *image.get_pixel(40 * x as u32 + 30, 40 * y as u32 + 30);
+
+        assert_eq!(center((3, 3)), image::Rgb([0, 0, 0]));
+        assert_eq!(center((2, 3)), image::Rgb([250, 250, 250]));
+    }
+
+    #[test]
+    fn legend_extends_image() {
+        let grid = generate_maze();
+        let formatter = || Image::new().wall(10).passage(30).margin(5);
+        let plain = formatter().format(&grid).into_inner();
+        let with_legend = formatter()
+            .with_legend(vec![
+                (Color::RGB(0, 200, 0), "Start".to_string()),
+                (Color::RGB(200, 0, 0), "Goal".to_string()),
+            ])
+            .format(&grid)
+            .into_inner();
+
+        assert!(with_legend.height() > plain.height());
+        assert_eq!(with_legend.width(), plain.width());
+
+        // Legend entries are drawn only below the maze
+        let (_, maze_height) = formatter().sizes(&grid);
+        for color in [image::Rgb([0, 200, 0]), image::Rgb([200, 0, 0])] {
+            assert!(plain.pixels().all(|pixel| *pixel != color));
+            assert!(
+                with_legend
+                    .enumerate_pixels()
+                    .any(|(_, y, pixel)| *pixel == color && y as usize >= maze_height)
+            );
+        }
+    }
+
+    #[test]
+    fn tiled_pages_cover_maze() {
+        let mut maze = OrthogonalMaze::new(5, 3);
+        for y in 0..3 {
+            for x in 0..4 {
+                maze.get_grid_mut()
+                    .carve_passage((x, y), Cell::EAST)
+                    .unwrap();
+            }
+        }
+        let formatter = Image::new().wall(2).passage(4).margin(3);
+        let pages = formatter.tiled_pages(&maze, 2, 2);
+
+        let indexes: Vec<(usize, usize)> = pages.iter().map(|(c, r, _)| (*c, *r)).collect();
+        assert_eq!(
+            indexes,
+            vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+
+        // Cells are 6 pixels apart, while pages overlap by the 3 pixels margin. The last column
+        // and row are narrower, since they contain fewer cells
+        let sizes: Vec<(u32, u32)> = pages.iter().map(|(_, _, page)| page.dimensions()).collect();
+        assert_eq!(
+            sizes,
+            vec![(20, 20), (20, 20), (14, 20), (20, 14), (20, 14), (14, 14)]
+        );
+
+        // Pages are 2 cells, i.e. 12 pixels, apart and the area of a page starts after the margin,
+        // so every pixel of the maze is drawn unchanged in the area of some page
+        let full = formatter.format(maze.grid()).into_inner();
+        let (maze_width, maze_height) = (3 + 5 * 6 + 2, 3 + 3 * 6 + 2);
+        for y in 3..maze_height {
+            for x in 3..maze_width {
+                let drawn = pages.iter().find_map(|(column, row, page)| {
+                    let (left, top) = (3 + column * 12, 3 + row * 12);
+                    let (right, bottom) =
+                        ((left + 14).min(maze_width), (top + 14).min(maze_height));
+                    ((left..right).contains(&x) && (top..bottom).contains(&y))
+                        .then(|| page.get_pixel((x - column * 12) as u32, (y - row * 12) as u32))
+                });
+                assert_eq!(
+                    drawn,
+                    Some(full.get_pixel(x as u32, y as u32)),
+                    "({x}, {y})"
+                );
+            }
+        }
+
+        // Crop marks extend the corners of the page area into the margin
+        let (_, _, page) = &pages[0];
+        assert_eq!(*full.get_pixel(3, 0), image::Rgb([250, 250, 250]));
+        assert_eq!(*page.get_pixel(3, 0), image::Rgb([0, 0, 0]));
+        assert_eq!(*page.get_pixel(0, 3), image::Rgb([0, 0, 0]));
+    }
+
     fn generate_maze() -> Grid {
         let mut grid = Grid::new(4, 4);
 
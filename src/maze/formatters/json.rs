@@ -0,0 +1,187 @@
+use crate::maze::errors::MazeLoadError;
+use crate::maze::formatters::Formatter;
+use crate::maze::grid::{Grid, cell::Cell};
+use serde::{Deserialize, Serialize};
+
+use super::{StringWrapper, grid_from_cells};
+
+/// A formatter to emit the maze as JSON
+///
+/// The schema is stable and independent of the internal grid representation, so it can be
+/// consumed by JavaScript frontends and other tooling. It can be loaded back with
+/// [`OrthogonalMaze::from_json`](crate::maze::OrthogonalMaze::from_json).
+///
+/// # Example:
+///
+/// ```no_test
+/// {
+///   "width": 2,
+///   "height": 1,
+///   "cells": [
+///     { "x": 0, "y": 0, "walls": { "north": true, "south": true, "east": false, "west": true } },
+///     { "x": 1, "y": 0, "walls": { "north": true, "south": true, "east": true, "west": false } }
+///   ]
+/// }
+/// ```
+pub struct Json;
+
+/// The schema of a maze emitted by the [`Json`] formatter
+#[derive(Serialize, Deserialize)]
+pub struct JsonMaze {
+    width: usize,
+    height: usize,
+    cells: Vec<JsonCell>,
+}
+
+impl JsonMaze {
+    /// Returns the schema of a given grid
+    pub fn from_grid(grid: &Grid) -> Self {
+        let cells = (0..grid.height())
+            .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+            .map(|(x, y)| JsonCell {
+                x,
+                y,
+                walls: Walls::from_cell(grid[(x, y)]),
+            })
+            .collect();
+
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            cells,
+        }
+    }
+
+    /// Returns a grid built from the cells of the schema
+    ///
+    /// # Errors
+    /// Returns a [`MazeLoadError`] for the same reasons as [`parse_grid`]
+    pub fn into_grid(self) -> Result<Grid, MazeLoadError> {
+        let cells = self
+            .cells
+            .iter()
+            .map(|cell| ((cell.x, cell.y), cell.walls.to_cell()));
+
+        grid_from_cells(self.width, self.height, cells)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonCell {
+    x: usize,
+    y: usize,
+    walls: Walls,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
+struct Walls {
+    north: bool,
+    south: bool,
+    east: bool,
+    west: bool,
+}
+
+impl Walls {
+    const fn from_cell(cell: Cell) -> Self {
+        Self {
+            north: !cell.contains(Cell::NORTH),
+            south: !cell.contains(Cell::SOUTH),
+            east: !cell.contains(Cell::EAST),
+            west: !cell.contains(Cell::WEST),
+        }
+    }
+
+    fn to_cell(&self) -> Cell {
+        let mut cell = Cell::empty();
+        cell.set(Cell::NORTH, !self.north);
+        cell.set(Cell::SOUTH, !self.south);
+        cell.set(Cell::EAST, !self.east);
+        cell.set(Cell::WEST, !self.west);
+        cell
+    }
+}
+
+/// An implementation of a JSON formatter
+impl Formatter<StringWrapper> for Json {
+    /// Converts a given grid into JSON and returns an [`StringWrapper`] over that string
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        StringWrapper(serde_json::to_string_pretty(&JsonMaze::from_grid(grid)).unwrap())
+    }
+}
+
+/// Parses a grid from the JSON emitted by the [`Json`] formatter
+///
+/// Every cell coordinate must be present exactly once, and the walls of neighboring cells must
+/// agree with each other.
+pub fn parse_grid(json: &str) -> Result<Grid, MazeLoadError> {
+    let maze: JsonMaze = serde_json::from_str(json)
+        .map_err(|err| MazeLoadError::reason(format!("Invalid maze JSON: {err}")))?;
+    maze.into_grid()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_and_parse() {
+        let mut grid = Grid::new(2, 2);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::WEST).unwrap();
+
+        let json = Json.format(&grid).into_inner();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["width"], 2);
+        assert_eq!(value["cells"].as_array().unwrap().len(), 4);
+        assert_eq!(value["cells"][1]["x"], 1);
+        assert_eq!(value["cells"][1]["walls"]["west"], false);
+        assert_eq!(value["cells"][1]["walls"]["east"], true);
+
+        assert_eq!(parse_grid(&json).unwrap(), grid);
+    }
+
+    #[test]
+    fn parse_invalid_cells() {
+        let walls = r#"{ "north": true, "south": true, "east": true, "west": true }"#;
+        let maze = |cells: &[(usize, usize)]| {
+            let cells: Vec<String> = cells
+                .iter()
+                .map(|(x, y)| format!(r#"{{ "x": {x}, "y": {y}, "walls": {walls} }}"#))
+                .collect();
+            format!(
+                r#"{{ "width": 2, "height": 1, "cells": [{}] }}"#,
+                cells.join(",")
+            )
+        };
+
+        assert!(parse_grid(&maze(&[(0, 0), (1, 0)])).is_ok());
+        assert_eq!(
+            parse_grid(&maze(&[(0, 0)])).unwrap_err().reason,
+            "A 2x1 maze must contain 2 cells, but contains 1"
+        );
+        assert_eq!(
+            parse_grid(&maze(&[(0, 0), (0, 0)])).unwrap_err().reason,
+            "Cell (0, 0) is present more than once"
+        );
+        assert_eq!(
+            parse_grid(&maze(&[(0, 0), (2, 0)])).unwrap_err().reason,
+            "Cell (2, 0) is out of the 2x1 maze bounds"
+        );
+        assert!(parse_grid("not json").is_err());
+    }
+
+    #[test]
+    fn parse_mismatched_walls() {
+        let json = r#"{ "width": 2, "height": 1, "cells": [
+            { "x": 0, "y": 0, "walls": { "north": true, "south": true, "east": false, "west": true } },
+            { "x": 1, "y": 0, "walls": { "north": true, "south": true, "east": true, "west": true } }
+        ] }"#;
+
+        assert_eq!(
+            parse_grid(json).unwrap_err().reason,
+            "Walls of cell (0, 0) don't match its neighbors"
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::maze::{
+    formatters::{Formatter, StringWrapper},
+    grid::{Grid, cell::Cell},
+};
+use std::fmt::Write;
+
+/// A formatter to emit the maze as a `.lvl` style text level for retro game importers
+///
+/// The level is a tile map where every cell and every wall between two cells takes a single tile.
+/// It is preceded by a header with the level width and height in tiles, wrapped with a fixed
+/// border and followed by a legend of the symbols in use. The start is placed in the top-left cell
+/// and the goal in the bottom-right one.
+///
+/// # Example:
+///
+/// ```no_test
+/// LEVEL 9x5
+/// +---------+
+/// |#########|
+/// |#S....#.#|
+/// |#.###.#.#|
+/// |#...#..G#|
+/// |#########|
+/// +---------+
+/// LEGEND
+/// # wall
+/// . floor
+/// S start
+/// G goal
+/// ```
+pub struct LevelText {
+    wall: char,
+    floor: char,
+    start: char,
+    goal: char,
+}
+
+impl LevelText {
+    /// Returns a new instance of a [`LevelText`] formatter with the default symbols
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            wall: '#',
+            floor: '.',
+            start: 'S',
+            goal: 'G',
+        }
+    }
+
+    /// Sets a wall symbol and returns itself
+    #[must_use]
+    pub const fn wall(mut self, wall: char) -> Self {
+        self.wall = wall;
+        self
+    }
+
+    /// Sets a floor symbol and returns itself
+    #[must_use]
+    pub const fn floor(mut self, floor: char) -> Self {
+        self.floor = floor;
+        self
+    }
+
+    /// Sets a start symbol and returns itself
+    #[must_use]
+    pub const fn start(mut self, start: char) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Sets a goal symbol and returns itself
+    #[must_use]
+    pub const fn goal(mut self, goal: char) -> Self {
+        self.goal = goal;
+        self
+    }
+
+    fn tiles(&self, grid: &Grid) -> Vec<Vec<char>> {
+        let cols = grid.width() * 2 + 1;
+        let rows = grid.height() * 2 + 1;
+        let mut tiles = vec![vec![self.wall; cols]; rows];
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                if !grid.is_active((x, y)) {
+                    continue;
+                }
+
+                tiles[y * 2 + 1][x * 2 + 1] = self.floor;
+                if grid.is_carved((x, y), Cell::EAST) {
+                    tiles[y * 2 + 1][x * 2 + 2] = self.floor;
+                }
+                if grid.is_carved((x, y), Cell::SOUTH) {
+                    tiles[y * 2 + 2][x * 2 + 1] = self.floor;
+                }
+                // Inner passages to the North and West are drawn by the neighbors as well, but the
+                // openings in the outer wall aren't
+                if grid.is_carved((x, y), Cell::NORTH) {
+                    tiles[y * 2][x * 2 + 1] = self.floor;
+                }
+                if grid.is_carved((x, y), Cell::WEST) {
+                    tiles[y * 2 + 1][x * 2] = self.floor;
+                }
+            }
+        }
+
+        if grid.width() > 0 && grid.height() > 0 {
+            tiles[1][1] = self.start;
+            tiles[rows - 2][cols - 2] = self.goal;
+        }
+
+        tiles
+    }
+}
+
+impl Default for LevelText {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter<StringWrapper> for LevelText {
+    /// Converts a given grid into a framed tile map and returns a [`StringWrapper`] over it
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        let tiles = self.tiles(grid);
+        let cols = tiles[0].len();
+        let border = format!("+{}+", "-".repeat(cols));
+
+        let mut output = String::new();
+        let _ = writeln!(output, "LEVEL {cols}x{}", tiles.len());
+        let _ = writeln!(output, "{border}");
+        for row in &tiles {
+            let _ = writeln!(output, "|{}|", row.iter().collect::<String>());
+        }
+        let _ = writeln!(output, "{border}");
+        output.push_str("LEGEND\n");
+        let _ = writeln!(output, "{} wall", self.wall);
+        let _ = writeln!(output, "{} floor", self.floor);
+        let _ = writeln!(output, "{} start", self.start);
+        let _ = writeln!(output, "{} goal", self.goal);
+
+        StringWrapper(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_call() {
+        let formatter = LevelText::new();
+        assert_eq!('#', formatter.wall);
+        assert_eq!('.', formatter.floor);
+        assert_eq!('S', formatter.start);
+        assert_eq!('G', formatter.goal);
+    }
+
+    #[test]
+    fn format_with_custom_symbols() {
+        let mut grid = Grid::new(3, 2);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::EAST).unwrap();
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((2, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::EAST).unwrap();
+
+        let formatter = LevelText::new().wall('X').floor(' ').start('@').goal('>');
+        let actual = formatter.format(&grid).0;
+
+        let expected = "LEVEL 7x5
++-------+
+|XXXXXXX|
+|X@    X|
+|X XXX X|
+|X X  >X|
+|XXXXXXX|
++-------+
+LEGEND
+X wall
+  floor
+@ start
+> goal
+";
+        assert_eq!(actual, expected);
+        assert_eq!(actual.lines().next(), Some("LEVEL 7x5"));
+    }
+}
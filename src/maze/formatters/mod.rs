@@ -1,19 +1,92 @@
 //! Formatters for converting a generated maze into other data types
 
+mod ansi;
 mod ascii;
+mod csv;
+mod dot;
+mod font;
 mod game_map;
+mod hex_image;
 mod hex_text;
 mod image;
+#[cfg(feature = "serde")]
+mod json;
+mod level_text;
+mod svg;
+mod theta_svg;
+mod tmx;
+mod unicode_box;
 
-use crate::maze::grid::Grid;
+use crate::maze::grid::{Grid, cell::Cell, topology::Topology};
+use crate::utils::types::Coords;
 use ::image::RgbImage;
-use std::{fs::File, io::Write};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
 
 pub use self::image::Image;
-use super::errors::MazeSaveError;
-pub use ascii::{AsciiBroad, AsciiNarrow};
+use super::errors::{MazeLoadError, MazeSaveError};
+pub use ansi::AnsiColor;
+pub use ascii::{AsciiBroad, AsciiNarrow, StyledAsciiBroad};
+pub use csv::Csv;
+pub use dot::Dot;
 pub use game_map::GameMap;
+pub use hex_image::HexImage;
 pub use hex_text::HexText;
+#[cfg(feature = "serde")]
+pub use json::Json;
+#[cfg(feature = "serde")]
+pub(crate) use json::{JsonMaze, parse_grid};
+pub use level_text::LevelText;
+pub use svg::Svg;
+pub use theta_svg::ThetaSvg;
+pub use tmx::Tmx;
+pub use unicode_box::UnicodeBox;
+
+/// Default sizes of the maze elements shared by the [`Image`], [`Svg`] and [`GameMap`] formatters
+///
+/// Once attached to a maze with [`OrthogonalMaze::with_render_scale`](crate::maze::OrthogonalMaze::with_render_scale),
+/// it's used by these formatters instead of their own defaults, unless a size is set on a
+/// formatter explicitly. All the sizes are in pixels, except for [`GameMap`], where a passage is
+/// measured in characters and the other sizes are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenderScale {
+    /// Wall width
+    pub wall: usize,
+    /// Passage width
+    pub passage: usize,
+    /// Size of the empty space between the maze and the image borders
+    pub margin: usize,
+}
+
+/// Marks the sizes set on a formatter explicitly, so they take precedence over a [`RenderScale`]
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::struct_excessive_bools)]
+struct ExplicitSizes {
+    wall: bool,
+    passage: bool,
+    margin: bool,
+}
+
+impl ExplicitSizes {
+    const NONE: Self = Self {
+        wall: false,
+        passage: false,
+        margin: false,
+    };
+    const ALL: Self = Self {
+        wall: true,
+        passage: true,
+        margin: true,
+    };
+
+    const fn all(self) -> bool {
+        self.wall && self.passage && self.margin
+    }
+}
 
 /// A trait for maze formatters
 pub trait Formatter<T>
@@ -36,7 +109,279 @@ pub trait Saveable {
     fn save(&self, path: &str) -> Result<String, MazeSaveError>;
 }
 
+/// A trait for formatters that can write a maze row by row, without keeping the whole maze in
+/// memory
+///
+/// Together with a row generator such as [`Eller::rows`](crate::maze::Eller::rows), it allows
+/// saving mazes of any height with constant memory.
+pub trait StreamFormatter {
+    /// Writes the top border of a maze with a given width
+    ///
+    /// # Errors
+    /// Returns an [`io::Error`] if the writer fails
+    fn write_header(&self, width: usize, writer: &mut dyn Write) -> io::Result<()>;
+
+    /// Writes the top border of a maze above its first row
+    ///
+    /// Defaults to [`Self::write_header`] for the width of the row. Formatters override it to
+    /// leave gaps for the openings in the northern wall.
+    ///
+    /// # Errors
+    /// Returns an [`io::Error`] if the writer fails
+    fn write_top(&self, row: &[Cell], writer: &mut dyn Write) -> io::Result<()> {
+        self.write_header(row.len(), writer)
+    }
+
+    /// Writes a single row of cells ordered from west to east
+    ///
+    /// # Errors
+    /// Returns an [`io::Error`] if the writer fails
+    fn write_row(&self, row: &[Cell], writer: &mut dyn Write) -> io::Result<()>;
+
+    /// Writes the rows of a maze with a given width into a file one by one
+    ///
+    /// In case of success, returns the string with a success message.
+    /// Otherwise, returns a [`MazeSaveError`] with a custom reason message.
+    ///
+    /// # Errors
+    /// Returns a [`MazeSaveError`] if the file could not be written
+    fn save_rows<I>(&self, path: &str, width: usize, rows: I) -> Result<String, MazeSaveError>
+    where
+        I: IntoIterator<Item = Vec<Cell>>,
+        Self: Sized,
+    {
+        let (file, path) = create_file(path)?;
+        let mut writer = BufWriter::new(file);
+
+        let mut rows = rows.into_iter().peekable();
+        let header = match rows.peek() {
+            Some(first) => self.write_top(first, &mut writer),
+            None => self.write_header(width, &mut writer),
+        };
+        let written =
+            header.and_then(|()| rows.try_for_each(|row| self.write_row(&row, &mut writer)));
+
+        match written.and_then(|()| writer.flush()) {
+            Err(why) => Err(MazeSaveError {
+                reason: format!("Couldn't write to {}: {}", path.display(), why),
+            }),
+            Ok(()) => Ok(format!(
+                "Maze was successfully written to a file: {}",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// Formats a grid with a given [`StreamFormatter`] row by row
+fn stream_grid(formatter: &impl StreamFormatter, grid: &Grid) -> String {
+    let mut output = Vec::new();
+    if grid.height() == 0 {
+        formatter.write_header(grid.width(), &mut output).unwrap();
+    }
+
+    for y in 0..grid.height() {
+        let row: Vec<Cell> = (0..grid.width()).map(|x| grid[(x, y)]).collect();
+        if y == 0 {
+            formatter.write_top(&row, &mut output).unwrap();
+        }
+        formatter.write_row(&row, &mut output).unwrap();
+    }
+
+    String::from_utf8(output).unwrap()
+}
+
+/// A single block of a maze expanded into a grid of cells, walls and wall corners
+pub(crate) enum Block {
+    Wall,
+    /// A cell or an open passage between two cells, which takes the color of the first one
+    Passage(Coords, Option<Coords>),
+}
+
+/// Returns the block at a given position, where cells are at odd positions and walls or passages
+/// between them are in between
+pub(crate) fn block_at(grid: &Grid, block_x: usize, block_y: usize) -> Block {
+    let (x, y) = (block_x / 2, block_y / 2);
+    let open = |coords: Coords, direction: Cell| {
+        grid.is_active(coords) && grid[coords].contains(direction)
+    };
+
+    match (block_x % 2, block_y % 2) {
+        (1, 1) if grid.is_active((x, y)) => Block::Passage((x, y), None),
+        // A wall between a cell above and a cell below
+        (1, 0) => {
+            let above = y.checked_sub(1).map(|above| (x, above));
+            if above.is_some_and(|above| open(above, Cell::SOUTH)) {
+                Block::Passage(above.unwrap(), Some((x, y)).filter(|c| grid.contains(*c)))
+            } else if open((x, y), Cell::NORTH) {
+                Block::Passage((x, y), above)
+            } else {
+                Block::Wall
+            }
+        }
+        // A wall between a cell on the left and a cell on the right
+        (0, 1) => {
+            let left = x.checked_sub(1).map(|left| (left, y));
+            if left.is_some_and(|left| open(left, Cell::EAST)) {
+                Block::Passage(left.unwrap(), Some((x, y)).filter(|c| grid.contains(*c)))
+            } else if open((x, y), Cell::WEST) {
+                Block::Passage((x, y), left)
+            } else {
+                Block::Wall
+            }
+        }
+        _ => Block::Wall,
+    }
+}
+
+/// Creates a file at a given path relative to the current dir
+fn create_file(path: &str) -> Result<(File, PathBuf), MazeSaveError> {
+    let path = match std::env::current_dir() {
+        Err(why) => {
+            return Err(MazeSaveError {
+                reason: format!("Couldn't find path to current dir: {why}"),
+            });
+        }
+        Ok(dir) => dir.join(path),
+    };
+
+    match File::create(&path) {
+        Err(why) => Err(MazeSaveError {
+            reason: format!("Couldn't create {}: {}", path.display(), why),
+        }),
+        Ok(file) => Ok((file, path)),
+    }
+}
+
+/// The largest number of cells of a maze loaded from a file
+pub(crate) const MAX_LOADED_CELLS: usize = 1 << 24;
+
+/// Returns the number of cells of a parsed maze of a given size, or an error if the size
+/// overflows or exceeds [`MAX_LOADED_CELLS`]
+pub(crate) fn loaded_cell_count(width: usize, height: usize) -> Result<usize, MazeLoadError> {
+    width
+        .checked_mul(height)
+        .filter(|size| *size <= MAX_LOADED_CELLS)
+        .ok_or_else(|| {
+            MazeLoadError::reason(format!(
+                "A {width}x{height} maze exceeds the limit of {MAX_LOADED_CELLS} cells"
+            ))
+        })
+}
+
+/// Builds a grid of a given size from the cells of a parsed maze
+///
+/// Every cell coordinate must be present exactly once, and the walls of neighboring cells must
+/// agree with each other. The maze can't have more than [`MAX_LOADED_CELLS`] cells.
+pub(crate) fn grid_from_cells(
+    width: usize,
+    height: usize,
+    parsed: impl IntoIterator<Item = (Coords, Cell)>,
+) -> Result<Grid, MazeLoadError> {
+    let size = loaded_cell_count(width, height)?;
+    let parsed: Vec<_> = parsed.into_iter().collect();
+    if parsed.len() != size {
+        return Err(MazeLoadError::reason(format!(
+            "A {width}x{height} maze must contain {size} cells, but contains {}",
+            parsed.len()
+        )));
+    }
+
+    let mut cells = vec![None; size];
+    for ((x, y), cell) in parsed {
+        if x >= width || y >= height {
+            return Err(MazeLoadError::reason(format!(
+                "Cell ({x}, {y}) is out of the {width}x{height} maze bounds"
+            )));
+        }
+
+        let slot = &mut cells[y * width + x];
+        if slot.is_some() {
+            return Err(MazeLoadError::reason(format!(
+                "Cell ({x}, {y}) is present more than once"
+            )));
+        }
+        *slot = Some(cell);
+    }
+
+    if let Some(idx) = cells.iter().position(Option::is_none) {
+        return Err(MazeLoadError::reason(format!(
+            "Cell ({}, {}) is missing",
+            idx % width,
+            idx / width
+        )));
+    }
+
+    let cells: Vec<Cell> = cells.into_iter().flatten().collect();
+    grid_from_row_major(width, height, &cells)
+}
+
+/// Builds a grid of a given size from all of its cells in row-major order
+///
+/// The walls of neighboring cells must agree with each other. Unlike [`grid_from_cells`], the
+/// size isn't limited, since the cells are already in memory.
+pub(crate) fn grid_from_row_major(
+    width: usize,
+    height: usize,
+    cells: &[Cell],
+) -> Result<Grid, MazeLoadError> {
+    if width.checked_mul(height) != Some(cells.len()) {
+        return Err(MazeLoadError::reason(format!(
+            "A {width}x{height} maze must contain {width}x{height} cells, but contains {}",
+            cells.len()
+        )));
+    }
+
+    let mut grid = Grid::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            let cell = cells[y * width + x];
+            for direction in Topology::Orthogonal.directions() {
+                let neighbor = Topology::Orthogonal
+                    .next_coords((x, y), *direction, width, height)
+                    .map(|(nx, ny)| cells[ny * width + nx]);
+                let opposite = Topology::Orthogonal.opposite(*direction).unwrap();
+                // Passages out of the maze are openings in its outer wall
+                let agrees = neighbor.is_none_or(|neighbor| {
+                    cell.contains(*direction) == neighbor.contains(opposite)
+                });
+
+                if !agrees {
+                    return Err(MazeLoadError::reason(format!(
+                        "Walls of cell ({x}, {y}) don't match its neighbors"
+                    )));
+                }
+            }
+
+            for direction in [Cell::EAST, Cell::SOUTH] {
+                if cell.contains(direction) && !grid.open_border((x, y), direction) {
+                    grid.carve_passage((x, y), direction).unwrap();
+                }
+            }
+            for direction in [Cell::NORTH, Cell::WEST] {
+                if cell.contains(direction) {
+                    grid.open_border((x, y), direction);
+                }
+            }
+        }
+    }
+
+    Ok(grid)
+}
+
 /// A custom wrapper over [`RgbImage`] for converting a maze to an image
+///
+/// Besides saving it to a file, the image can be taken out with [`ImageWrapper::into_inner`] to
+/// process it in memory, e.g. to upload it as a texture.
+///
+/// # Example
+/// ```rust
+/// use bevy_knossos::maze::*;
+///
+/// let maze = OrthogonalMaze::new(2, 2);
+/// let image = maze.format(Image::new().margin(10)).into_inner();
+/// assert_eq!(image.dimensions(), (10 * 2 + 200, 10 * 2 + 200));
+/// ```
 pub struct ImageWrapper(RgbImage);
 
 impl ImageWrapper {
@@ -77,23 +422,7 @@ impl StringWrapper {
 impl Saveable for StringWrapper {
     /// Saves a maze string to a file to a given path
     fn save(&self, path: &str) -> Result<String, MazeSaveError> {
-        let path = match std::env::current_dir() {
-            Err(why) => {
-                return Err(MazeSaveError {
-                    reason: format!("Couldn't find path to current dir: {why}"),
-                });
-            }
-            Ok(dir) => dir.join(path),
-        };
-
-        let mut file = match File::create(&path) {
-            Err(why) => {
-                return Err(MazeSaveError {
-                    reason: format!("Couldn't create {}: {}", path.display(), why),
-                });
-            }
-            Ok(file) => file,
-        };
+        let (mut file, path) = create_file(path)?;
 
         match file.write_all(self.0.as_bytes()) {
             Err(why) => Err(MazeSaveError {
@@ -112,6 +441,30 @@ mod tests {
     use super::*;
     use ::image::Rgb;
 
+    #[test]
+    fn grid_from_cells_rejects_oversize_mazes() {
+        let overflowing = grid_from_cells(usize::MAX, 2, []);
+        let oversize = grid_from_cells(MAX_LOADED_CELLS + 1, 1, []);
+
+        assert!(
+            overflowing
+                .unwrap_err()
+                .reason
+                .contains("exceeds the limit")
+        );
+        assert!(oversize.unwrap_err().reason.contains("exceeds the limit"));
+    }
+
+    #[test]
+    fn grid_from_cells_requires_every_cell() {
+        let cells = [((0, 0), Cell::EAST), ((1, 0), Cell::WEST)];
+
+        let result = grid_from_cells(2, 2, cells);
+
+        assert!(result.unwrap_err().reason.contains("must contain 4 cells"));
+        assert!(grid_from_cells(2, 1, cells).is_ok());
+    }
+
     #[test]
     fn into_inner_returns_inner_string() {
         let wrapper = StringWrapper(String::from("Hello, Rust!"));
@@ -0,0 +1,323 @@
+use crate::maze::grid::cell::Cell;
+use crate::maze::{formatters::Formatter, grid::Grid};
+use crate::utils::color::Color;
+use crate::utils::types::Coords;
+use std::fmt::Write;
+
+use super::{ExplicitSizes, RenderScale, StringWrapper};
+
+/// An SVG formatter for a generated maze
+///
+/// Walls are emitted as `<line>` elements grouped into a `<g id="walls">` layer. When a solution
+/// path is provided via [`Svg::with_solution_layer`], it is emitted into a separate
+/// `<g id="solution">` layer, so it can be shown or hidden independently, e.g. from JavaScript.
+#[derive(Clone)]
+pub struct Svg {
+    cell_size: f32,
+    wall_width: f32,
+    margin: f32,
+    stroke_color: Color,
+    background_color: Color,
+    solution: Option<Vec<Coords>>,
+    solution_color: Color,
+    explicit_sizes: ExplicitSizes,
+}
+
+impl Svg {
+    /// Returns a new instance of an [Svg] formatter with a default settings
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            cell_size: 20.0,
+            wall_width: 2.0,
+            margin: 10.0,
+            stroke_color: Color::RGB(0, 0, 0),
+            background_color: Color::RGB(250, 250, 250),
+            solution: None,
+            solution_color: Color::RGB(255, 0, 0),
+            explicit_sizes: ExplicitSizes::NONE,
+        }
+    }
+
+    /// Sets a wall width and returns itself
+    #[must_use]
+    pub const fn wall_width(mut self, width: f32) -> Self {
+        self.wall_width = width;
+        self.explicit_sizes.wall = true;
+        self
+    }
+
+    /// Sets a cell size and returns itself
+    #[must_use]
+    pub const fn cell_size(mut self, size: f32) -> Self {
+        self.cell_size = size;
+        self.explicit_sizes.passage = true;
+        self
+    }
+
+    /// Sets a margin (a distance between a maze and the document borders) and returns itself
+    #[must_use]
+    pub const fn margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self.explicit_sizes.margin = true;
+        self
+    }
+
+    /// Sets a wall color and returns itself
+    #[must_use]
+    pub const fn stroke_color(mut self, color: Color) -> Self {
+        self.stroke_color = color;
+        self
+    }
+
+    /// Sets a background color and returns itself
+    #[must_use]
+    pub const fn background(mut self, color: Color) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    /// Adds a layer with a solution path drawn through the centers of the given cells and returns
+    /// itself
+    #[must_use]
+    pub fn with_solution_layer(mut self, path: Vec<Coords>) -> Self {
+        self.solution = Some(path);
+        self
+    }
+
+    /// Returns a copy of the formatter with the sizes that weren't set explicitly taken from a
+    /// given scale
+    ///
+    /// Walls are stroked along the cell borders, so half of the outer walls lies in the margin.
+    /// The margin is extended by that half to render the maze at the same size as the [`Image`]
+    /// formatter does.
+    ///
+    /// [`Image`]: super::Image
+    fn scaled(&self, scale: RenderScale) -> Self {
+        let mut svg = self.clone();
+        if !self.explicit_sizes.wall {
+            svg.wall_width = scale.wall as f32;
+        }
+        if !self.explicit_sizes.passage {
+            svg.cell_size = (scale.passage + scale.wall) as f32;
+        }
+        if !self.explicit_sizes.margin {
+            svg.margin = scale.wall as f32 / 2.0 + scale.margin as f32;
+        }
+        svg.explicit_sizes = ExplicitSizes::ALL;
+        svg
+    }
+
+    fn size(&self, cells: usize) -> f32 {
+        (cells as f32).mul_add(self.cell_size, self.margin * 2.0)
+    }
+
+    fn write_line(output: &mut String, from: (f32, f32), to: (f32, f32)) {
+        let _ = writeln!(
+            output,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#,
+            from.0, from.1, to.0, to.1
+        );
+    }
+
+    fn write_walls(&self, output: &mut String, grid: &Grid) {
+        let _ = writeln!(
+            output,
+            r#"<g id="walls" stroke="{}" stroke-width="{}" stroke-linecap="square">"#,
+            self.stroke_color, self.wall_width
+        );
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                let left = (x as f32).mul_add(self.cell_size, self.margin);
+                let top = (y as f32).mul_add(self.cell_size, self.margin);
+                let right = left + self.cell_size;
+                let bottom = top + self.cell_size;
+
+                // Every inner wall is shared by two cells, so only northern and western walls are
+                // drawn for each cell, while the eastern and southern ones close the outer border
+                if !grid.is_carved((x, y), Cell::NORTH) {
+                    Self::write_line(output, (left, top), (right, top));
+                }
+                if !grid.is_carved((x, y), Cell::WEST) {
+                    Self::write_line(output, (left, top), (left, bottom));
+                }
+                if x + 1 == grid.width() && !grid.is_carved((x, y), Cell::EAST) {
+                    Self::write_line(output, (right, top), (right, bottom));
+                }
+                if y + 1 == grid.height() && !grid.is_carved((x, y), Cell::SOUTH) {
+                    Self::write_line(output, (left, bottom), (right, bottom));
+                }
+            }
+        }
+
+        output.push_str("</g>\n");
+    }
+
+    fn write_solution(&self, output: &mut String, path: &[Coords]) {
+        let _ = writeln!(
+            output,
+            r#"<g id="solution" stroke="{}" stroke-width="{}" stroke-linecap="round">"#,
+            self.solution_color, self.wall_width
+        );
+
+        let center = |(x, y): Coords| {
+            (
+                (x as f32 + 0.5).mul_add(self.cell_size, self.margin),
+                (y as f32 + 0.5).mul_add(self.cell_size, self.margin),
+            )
+        };
+        for step in path.windows(2) {
+            Self::write_line(output, center(step[0]), center(step[1]));
+        }
+
+        output.push_str("</g>\n");
+    }
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implementation of a formatter
+impl Formatter<StringWrapper> for Svg {
+    /// Converts a given grid into an SVG document and returns a [`StringWrapper`] over it
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        if let Some(scale) = grid.render_scale()
+            && !self.explicit_sizes.all()
+        {
+            return self.scaled(scale).format(grid);
+        }
+
+        let width = self.size(grid.width());
+        let height = self.size(grid.height());
+        let mut output = String::new();
+
+        let _ = writeln!(
+            output,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        let _ = writeln!(
+            output,
+            r#"<rect width="100%" height="100%" fill="{}"/>"#,
+            self.background_color
+        );
+
+        self.write_walls(&mut output, grid);
+        if let Some(path) = &self.solution {
+            self.write_solution(&mut output, path);
+        }
+
+        output.push_str("</svg>\n");
+
+        StringWrapper(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_call_default_params() {
+        let svg = Svg::new();
+        assert_eq!(20.0, svg.cell_size);
+        assert_eq!(2.0, svg.wall_width);
+        assert_eq!(10.0, svg.margin);
+        assert_eq!(Color::RGB(0, 0, 0), svg.stroke_color);
+        assert_eq!(Color::RGB(250, 250, 250), svg.background_color);
+        assert_eq!(None, svg.solution);
+    }
+
+    #[test]
+    fn params_change() {
+        let svg = Svg::new()
+            .wall_width(1.5)
+            .cell_size(8.0)
+            .margin(0.0)
+            .stroke_color(Color::RGB(1, 2, 3))
+            .background(Color::RGB(4, 5, 6));
+
+        assert_eq!(1.5, svg.wall_width);
+        assert_eq!(8.0, svg.cell_size);
+        assert_eq!(0.0, svg.margin);
+        assert_eq!(Color::RGB(1, 2, 3), svg.stroke_color);
+        assert_eq!(Color::RGB(4, 5, 6), svg.background_color);
+    }
+
+    #[test]
+    fn line_per_uncarved_wall() {
+        let grid = generate_maze();
+        let actual = Svg::new()
+            .cell_size(10.0)
+            .margin(0.0)
+            .format(&grid)
+            .into_inner();
+
+        // A 4x4 grid has 40 wall segments and a perfect maze carves 15 of them
+        assert_eq!(actual.matches("<line ").count(), 25);
+        assert!(actual.contains(r#"viewBox="0 0 40 40""#));
+        assert!(actual.contains(r#"<line x1="0" y1="0" x2="10" y2="0"/>"#));
+        assert!(!actual.contains(r#"<line x1="0" y1="10" x2="10" y2="10"/>"#));
+    }
+
+    #[test]
+    fn format_without_solution_layer() {
+        let grid = generate_maze();
+        let actual = Svg::new().format(&grid).into_inner();
+
+        assert!(actual.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="100""#));
+        assert!(actual.ends_with("</svg>\n"));
+        assert_eq!(actual.matches("<g ").count(), 1);
+        assert!(actual.contains(r#"<g id="walls""#));
+        assert!(!actual.contains(r#"<g id="solution""#));
+    }
+
+    #[test]
+    fn format_with_solution_layer() {
+        let grid = generate_maze();
+        let path = vec![(0, 0), (0, 1), (1, 1), (1, 2), (2, 2)];
+        let actual = Svg::new()
+            .with_solution_layer(path)
+            .format(&grid)
+            .into_inner();
+
+        assert_eq!(actual.matches("<g ").count(), 2);
+        assert!(actual.contains(r#"<g id="walls""#));
+
+        let solution = actual
+            .split(r#"<g id="solution""#)
+            .nth(1)
+            .and_then(|layer| layer.split("</g>").next())
+            .unwrap();
+        assert_eq!(solution.matches("<line ").count(), 4);
+        assert!(solution.contains(r#"<line x1="20" y1="20" x2="20" y2="40"/>"#));
+    }
+
+    fn generate_maze() -> Grid {
+        let mut grid = Grid::new(4, 4);
+
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((0, 1), Cell::EAST).unwrap();
+        grid.carve_passage((0, 2), Cell::EAST).unwrap();
+        grid.carve_passage((0, 2), Cell::SOUTH).unwrap();
+        grid.carve_passage((0, 3), Cell::EAST).unwrap();
+
+        grid.carve_passage((1, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 1), Cell::EAST).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 2), Cell::EAST).unwrap();
+        grid.carve_passage((1, 3), Cell::EAST).unwrap();
+
+        grid.carve_passage((2, 0), Cell::EAST).unwrap();
+        grid.carve_passage((2, 2), Cell::EAST).unwrap();
+        grid.carve_passage((2, 3), Cell::EAST).unwrap();
+
+        grid.carve_passage((3, 1), Cell::NORTH).unwrap();
+        grid.carve_passage((3, 1), Cell::SOUTH).unwrap();
+
+        grid
+    }
+}
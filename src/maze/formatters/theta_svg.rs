@@ -0,0 +1,185 @@
+use crate::maze::{
+    ThetaMaze,
+    grid::polar::{PolarCell, PolarGrid},
+};
+use crate::utils::color::Color;
+use std::{f32::consts::TAU, fmt::Write};
+
+use super::StringWrapper;
+
+/// An SVG formatter for a circular [`ThetaMaze`]
+///
+/// Walls between the rings are emitted as arcs and walls within a ring as radial lines, all grouped
+/// into a `<g id="walls">` layer. The first cell of every ring starts at the top of the maze.
+#[derive(Clone)]
+pub struct ThetaSvg {
+    cell_size: f32,
+    wall_width: f32,
+    margin: f32,
+    stroke_color: Color,
+    background_color: Color,
+}
+
+impl ThetaSvg {
+    /// Returns a new instance of a [`ThetaSvg`] formatter with a default settings
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            cell_size: 20.0,
+            wall_width: 2.0,
+            margin: 10.0,
+            stroke_color: Color::RGB(0, 0, 0),
+            background_color: Color::RGB(250, 250, 250),
+        }
+    }
+
+    /// Sets a wall width and returns itself
+    #[must_use]
+    pub const fn wall_width(mut self, width: f32) -> Self {
+        self.wall_width = width;
+        self
+    }
+
+    /// Sets a cell size, i.e. the width of a ring, and returns itself
+    #[must_use]
+    pub const fn cell_size(mut self, size: f32) -> Self {
+        self.cell_size = size;
+        self
+    }
+
+    /// Sets a margin (a distance between a maze and the document borders) and returns itself
+    #[must_use]
+    pub const fn margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets a wall color and returns itself
+    #[must_use]
+    pub const fn stroke_color(mut self, color: Color) -> Self {
+        self.stroke_color = color;
+        self
+    }
+
+    /// Sets a background color and returns itself
+    #[must_use]
+    pub const fn background(mut self, color: Color) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    /// Converts a given maze into an SVG document and returns a [`StringWrapper`] over it
+    #[must_use]
+    pub fn format(&self, maze: &ThetaMaze) -> StringWrapper {
+        let grid = maze.grid();
+        let radius = grid.rings() as f32 * self.cell_size;
+        let size = 2.0f32.mul_add(radius, self.margin * 2.0);
+        let mut output = String::new();
+
+        let _ = writeln!(
+            output,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">"#
+        );
+        let _ = writeln!(
+            output,
+            r#"<rect width="100%" height="100%" fill="{}"/>"#,
+            self.background_color
+        );
+
+        self.write_walls(&mut output, grid, radius);
+        output.push_str("</svg>\n");
+
+        StringWrapper(output)
+    }
+
+    fn write_walls(&self, output: &mut String, grid: &PolarGrid, radius: f32) {
+        let _ = writeln!(
+            output,
+            r#"<g id="walls" stroke="{}" stroke-width="{}" stroke-linecap="round" fill="none">"#,
+            self.stroke_color, self.wall_width
+        );
+
+        let center = self.margin + radius;
+        let point = |distance: f32, angle: f32| {
+            (
+                distance.mul_add(angle.cos(), center),
+                distance.mul_add(angle.sin(), center),
+            )
+        };
+
+        for (index, ring) in grid.coords() {
+            let count = grid.ring_cells(ring);
+            let cell = grid.cell((index, ring));
+            let inner = ring as f32 * self.cell_size;
+            let outer = inner + self.cell_size;
+            // Angles grow clockwise, as the Y axis points down
+            let start = (TAU * index as f32).mul_add(1.0 / count as f32, -TAU / 4.0);
+            let end = start + TAU / count as f32;
+
+            if ring > 0 && !cell.contains(PolarCell::INWARD) {
+                if count == 1 {
+                    Self::write_circle(output, center, inner);
+                } else {
+                    let (from, to) = (point(inner, start), point(inner, end));
+                    let _ = writeln!(
+                        output,
+                        r#"<path d="M {:.2} {:.2} A {inner} {inner} 0 0 1 {:.2} {:.2}"/>"#,
+                        from.0, from.1, to.0, to.1
+                    );
+                }
+            }
+
+            if count > 1 && !cell.contains(PolarCell::CLOCKWISE) {
+                let (from, to) = (point(inner, end), point(outer, end));
+                let _ = writeln!(
+                    output,
+                    r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}"/>"#,
+                    from.0, from.1, to.0, to.1
+                );
+            }
+        }
+
+        Self::write_circle(output, center, radius);
+        output.push_str("</g>\n");
+    }
+
+    fn write_circle(output: &mut String, center: f32, radius: f32) {
+        let _ = writeln!(
+            output,
+            r#"<circle cx="{center}" cy="{center}" r="{radius}"/>"#
+        );
+    }
+}
+
+impl Default for ThetaSvg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::ThetaMazeBuilder;
+
+    #[test]
+    fn walls_of_a_perfect_maze() {
+        let maze = ThetaMazeBuilder::new().rings(4).seed(5).build().unwrap();
+        let svg = ThetaSvg::new()
+            .cell_size(10.0)
+            .margin(5.0)
+            .format(&maze)
+            .into_inner();
+
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="90" height="90""#)
+        );
+        assert!(svg.contains(r#"<circle cx="45" cy="45" r="40"/>"#));
+
+        // Every cell around the center has an inward and a clockwise wall, and a perfect maze
+        // carves one passage per cell but the starting one, leaving half of the walls in place
+        let cells: usize = (0..maze.rings()).map(|ring| maze.ring_cells(ring)).sum();
+        let walls = svg.matches("<path").count() + svg.matches("<line").count();
+        assert_eq!(walls, cells - 1);
+    }
+}
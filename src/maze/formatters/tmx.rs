@@ -0,0 +1,161 @@
+use crate::maze::{
+    formatters::{Formatter, StringWrapper},
+    grid::Grid,
+};
+use std::fmt::Write;
+
+/// Number of tiles in the tileset, one for every wall configuration of an orthogonal cell
+const TILE_COUNT: u32 = 16;
+
+/// A formatter to emit the maze as a [Tiled](https://www.mapeditor.org) TMX map
+///
+/// The map has a single tile layer, where the GID of every cell is `1 +`
+/// [`Cell::to_bits`](crate::maze::Cell::to_bits), so the tile at index `n` of the tileset must
+/// picture a cell with the passages of `n`, just like the `tile_{bits}.png` sprites of the Bevy
+/// example. The tileset refers to a `<tileset name>.png` image with all the 16 tiles in a row.
+/// Only orthogonal mazes are supported.
+///
+/// # Example:
+///
+/// ```no_test
+/// <?xml version="1.0" encoding="UTF-8"?>
+/// <map version="1.10" orientation="orthogonal" renderorder="right-down" width="2" height="1" tilewidth="32" tileheight="32" infinite="0" nextlayerid="2" nextobjectid="1">
+///  <tileset firstgid="1" name="maze" tilewidth="32" tileheight="32" tilecount="16" columns="16">
+///   <image source="maze.png" width="512" height="32"/>
+///  </tileset>
+///  <layer id="1" name="maze" width="2" height="1">
+///   <data encoding="csv">
+/// 5,9
+/// </data>
+///  </layer>
+/// </map>
+/// ```
+pub struct Tmx {
+    tileset_name: String,
+    tile_size: u32,
+}
+
+impl Tmx {
+    /// Returns a new instance of a [`Tmx`] formatter with a `maze` tileset of 32x32 pixel tiles
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            tileset_name: "maze".to_string(),
+            tile_size: 32,
+        }
+    }
+
+    /// Sets a tileset name, which is also the name of its image without the `.png` extension, and
+    /// returns itself
+    #[must_use]
+    pub fn tileset_name(mut self, name: &str) -> Self {
+        self.tileset_name = name.to_string();
+        self
+    }
+
+    /// Sets a width and height of a tile in pixels and returns itself
+    #[must_use]
+    pub const fn tile_size(mut self, size: u32) -> Self {
+        self.tile_size = size;
+        self
+    }
+}
+
+impl Default for Tmx {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implementation of a TMX formatter
+impl Formatter<StringWrapper> for Tmx {
+    /// Converts a given grid into a TMX map and returns an [`StringWrapper`] over that string
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        let (width, height) = (grid.width(), grid.height());
+        let size = self.tile_size;
+        let name = escape(&self.tileset_name);
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+        let _ = writeln!(
+            output,
+            r#"<map version="1.10" orientation="orthogonal" renderorder="right-down" width="{width}" height="{height}" tilewidth="{size}" tileheight="{size}" infinite="0" nextlayerid="2" nextobjectid="1">"#
+        );
+        let _ = writeln!(
+            output,
+            r#" <tileset firstgid="1" name="{name}" tilewidth="{size}" tileheight="{size}" tilecount="{TILE_COUNT}" columns="{TILE_COUNT}">"#
+        );
+        let _ = writeln!(
+            output,
+            r#"  <image source="{name}.png" width="{}" height="{size}"/>"#,
+            size * TILE_COUNT
+        );
+        output.push_str(" </tileset>\n");
+        let _ = writeln!(
+            output,
+            r#" <layer id="1" name="maze" width="{width}" height="{height}">"#
+        );
+        output.push_str("  <data encoding=\"csv\">\n");
+
+        let rows: Vec<String> = (0..height)
+            .map(|y| {
+                let gids: Vec<String> = (0..width)
+                    .map(|x| (u32::from(grid[(x, y)].to_bits()) % TILE_COUNT + 1).to_string())
+                    .collect();
+                gids.join(",")
+            })
+            .collect();
+        output.push_str(&rows.join(",\n"));
+
+        output.push_str("\n</data>\n </layer>\n</map>\n");
+        StringWrapper(output)
+    }
+}
+
+/// Escapes the characters that can't appear in an XML attribute value
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::grid::cell::Cell;
+
+    #[test]
+    fn map_parses_with_a_tile_per_cell() {
+        let mut grid = Grid::new(3, 2);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::EAST).unwrap();
+
+        let tmx = Tmx::new()
+            .tileset_name("dungeon & co")
+            .tile_size(16)
+            .format(&grid)
+            .into_inner();
+        let document = roxmltree::Document::parse(&tmx).unwrap();
+        let map = document.root_element();
+
+        assert_eq!(map.attribute("width"), Some("3"));
+        assert_eq!(map.attribute("tilewidth"), Some("16"));
+        let tileset = map.children().find(|n| n.has_tag_name("tileset")).unwrap();
+        assert_eq!(tileset.attribute("name"), Some("dungeon & co"));
+
+        let data = document
+            .descendants()
+            .find(|n| n.has_tag_name("data"))
+            .unwrap();
+        let gids: Vec<u32> = data
+            .text()
+            .unwrap()
+            .split(',')
+            .map(|gid| gid.trim().parse().unwrap())
+            .collect();
+        assert_eq!(gids.len(), 3 * 2);
+        assert_eq!(gids, vec![5, 11, 1, 1, 6, 9]);
+    }
+}
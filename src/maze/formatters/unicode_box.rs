@@ -0,0 +1,289 @@
+use crate::maze::{
+    formatters::{Formatter, StringWrapper},
+    grid::{Grid, cell::Cell},
+};
+
+/// A formatter to emit the maze with box-drawing characters
+///
+/// Walls are drawn with single lines and joined at the corners of the cells with the matching
+/// junctions. With [`framed`](Self::framed), the outer border of the maze is drawn with double
+/// lines, while inner walls meeting it use the mixed single and double junctions.
+///
+/// # Example:
+///
+/// ```no_test
+/// ╔═══╤═══════════╗
+/// ║   │           ║
+/// ║   └───────┐   ║
+/// ║           │   ║
+/// ╟───╴   ╶───┘   ║
+/// ║               ║
+/// ║   ╶───────────╢
+/// ║               ║
+/// ╚═══════════════╝
+/// ```
+pub struct UnicodeBox {
+    framed: bool,
+}
+
+/// A wall joining a corner of the cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Line {
+    None,
+    Single,
+    Double,
+}
+
+impl UnicodeBox {
+    /// Returns a new instance of a [`UnicodeBox`] formatter drawing all the walls with single
+    /// lines
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { framed: false }
+    }
+
+    /// Draws the outer border of the maze with double lines and returns itself
+    #[must_use]
+    pub const fn framed(mut self) -> Self {
+        self.framed = true;
+        self
+    }
+
+    /// Returns the line of a wall, which is doubled on the border of a framed maze
+    const fn line(&self, wall: bool, border: bool) -> Line {
+        match (wall, border && self.framed) {
+            (false, _) => Line::None,
+            (true, false) => Line::Single,
+            (true, true) => Line::Double,
+        }
+    }
+
+    /// Returns the line of the wall above the cell at given coords, or below the last row
+    fn horizontal(&self, grid: &Grid, x: usize, y: usize) -> Line {
+        let wall = if y == grid.height() {
+            !grid.is_carved((x, y - 1), Cell::SOUTH)
+        } else if y == 0 {
+            !grid.is_carved((x, y), Cell::NORTH)
+        } else {
+            !grid.is_carved((x, y - 1), Cell::SOUTH)
+        };
+        self.line(wall, y == 0 || y == grid.height())
+    }
+
+    /// Returns the line of the wall to the left of the cell at given coords, or to the right of the
+    /// last column
+    fn vertical(&self, grid: &Grid, x: usize, y: usize) -> Line {
+        let wall = if x == grid.width() {
+            !grid.is_carved((x - 1, y), Cell::EAST)
+        } else if x == 0 {
+            !grid.is_carved((x, y), Cell::WEST)
+        } else {
+            !grid.is_carved((x - 1, y), Cell::EAST)
+        };
+        self.line(wall, x == 0 || x == grid.width())
+    }
+
+    /// Returns the junction at the top-left corner of the cell at given coords
+    fn corner(&self, grid: &Grid, x: usize, y: usize) -> char {
+        let up = if y > 0 {
+            self.vertical(grid, x, y - 1)
+        } else {
+            Line::None
+        };
+        let down = if y < grid.height() {
+            self.vertical(grid, x, y)
+        } else {
+            Line::None
+        };
+        let left = if x > 0 {
+            self.horizontal(grid, x - 1, y)
+        } else {
+            Line::None
+        };
+        let right = if x < grid.width() {
+            self.horizontal(grid, x, y)
+        } else {
+            Line::None
+        };
+        junction(up, down, left, right)
+    }
+}
+
+impl Default for UnicodeBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the box-drawing character joining the walls going up, down, left and right from a corner
+const fn junction(up: Line, down: Line, left: Line, right: Line) -> char {
+    use Line::{Double as D, None as N, Single as S};
+
+    match (up, down, left, right) {
+        // Corners and junctions of the frame
+        (N, D, N, D) => '╔',
+        (N, D, D, N) => '╗',
+        (D, N, N, D) => '╚',
+        (D, N, D, N) => '╝',
+        (N, S, D, D) => '╤',
+        (S, N, D, D) => '╧',
+        (D, D, N, S) => '╟',
+        (D, D, S, N) => '╢',
+        (N, S, D, N) => '╕',
+        (N, S, N, D) => '╒',
+        (S, N, D, N) => '╛',
+        (S, N, N, D) => '╘',
+        (D, N, N, S) => '╙',
+        (N, D, N, S) => '╓',
+        (D, N, S, N) => '╜',
+        (N, D, S, N) => '╖',
+        // The rest of the frame, where it's broken by an opening
+        (_, _, D, _) | (_, _, _, D) => '═',
+        (D, _, _, _) | (_, D, _, _) => '║',
+        (N, N, N, N) => ' ',
+        (S, N, N, N) => '╵',
+        (N, S, N, N) => '╷',
+        (N, N, S, N) => '╴',
+        (N, N, N, S) => '╶',
+        (S, S, N, N) => '│',
+        (N, N, S, S) => '─',
+        (N, S, N, S) => '┌',
+        (N, S, S, N) => '┐',
+        (S, N, N, S) => '└',
+        (S, N, S, N) => '┘',
+        (S, S, N, S) => '├',
+        (S, S, S, N) => '┤',
+        (N, S, S, S) => '┬',
+        (S, N, S, S) => '┴',
+        (S, S, S, S) => '┼',
+    }
+}
+
+/// An implementation of a box-drawing formatter
+impl Formatter<StringWrapper> for UnicodeBox {
+    /// Converts a given grid into box-drawing characters and returns a [`StringWrapper`] over them
+    fn format(&self, grid: &Grid) -> StringWrapper {
+        let mut output = String::new();
+        if grid.width() == 0 || grid.height() == 0 {
+            return StringWrapper(output);
+        }
+
+        for y in 0..=grid.height() {
+            for x in 0..=grid.width() {
+                output.push(self.corner(grid, x, y));
+                if x < grid.width() {
+                    let wall = match self.horizontal(grid, x, y) {
+                        Line::None => " ",
+                        Line::Single => "─",
+                        Line::Double => "═",
+                    };
+                    output.push_str(&wall.repeat(3));
+                }
+            }
+            output.push('\n');
+
+            if y == grid.height() {
+                break;
+            }
+            for x in 0..=grid.width() {
+                output.push(match self.vertical(grid, x, y) {
+                    Line::None => ' ',
+                    Line::Single => '│',
+                    Line::Double => '║',
+                });
+                if x < grid.width() {
+                    output.push_str("   ");
+                }
+            }
+            output.push('\n');
+        }
+
+        StringWrapper(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_single_lines() {
+        let mut expected = String::new();
+        expected.push_str("┌───┬───────────┐\n");
+        expected.push_str("│   │           │\n");
+        expected.push_str("│   └───────┐   │\n");
+        expected.push_str("│           │   │\n");
+        expected.push_str("├───╴   ╶───┘   │\n");
+        expected.push_str("│               │\n");
+        expected.push_str("│   ╶───────────┤\n");
+        expected.push_str("│               │\n");
+        expected.push_str("└───────────────┘\n");
+
+        let actual = UnicodeBox::new().format(&generate_maze()).0;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn format_framed() {
+        let mut expected = String::new();
+        expected.push_str("╔═══╤═══════════╗\n");
+        expected.push_str("║   │           ║\n");
+        expected.push_str("║   └───────┐   ║\n");
+        expected.push_str("║           │   ║\n");
+        expected.push_str("╟───╴   ╶───┘   ║\n");
+        expected.push_str("║               ║\n");
+        expected.push_str("║   ╶───────────╢\n");
+        expected.push_str("║               ║\n");
+        expected.push_str("╚═══════════════╝\n");
+
+        let actual = UnicodeBox::new().framed().format(&generate_maze()).0;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn format_framed_with_openings() {
+        let mut grid = generate_maze();
+        assert!(grid.open_border((0, 0), Cell::WEST));
+        assert!(grid.open_border((3, 3), Cell::SOUTH));
+
+        let actual = UnicodeBox::new().framed().format(&grid).0;
+        let lines: Vec<&str> = actual.lines().collect();
+
+        assert_eq!(lines[0], "════╤═══════════╗");
+        assert_eq!(lines[1], "    │           ║");
+        assert_eq!(lines[2], "║   └───────┐   ║");
+        assert_eq!(lines[8], "╚════════════   ║");
+    }
+
+    #[test]
+    fn format_empty() {
+        assert_eq!(UnicodeBox::new().format(&Grid::new(0, 3)).0, "");
+    }
+
+    fn generate_maze() -> Grid {
+        let mut grid = Grid::new(4, 4);
+
+        grid.carve_passage((0, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((0, 1), Cell::EAST).unwrap();
+        grid.carve_passage((0, 2), Cell::EAST).unwrap();
+        grid.carve_passage((0, 2), Cell::SOUTH).unwrap();
+        grid.carve_passage((0, 3), Cell::EAST).unwrap();
+
+        grid.carve_passage((1, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 1), Cell::EAST).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 2), Cell::EAST).unwrap();
+        grid.carve_passage((1, 3), Cell::EAST).unwrap();
+
+        grid.carve_passage((2, 0), Cell::EAST).unwrap();
+        grid.carve_passage((2, 2), Cell::EAST).unwrap();
+        grid.carve_passage((2, 3), Cell::EAST).unwrap();
+
+        grid.carve_passage((3, 1), Cell::NORTH).unwrap();
+        grid.carve_passage((3, 1), Cell::SOUTH).unwrap();
+
+        grid
+    }
+}
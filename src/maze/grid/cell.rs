@@ -7,6 +7,8 @@ bitflags! {
     ///Maze Cell defining open passages
     #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Component, Reflect)]
     #[reflect(opaque)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct Cell: u8 {
         /// Has passage to NORTH
         const NORTH = 0b0001;
@@ -28,6 +30,9 @@ bitflags! {
 }
 
 impl Cell {
+    /// Directions of an orthogonal cell
+    const ORTHOGONAL: [Self; 4] = [Self::NORTH, Self::SOUTH, Self::EAST, Self::WEST];
+
     ///Returns bits &str representation.
     /// > use `to_bits_string` for string value
     #[must_use]
@@ -67,6 +72,23 @@ impl Cell {
         sides.saturating_sub(self.bits().count_ones() as u8)
     }
 
+    ///Returns the orthogonal directions with an open passage, in `NORTH`, `SOUTH`, `EAST`, `WEST`
+    ///order.
+    pub fn open_directions(&self) -> impl Iterator<Item = Self> {
+        let cell = *self;
+        Self::ORTHOGONAL
+            .into_iter()
+            .filter(move |direction| cell.contains(*direction))
+    }
+
+    ///Returns the orthogonal directions closed by a wall, in `NORTH`, `SOUTH`, `EAST`, `WEST` order.
+    pub fn walls(&self) -> impl Iterator<Item = Self> {
+        let cell = *self;
+        Self::ORTHOGONAL
+            .into_iter()
+            .filter(move |direction| !cell.contains(*direction))
+    }
+
     ///Checks if [`Cell`] has 3 walls (*Dead End*)
     #[must_use]
     pub const fn is_end_sq(&self) -> bool {
@@ -98,6 +120,7 @@ impl fmt::Display for Cell {
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CellStatus {
     visited: bool,
     marked: bool,
@@ -124,6 +147,19 @@ impl CellStatus {
 mod tests {
     use super::Cell;
 
+    #[test]
+    fn open_directions_and_walls() {
+        let cell = Cell::SOUTH | Cell::EAST;
+
+        assert_eq!(
+            cell.open_directions().collect::<Vec<_>>(),
+            [Cell::SOUTH, Cell::EAST]
+        );
+        assert_eq!(cell.walls().collect::<Vec<_>>(), [Cell::NORTH, Cell::WEST]);
+        assert_eq!(Cell::empty().open_directions().count(), 0);
+        assert_eq!(Cell::all().walls().count(), 0);
+    }
+
     #[test]
     fn empty_is_0000_str() {
         let zero = Cell::empty();
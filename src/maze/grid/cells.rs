@@ -0,0 +1,193 @@
+use super::{cell::Cell, topology::Topology};
+
+/// All the cells an orthogonal grid may contain, indexed by their bits. Packed cells are resolved
+/// to references into this table, so lookups can keep returning `&Cell`
+#[cfg(feature = "compact")]
+static ORTHOGONAL_CELLS: [Cell; 16] = {
+    let mut cells = [Cell::empty(); 16];
+    let mut bits = 0;
+    while bits < 16 {
+        cells[bits] = Cell::from_bits_retain(bits as u8);
+        bits += 1;
+    }
+    cells
+};
+
+/// Storage for the cells of a grid
+///
+/// By default, each cell takes a byte. With the `compact` feature enabled, orthogonal grids store
+/// their cells as 4-bit values packed two per byte, which halves the memory required for the cells
+/// of large mazes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cells {
+    Full(Vec<Cell>),
+    #[cfg(feature = "compact")]
+    Packed {
+        bytes: Vec<u8>,
+        len: usize,
+    },
+}
+
+impl Cells {
+    pub fn new(len: usize, topology: Topology) -> Self {
+        #[cfg(feature = "compact")]
+        if topology == Topology::Orthogonal {
+            return Self::packed(len);
+        }
+
+        let _ = topology;
+        Self::full(len)
+    }
+
+    pub fn full(len: usize) -> Self {
+        Self::Full(vec![Cell::default(); len])
+    }
+
+    #[cfg(feature = "compact")]
+    pub fn packed(len: usize) -> Self {
+        Self::Packed {
+            bytes: vec![0; len.div_ceil(2)],
+            len,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        match self {
+            Self::Full(cells) => cells.len(),
+            #[cfg(feature = "compact")]
+            Self::Packed { len, .. } => *len,
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Cell> {
+        match self {
+            Self::Full(cells) => cells.get(index),
+            #[cfg(feature = "compact")]
+            Self::Packed { bytes, len } => {
+                if index >= *len {
+                    return None;
+                }
+                let nibble = (bytes[index / 2] >> ((index % 2) * 4)) & 0x0F;
+                Some(&ORTHOGONAL_CELLS[nibble as usize])
+            }
+        }
+    }
+
+    pub fn set(&mut self, index: usize, cell: Cell) {
+        match self {
+            Self::Full(cells) => cells[index] = cell,
+            #[cfg(feature = "compact")]
+            Self::Packed { bytes, .. } => {
+                debug_assert!(
+                    cell.bits() <= 0x0F,
+                    "Packed cells only support orthogonal passages"
+                );
+                let shift = (index % 2) * 4;
+                let byte = &mut bytes[index / 2];
+                *byte = (*byte & !(0x0F << shift)) | ((cell.bits() & 0x0F) << shift);
+            }
+        }
+    }
+
+    /// Returns a mutable handle to a cell, which writes the cell back when dropped, as packed cells
+    /// can't be borrowed mutably
+    pub fn get_mut(&mut self, index: usize) -> Option<CellMut<'_>> {
+        let cell = *self.get(index)?;
+        Some(CellMut {
+            cells: self,
+            index,
+            cell,
+        })
+    }
+
+    pub fn insert(&mut self, index: usize, direction: Cell) {
+        let cell = self[index] | direction;
+        self.set(index, cell);
+    }
+
+    pub fn remove(&mut self, index: usize, direction: Cell) {
+        let cell = self[index] - direction;
+        self.set(index, cell);
+    }
+}
+
+/// A mutable reference to a cell of a maze
+///
+/// Dereferences to a [`Cell`] and stores the changes in the maze when dropped.
+pub struct CellMut<'a> {
+    cells: &'a mut Cells,
+    index: usize,
+    cell: Cell,
+}
+
+impl std::ops::Deref for CellMut<'_> {
+    type Target = Cell;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cell
+    }
+}
+
+impl std::ops::DerefMut for CellMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cell
+    }
+}
+
+impl Drop for CellMut<'_> {
+    fn drop(&mut self) {
+        self.cells.set(self.index, self.cell);
+    }
+}
+
+impl std::ops::Index<usize> for Cells {
+    type Output = Cell;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index)
+            .unwrap_or_else(|| panic!("Cell at index {index} doesn't exist."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_cells() {
+        let mut cells = Cells::full(3);
+        cells.insert(1, Cell::NORTH);
+        cells.insert(1, Cell::EAST);
+        cells.set(2, Cell::SOUTH);
+
+        assert_eq!(cells.len(), 3);
+        assert_eq!(cells[0], Cell::empty());
+        assert_eq!(cells[1], Cell::NORTH | Cell::EAST);
+        assert_eq!(cells[2], Cell::SOUTH);
+        assert_eq!(cells.get(3), None);
+    }
+
+    #[test]
+    #[cfg(feature = "compact")]
+    fn packed_cells() {
+        let mut cells = Cells::packed(3);
+        cells.insert(1, Cell::NORTH);
+        cells.insert(1, Cell::EAST);
+        cells.set(2, Cell::SOUTH);
+        cells.set(0, Cell::NORTH | Cell::SOUTH | Cell::EAST);
+
+        assert_eq!(cells.len(), 3);
+        assert_eq!(cells[0], Cell::NORTH | Cell::SOUTH | Cell::EAST);
+        assert_eq!(cells[1], Cell::NORTH | Cell::EAST);
+        assert_eq!(cells[2], Cell::SOUTH);
+        assert_eq!(cells.get(3), None);
+        assert_eq!(
+            cells,
+            Cells::Packed {
+                bytes: vec![0x57, 0x02],
+                len: 3
+            }
+        );
+    }
+}
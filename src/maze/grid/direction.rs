@@ -0,0 +1,139 @@
+use super::cell::Cell;
+use crate::utils::types::Coords;
+
+/// A direction of a passage of an orthogonal maze
+///
+/// Unlike [`Cell`], which is a set of open passages, a direction is always a single one, so it
+/// can't be empty or mixed with other directions by mistake. Convert it into a [`Cell`] with
+/// `Cell::from` to work with the passages of a cell. Grid methods carving or following passages
+/// accept either of them.
+///
+/// Algorithms limited to orthogonal grids step in directions, while the ones that also run on
+/// hexagonal grids, e.g. [`RecursiveBacktracking`](crate::maze::RecursiveBacktracking), iterate
+/// over the [`Cell`] directions of the grid topology, which has six of them on hexagonal grids.
+///
+/// # Example
+/// ```
+/// use bevy_knossos::maze::{Cell, Direction};
+///
+/// let direction = Direction::East;
+/// assert_eq!(direction.opposite(), Direction::West);
+/// assert_eq!(direction.to_offset(), (1, 0));
+/// assert_eq!(Cell::from(direction), Cell::EAST);
+/// assert_eq!(Direction::from_cell(Cell::EAST), Some(direction));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// Towards the top row, i.e. decreasing `y`
+    North,
+    /// Towards the bottom row, i.e. increasing `y`
+    South,
+    /// Towards the last column, i.e. increasing `x`
+    East,
+    /// Towards the first column, i.e. decreasing `x`
+    West,
+}
+
+impl Direction {
+    /// All the directions in the order of their [`Cell`] flags
+    pub const ALL: [Self; 4] = [Self::North, Self::South, Self::East, Self::West];
+
+    /// Returns the direction pointing the other way
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::South => Self::North,
+            Self::East => Self::West,
+            Self::West => Self::East,
+        }
+    }
+
+    /// Returns the change of the `x` and `y` coords after a step in this direction
+    #[must_use]
+    pub const fn to_offset(self) -> (isize, isize) {
+        match self {
+            Self::North => (0, -1),
+            Self::South => (0, 1),
+            Self::East => (1, 0),
+            Self::West => (-1, 0),
+        }
+    }
+
+    /// Returns the direction of a [`Cell`] with a single orthogonal passage, or `None` for any
+    /// other cell
+    #[must_use]
+    pub const fn from_cell(cell: Cell) -> Option<Self> {
+        match cell {
+            Cell::NORTH => Some(Self::North),
+            Cell::SOUTH => Some(Self::South),
+            Cell::EAST => Some(Self::East),
+            Cell::WEST => Some(Self::West),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`Cell`] flag of the direction, like `Cell::from`, but in const contexts
+    #[must_use]
+    pub const fn to_cell(self) -> Cell {
+        match self {
+            Self::North => Cell::NORTH,
+            Self::South => Cell::SOUTH,
+            Self::East => Cell::EAST,
+            Self::West => Cell::WEST,
+        }
+    }
+
+    /// Returns the coords one step away in this direction, or `None` if the step leaves the
+    /// `width` x `height` grid
+    #[must_use]
+    pub(crate) const fn step(self, (x, y): Coords, width: usize, height: usize) -> Option<Coords> {
+        let (dx, dy) = self.to_offset();
+        match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
+            (Some(nx), Some(ny)) if nx < width && ny < height => Some((nx, ny)),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction> for Cell {
+    fn from(direction: Direction) -> Self {
+        direction.to_cell()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_and_from_cells() {
+        for direction in Direction::ALL {
+            let cell = Cell::from(direction);
+
+            assert_eq!(cell.bits().count_ones(), 1);
+            assert_eq!(Direction::from_cell(cell), Some(direction));
+            assert_eq!(direction.opposite().opposite(), direction);
+
+            let (dx, dy) = direction.to_offset();
+            assert_eq!(direction.opposite().to_offset(), (-dx, -dy));
+        }
+
+        assert_eq!(Direction::from_cell(Cell::empty()), None);
+        assert_eq!(Direction::from_cell(Cell::NORTH | Cell::EAST), None);
+        assert_eq!(Direction::from_cell(Cell::NORTH_EAST), None);
+    }
+
+    #[test]
+    fn steps_stay_within_the_grid() {
+        assert_eq!(Direction::North.step((1, 1), 3, 3), Some((1, 0)));
+        assert_eq!(Direction::South.step((1, 1), 3, 3), Some((1, 2)));
+        assert_eq!(Direction::East.step((1, 1), 3, 3), Some((2, 1)));
+        assert_eq!(Direction::West.step((1, 1), 3, 3), Some((0, 1)));
+        assert_eq!(Direction::North.step((0, 0), 3, 3), None);
+        assert_eq!(Direction::West.step((0, 0), 3, 3), None);
+        assert_eq!(Direction::East.step((2, 2), 3, 3), None);
+        assert_eq!(Direction::South.step((2, 2), 3, 3), None);
+    }
+}
@@ -1,22 +1,67 @@
 pub mod cell;
+pub mod cells;
+pub mod direction;
+pub mod overpass;
+pub mod polar;
 pub mod topology;
 use self::cell::CellStatus;
 
-use super::errors::TransitError;
-use crate::utils::types::Coords;
+use super::{errors::TransitError, formatters::RenderScale, mask::Mask, steps::observe_carve};
+use crate::utils::{
+    arena::{ArenaTree, NodeId},
+    types::Coords,
+};
 use cell::Cell;
-use std::fmt;
+use cells::{CellMut, Cells};
+use overpass::Overpass;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    ops::ControlFlow,
+};
 use topology::Topology;
 
 type TransitResult<T> = Result<T, TransitError>;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Grid {
     width: usize,
     height: usize,
     topology: Topology,
-    pub(crate) cells: Vec<Cell>,
+    pub(crate) cells: Cells,
     cell_statuses: Vec<CellStatus>,
+    mask: Option<Mask>,
+    render_scale: Option<RenderScale>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "HashMap::is_empty")
+    )]
+    overpasses: HashMap<Coords, Overpass>,
+}
+
+/// Grids are compared without their render scale, which only holds default formatter sizes
+impl PartialEq for Grid {
+    fn eq(&self, other: &Self) -> bool {
+        let Self {
+            width,
+            height,
+            topology,
+            cells,
+            cell_statuses,
+            mask,
+            render_scale: _,
+            overpasses,
+        } = self;
+
+        *width == other.width
+            && *height == other.height
+            && *topology == other.topology
+            && *cells == other.cells
+            && *cell_statuses == other.cell_statuses
+            && *mask == other.mask
+            && *overpasses == other.overpasses
+    }
 }
 
 impl Grid {
@@ -33,8 +78,11 @@ impl Grid {
             width,
             height,
             topology,
-            cells: vec![Cell::default(); width * height],
+            cells: Cells::new(width * height, topology),
             cell_statuses: vec![CellStatus::default(); width * height],
+            mask: None,
+            render_scale: None,
+            overpasses: HashMap::new(),
         }
     }
 
@@ -71,34 +119,86 @@ impl Grid {
         self.cell_statuses[y * self.width + x]
     }
 
-    pub fn is_carved(&self, coords: Coords, direction: Cell) -> bool {
+    pub fn is_carved(&self, coords: Coords, direction: impl Into<Cell>) -> bool {
         let (x, y) = coords;
-        self.cells[y * self.width + x].contains(direction)
+        self.cells[y * self.width + x].contains(direction.into())
     }
 
-    pub fn carve_passage(&mut self, coords: Coords, direction: Cell) -> TransitResult<Coords> {
+    /// Opens the outer wall of a border cell in a given direction pointing out of the grid
+    ///
+    /// Returns `false` if the cell is outside of the grid or masked off, or if the direction leads
+    /// to another cell, even a masked off one.
+    pub fn open_border(&mut self, coords: Coords, direction: Cell) -> bool {
         let (x, y) = coords;
+        if !self.is_active(coords)
+            || !self.directions().contains(&direction)
+            || self
+                .topology
+                .next_coords(coords, direction, self.width, self.height)
+                .is_some()
+        {
+            return false;
+        }
+
+        self.cells.insert(y * self.width + x, direction);
+        true
+    }
+
+    pub fn carve_passage(
+        &mut self,
+        coords: Coords,
+        direction: impl Into<Cell>,
+    ) -> TransitResult<Coords> {
+        let (x, y) = coords;
+        let direction = direction.into();
         let (nx, ny) = self.get_next_cell_coords(coords, direction)?;
-        let opposite = self
-            .topology
-            .opposite(direction)
-            .ok_or_else(|| TransitError {
-                coords,
-                reason: format!("Invalid direction for {:?} topology", self.topology),
-            })?;
+        let opposite = self.opposite(coords, direction)?;
 
-        self.cells[y * self.width + x] |= direction;
-        self.cells[ny * self.width + nx] |= opposite;
+        self.cells.insert(y * self.width + x, direction);
+        self.cells.insert(ny * self.width + nx, opposite);
 
         self.visit_cell(coords);
         self.visit_cell((nx, ny));
+        observe_carve(coords, direction);
+
+        Ok((nx, ny))
+    }
+
+    /// Closes the passage between a cell and its neighbor in a given direction on both sides
+    pub fn fill_wall(
+        &mut self,
+        coords: Coords,
+        direction: impl Into<Cell>,
+    ) -> TransitResult<Coords> {
+        let (x, y) = coords;
+        let direction = direction.into();
+        let (nx, ny) = self.get_next_cell_coords(coords, direction)?;
+        let opposite = self.opposite(coords, direction)?;
+
+        self.cells.remove(y * self.width + x, direction);
+        self.cells.remove(ny * self.width + nx, opposite);
 
         Ok((nx, ny))
     }
 
-    pub fn get_next_cell_coords(&self, coords: Coords, direction: Cell) -> TransitResult<Coords> {
+    fn opposite(&self, coords: Coords, direction: Cell) -> TransitResult<Cell> {
+        self.topology
+            .opposite(direction)
+            .ok_or_else(|| TransitError {
+                coords,
+                reason: format!("Invalid direction for {:?} topology", self.topology),
+            })
+    }
+
+    pub fn get_next_cell_coords(
+        &self,
+        coords: Coords,
+        direction: impl Into<Cell>,
+    ) -> TransitResult<Coords> {
+        let direction = direction.into();
         self.topology
             .next_coords(coords, direction, self.width, self.height)
+            .filter(|next| self.is_active(*next))
             .ok_or_else(|| TransitError {
                 coords,
                 reason: format!(
@@ -119,6 +219,279 @@ impl Grid {
             .collect()
     }
 
+    /// Returns the cells reachable in a single step from a given cell through carved passages
+    ///
+    /// A crossing is left only along its corridor on top, while a corridor tunneling under a
+    /// crossing leads straight to the cell behind it.
+    pub fn steps(&self, coords: Coords) -> impl Iterator<Item = Coords> + '_ {
+        let over = self.overpass(coords);
+        self.neighbor_coords(coords)
+            .into_iter()
+            .filter(move |(dir, _)| {
+                self.is_carved(coords, *dir) && over.is_none_or(|over| over.is_over(*dir))
+            })
+            .filter_map(|(dir, next)| match self.overpass(next) {
+                Some(over) if !over.is_over(dir) => self.get_next_cell_coords(next, dir).ok(),
+                _ => Some(next),
+            })
+    }
+
+    /// Marks a cell as a crossing with a given corridor on top
+    pub fn set_overpass(&mut self, coords: Coords, overpass: Overpass) {
+        self.overpasses.insert(coords, overpass);
+    }
+
+    /// Removes the crossing of a cell, if any
+    pub(crate) fn clear_overpass(&mut self, coords: Coords) {
+        self.overpasses.remove(&coords);
+    }
+
+    /// Returns `true` if any cell is a crossing
+    pub fn has_overpasses(&self) -> bool {
+        !self.overpasses.is_empty()
+    }
+
+    /// Returns the corridor on top if a cell is a crossing
+    pub fn overpass(&self, coords: Coords) -> Option<Overpass> {
+        self.overpasses.get(&coords).copied()
+    }
+
+    /// Visits every cell reachable from `start` through carved passages in BFS order, calling
+    /// `visit` with the cell coords and its distance from `start`
+    pub fn flood_fill(&self, start: Coords, visit: impl FnMut(Coords, u32)) {
+        self.flood_fill_from([start], visit);
+    }
+
+    /// Like [`flood_fill`](Self::flood_fill), but starts from several cells at once, so every
+    /// cell is visited with its distance from the closest of them
+    pub(crate) fn flood_fill_from(
+        &self,
+        starts: impl IntoIterator<Item = Coords>,
+        mut visit: impl FnMut(Coords, u32),
+    ) {
+        self.flood_until(starts, |coords, distance| {
+            visit(coords, distance);
+            ControlFlow::Continue(())
+        });
+    }
+
+    /// Like [`flood_fill_from`](Self::flood_fill_from), but stops as soon as `visit` breaks
+    fn flood_until(
+        &self,
+        starts: impl IntoIterator<Item = Coords>,
+        mut visit: impl FnMut(Coords, u32) -> ControlFlow<()>,
+    ) {
+        let mut distances = vec![None; self.width * self.height];
+        let mut queue = VecDeque::new();
+        for start in starts {
+            if self.contains(start) && distances[start.1 * self.width + start.0].is_none() {
+                distances[start.1 * self.width + start.0] = Some(0);
+                queue.push_back(start);
+            }
+        }
+
+        while let Some(coords) = queue.pop_front() {
+            let Some(distance) = distances[coords.1 * self.width + coords.0] else {
+                continue;
+            };
+            if visit(coords, distance).is_break() {
+                return;
+            }
+
+            for (nx, ny) in self.steps(coords) {
+                if distances[ny * self.width + nx].is_some() {
+                    continue;
+                }
+                distances[ny * self.width + nx] = Some(distance + 1);
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    /// Returns all the cells reachable from `start` through carved passages in BFS order
+    pub fn reachable_cells(&self, start: Coords) -> Vec<Coords> {
+        let mut reachable = Vec::new();
+        self.flood_fill(start, |coords, _| reachable.push(coords));
+        reachable
+    }
+
+    /// Returns the number of connected components and the component index of every cell in
+    /// row-major order
+    ///
+    /// Cells are joined with a union-find over the carved passages, so the cost is linear in the
+    /// size of the grid and the call stack doesn't grow with it. Components are numbered in the
+    /// row-major order of their first cell, while masked-off cells belong to none.
+    pub fn components(&self) -> (usize, Vec<Option<usize>>) {
+        let mut arena = ArenaTree::new();
+        for _ in 0..self.width * self.height {
+            arena.new_node();
+        }
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if !self.is_active((x, y)) {
+                    continue;
+                }
+                // A passage joins two cells only if it's carved from both sides
+                for (nx, ny) in self.steps((x, y)) {
+                    if self.steps((nx, ny)).any(|back| back == (x, y)) {
+                        arena.connect(NodeId(y * self.width + x), NodeId(ny * self.width + nx));
+                    }
+                }
+            }
+        }
+
+        let mut labels = vec![None; self.width * self.height];
+        let mut root_labels: HashMap<usize, usize> = HashMap::new();
+        for (index, label) in labels.iter_mut().enumerate() {
+            if !self.is_active((index % self.width, index / self.width)) {
+                continue;
+            }
+            let Some(NodeId(root)) = arena.root(NodeId(index)) else {
+                continue;
+            };
+            let next = root_labels.len();
+            *label = Some(*root_labels.entry(root).or_insert(next));
+        }
+
+        (root_labels.len(), labels)
+    }
+
+    /// Returns `true` if `goal` can be reached from `start` through carved passages, where both
+    /// cells are within the grid and not masked off
+    pub fn is_reachable(&self, start: Coords, goal: Coords) -> bool {
+        self.is_active(start) && self.is_active(goal) && self.distance(start, goal).is_some()
+    }
+
+    /// Returns the number of steps between two cells or `None` if `goal` is unreachable
+    pub fn distance(&self, start: Coords, goal: Coords) -> Option<u32> {
+        if !self.contains(goal) {
+            return None;
+        }
+
+        let mut distance = None;
+        self.flood_until([start], |coords, steps| {
+            if coords != goal {
+                return ControlFlow::Continue(());
+            }
+            distance = Some(steps);
+            ControlFlow::Break(())
+        });
+        distance
+    }
+
+    /// Returns the shortest path between two cells, both included, or `None` if `goal` is
+    /// unreachable
+    pub fn shortest_path(&self, start: Coords, goal: Coords) -> Option<Vec<Coords>> {
+        if !self.contains(start) || !self.contains(goal) {
+            return None;
+        }
+
+        let mut parents: Vec<Option<Coords>> = vec![None; self.width * self.height];
+        let mut queue = VecDeque::from([start]);
+        parents[start.1 * self.width + start.0] = Some(start);
+
+        while let Some(coords) = queue.pop_front() {
+            if coords == goal {
+                let mut path = vec![goal];
+                let mut current = goal;
+                while current != start {
+                    current = parents[current.1 * self.width + current.0]?;
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for (nx, ny) in self.steps(coords) {
+                if parents[ny * self.width + nx].is_some() {
+                    continue;
+                }
+                parents[ny * self.width + nx] = Some(coords);
+                queue.push_back((nx, ny));
+            }
+        }
+
+        None
+    }
+
+    /// Returns `true` if the coords are within the grid bounds
+    pub const fn contains(&self, (x, y): Coords) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Returns a cell at given coords, or `None` if the coords are out of the grid
+    pub fn get(&self, coords: Coords) -> Option<&Cell> {
+        if !self.contains(coords) {
+            return None;
+        }
+        self.cells.get(coords.1 * self.width + coords.0)
+    }
+
+    /// Returns a mutable handle to a cell at given coords, or `None` if the coords are out of the
+    /// grid
+    pub fn get_mut(&mut self, coords: Coords) -> Option<CellMut<'_>> {
+        if !self.contains(coords) {
+            return None;
+        }
+        self.cells.get_mut(coords.1 * self.width + coords.0)
+    }
+
+    /// Excludes the cells masked off by a given mask from the grid
+    pub fn apply_mask(&mut self, mask: Mask) {
+        self.mask = Some(mask);
+    }
+
+    /// Returns `true` if the coords are within the grid bounds and not masked off
+    pub fn is_active(&self, coords: Coords) -> bool {
+        self.contains(coords) && self.mask.as_ref().is_none_or(|mask| mask.is_active(coords))
+    }
+
+    /// Sets default sizes for the formatters rendering the grid
+    pub const fn set_render_scale(&mut self, scale: RenderScale) {
+        self.render_scale = Some(scale);
+    }
+
+    /// Returns default sizes for the formatters rendering the grid, if any
+    pub const fn render_scale(&self) -> Option<RenderScale> {
+        self.render_scale
+    }
+
+    /// Returns `true` if a mask is applied to the grid
+    pub const fn is_masked(&self) -> bool {
+        self.mask.is_some()
+    }
+
+    /// Returns the mask applied to the grid, if any
+    pub const fn mask(&self) -> Option<&Mask> {
+        self.mask.as_ref()
+    }
+
+    /// Returns random coords of a cell that is not masked off
+    pub fn random_active_cell(&self, rng: &mut impl rand::Rng) -> Option<Coords> {
+        use rand::seq::IndexedRandom;
+
+        let cells: Vec<Coords> = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|coords| self.is_active(*coords))
+            .collect();
+        cells.choose(rng).copied()
+    }
+
+    /// Returns the number of cells that are not masked off
+    pub fn active_count(&self) -> usize {
+        self.mask
+            .as_ref()
+            .map_or(self.width * self.height, Mask::active_count)
+    }
+
+    /// Returns the first active cell in row-major order
+    pub fn first_active_cell(&self) -> Option<Coords> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .find(|coords| self.is_active(*coords))
+    }
+
     fn visit_cell(&mut self, coords: Coords) {
         self.get_cell_status_mut(coords).visit();
     }
@@ -184,3 +557,98 @@ impl fmt::Display for Grid {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "compact")]
+    fn packed_grid_generates_identical_mazes() {
+        use crate::maze::algorithms::{Algorithm, Kruskal, RecursiveBacktracking};
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let algorithms: [fn() -> Box<dyn Algorithm>; 2] =
+            [|| Box::new(RecursiveBacktracking), || Box::new(Kruskal)];
+
+        for algorithm in algorithms {
+            for seed in [0, 7, 42] {
+                let mut packed = Grid::new(9, 7);
+                let mut full = Grid {
+                    cells: Cells::full(9 * 7),
+                    ..Grid::new(9, 7)
+                };
+                assert!(matches!(packed.cells, Cells::Packed { .. }));
+
+                algorithm().generate(&mut packed, None, &mut StdRng::seed_from_u64(seed));
+                algorithm().generate(&mut full, None, &mut StdRng::seed_from_u64(seed));
+
+                for y in 0..7 {
+                    for x in 0..9 {
+                        assert_eq!(packed[(x, y)], full[(x, y)]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn flood_fill_matches_manual_bfs() {
+        let mut grid = Grid::new(3, 3);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::WEST).unwrap();
+        grid.carve_passage((1, 1), Cell::EAST).unwrap();
+        grid.carve_passage((2, 1), Cell::NORTH).unwrap();
+        grid.carve_passage((0, 2), Cell::EAST).unwrap();
+
+        let mut visited = Vec::new();
+        grid.flood_fill((0, 0), |coords, distance| visited.push((coords, distance)));
+
+        // A manual BFS over the carved passages
+        let mut expected = vec![((0, 0), 0)];
+        let mut index = 0;
+        while index < expected.len() {
+            let (coords, distance) = expected[index];
+            for (dir, next) in grid.neighbor_coords(coords) {
+                if grid.is_carved(coords, dir) && !expected.iter().any(|(c, _)| *c == next) {
+                    expected.push((next, distance + 1));
+                }
+            }
+            index += 1;
+        }
+
+        assert_eq!(visited, expected);
+        assert_eq!(visited.len(), grid.reachable_cells((0, 0)).len());
+        assert_eq!(visited.len(), 6);
+        assert_eq!(grid.distance((0, 0), (2, 0)), Some(4));
+        assert_eq!(grid.distance((0, 0), (0, 2)), None);
+    }
+
+    #[test]
+    fn traversal_respects_overpasses() {
+        let mut grid = Grid::new(3, 3);
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((1, 1), Cell::SOUTH).unwrap();
+        grid.carve_passage((0, 1), Cell::EAST).unwrap();
+        grid.carve_passage((1, 1), Cell::EAST).unwrap();
+        grid.set_overpass((1, 1), Overpass::NorthSouth);
+
+        assert_eq!(grid.distance((1, 0), (1, 2)), Some(2));
+        assert_eq!(grid.distance((1, 0), (0, 1)), None);
+        assert_eq!(grid.distance((1, 1), (2, 1)), None);
+        // Tunneling under a crossing takes a single step
+        assert_eq!(grid.distance((0, 1), (2, 1)), Some(1));
+        assert_eq!(
+            grid.shortest_path((0, 1), (2, 1)),
+            Some(vec![(0, 1), (2, 1)])
+        );
+        assert_eq!(grid.reachable_cells((0, 1)), vec![(0, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn hex_grid_uses_full_cells() {
+        let grid = Grid::new_hex(3, 3);
+        assert!(matches!(grid.cells, Cells::Full(_)));
+    }
+}
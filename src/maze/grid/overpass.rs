@@ -0,0 +1,55 @@
+use super::cell::Cell;
+
+/// The corridor running on top at a crossing of a weave maze
+///
+/// A crossing cell has all four passages open, but its corridors don't connect: the one named by
+/// the overpass runs on top, while the other one tunnels under it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Overpass {
+    /// The north-south corridor runs on top, the east-west one tunnels under it
+    NorthSouth,
+    /// The east-west corridor runs on top, the north-south one tunnels under it
+    EastWest,
+}
+
+impl Overpass {
+    /// Returns the overpass running across a given direction
+    #[must_use]
+    pub const fn across(direction: Cell) -> Self {
+        if direction.intersects(Cell::NORTH.union(Cell::SOUTH)) {
+            Self::EastWest
+        } else {
+            Self::NorthSouth
+        }
+    }
+
+    /// Returns the open passages of the corridor on top
+    #[must_use]
+    pub const fn passages(self) -> Cell {
+        match self {
+            Self::NorthSouth => Cell::NORTH.union(Cell::SOUTH),
+            Self::EastWest => Cell::EAST.union(Cell::WEST),
+        }
+    }
+
+    /// Returns `true` if a given direction runs along the corridor on top
+    #[must_use]
+    pub const fn is_over(self, direction: Cell) -> bool {
+        self.passages().contains(direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directions() {
+        assert_eq!(Overpass::across(Cell::EAST), Overpass::NorthSouth);
+        assert_eq!(Overpass::across(Cell::NORTH), Overpass::EastWest);
+        assert!(Overpass::NorthSouth.is_over(Cell::SOUTH));
+        assert!(!Overpass::NorthSouth.is_over(Cell::WEST));
+        assert_eq!(Overpass::EastWest.passages(), Cell::EAST | Cell::WEST);
+    }
+}
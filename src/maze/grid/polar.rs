@@ -0,0 +1,265 @@
+use crate::utils::types::Coords;
+use bitflags::bitflags;
+use std::f64::consts::TAU;
+
+bitflags! {
+    /// Passages carved from a polar cell
+    ///
+    /// Only the inward and clockwise passages are stored, the outward and counter-clockwise ones
+    /// are the inward and clockwise passages of the neighboring cells.
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+    pub struct PolarCell: u8 {
+        /// Has passage to the inner ring
+        const INWARD = 0b01;
+        /// Has passage to the next cell of the same ring going clockwise
+        const CLOCKWISE = 0b10;
+    }
+}
+
+/// A grid of cells arranged in concentric rings
+///
+/// Cells are addressed by `(index, ring)`, where ring `0` is the innermost one and the index grows
+/// clockwise within a ring. The number of cells in a ring is always a multiple of the number of
+/// cells in the ring inside it, so that every cell has exactly one inward neighbor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolarGrid {
+    ring_cells: Vec<usize>,
+    offsets: Vec<usize>,
+    cells: Vec<PolarCell>,
+}
+
+impl PolarGrid {
+    /// Returns a grid with a single cell in the center and rings split into a multiple of the
+    /// cells of the inner ring, so their cells stay about as wide as they're tall
+    pub fn new(rings: usize) -> Self {
+        let mut ring_cells = Vec::with_capacity(rings);
+        for ring in 0..rings {
+            let count = ring_cells.last().map_or(1, |&previous: &usize| {
+                let width = TAU * ring as f64 / previous as f64;
+                previous * (width.round() as usize).max(1)
+            });
+            ring_cells.push(count);
+        }
+
+        Self::with_ring_cells(ring_cells)
+    }
+
+    /// Returns a grid with a given number of cells in every ring
+    ///
+    /// The counts must be validated beforehand with [`Self::check_ring_cells`].
+    pub fn with_ring_cells(ring_cells: Vec<usize>) -> Self {
+        let offsets = ring_cells
+            .iter()
+            .scan(0, |offset, &count| {
+                let start = *offset;
+                *offset += count;
+                Some(start)
+            })
+            .collect();
+        let total = ring_cells.iter().sum();
+
+        Self {
+            ring_cells,
+            offsets,
+            cells: vec![PolarCell::empty(); total],
+        }
+    }
+
+    /// Returns a reason why given ring sizes can't form a grid, if any
+    pub fn check_ring_cells(ring_cells: &[usize]) -> Option<String> {
+        if ring_cells.is_empty() {
+            return Some("A theta maze must have at least one ring".to_string());
+        }
+
+        for (ring, &count) in ring_cells.iter().enumerate() {
+            if count == 0 {
+                return Some(format!("Ring {ring} must have at least one cell"));
+            }
+
+            if ring > 0 && count % ring_cells[ring - 1] != 0 {
+                return Some(format!(
+                    "Ring {ring} has {count} cells, which isn't a multiple of the {} cells of ring {}",
+                    ring_cells[ring - 1],
+                    ring - 1
+                ));
+            }
+        }
+
+        None
+    }
+
+    pub const fn rings(&self) -> usize {
+        self.ring_cells.len()
+    }
+
+    pub fn ring_cells(&self, ring: usize) -> usize {
+        self.ring_cells.get(ring).copied().unwrap_or(0)
+    }
+
+    pub const fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn contains(&self, (index, ring): Coords) -> bool {
+        index < self.ring_cells(ring)
+    }
+
+    pub fn cell(&self, (index, ring): Coords) -> PolarCell {
+        self.cells[self.offsets[ring] + index]
+    }
+
+    /// Returns the inward neighbor of a cell, if the cell isn't in the innermost ring
+    pub fn inward(&self, (index, ring): Coords) -> Option<Coords> {
+        (ring > 0).then(|| {
+            let ratio = self.ring_cells[ring] / self.ring_cells[ring - 1];
+            (index / ratio, ring - 1)
+        })
+    }
+
+    /// Returns the outward neighbors of a cell, i.e. the cells of the next ring splitting it
+    pub fn outward(&self, (index, ring): Coords) -> Vec<Coords> {
+        if ring + 1 >= self.rings() {
+            return vec![];
+        }
+
+        let ratio = self.ring_cells[ring + 1] / self.ring_cells[ring];
+        (index * ratio..(index + 1) * ratio)
+            .map(|next| (next, ring + 1))
+            .collect()
+    }
+
+    /// Returns the next cell of the same ring going clockwise, if the ring has more than one cell
+    pub fn clockwise(&self, (index, ring): Coords) -> Option<Coords> {
+        let count = self.ring_cells[ring];
+        (count > 1).then_some(((index + 1) % count, ring))
+    }
+
+    /// Returns the next cell of the same ring going counter-clockwise, if the ring has more than
+    /// one cell
+    pub fn counter_clockwise(&self, (index, ring): Coords) -> Option<Coords> {
+        let count = self.ring_cells[ring];
+        (count > 1).then_some(((index + count - 1) % count, ring))
+    }
+
+    /// Returns all the cells adjacent to a given one
+    pub fn neighbors(&self, coords: Coords) -> Vec<Coords> {
+        let mut neighbors: Vec<Coords> = self.inward(coords).into_iter().collect();
+        neighbors.extend(self.outward(coords));
+        // In a ring of two cells, the clockwise and counter-clockwise neighbors are the same
+        for next in [self.clockwise(coords), self.counter_clockwise(coords)]
+            .into_iter()
+            .flatten()
+        {
+            if !neighbors.contains(&next) {
+                neighbors.push(next);
+            }
+        }
+
+        neighbors
+    }
+
+    /// Returns the cells connected to a given one by carved passages
+    pub fn links(&self, coords: Coords) -> Vec<Coords> {
+        self.neighbors(coords)
+            .into_iter()
+            .filter(|&next| self.is_linked(coords, next))
+            .collect()
+    }
+
+    pub fn is_linked(&self, a: Coords, b: Coords) -> bool {
+        if self.inward(a) == Some(b) {
+            return self.cell(a).contains(PolarCell::INWARD);
+        }
+        if self.inward(b) == Some(a) {
+            return self.cell(b).contains(PolarCell::INWARD);
+        }
+
+        (self.clockwise(a) == Some(b) && self.cell(a).contains(PolarCell::CLOCKWISE))
+            || (self.clockwise(b) == Some(a) && self.cell(b).contains(PolarCell::CLOCKWISE))
+    }
+
+    /// Carves a passage between two adjacent cells
+    ///
+    /// Returns `false` if the cells aren't adjacent.
+    pub fn link(&mut self, a: Coords, b: Coords) -> bool {
+        let (coords, passage) = if self.inward(a) == Some(b) {
+            (a, PolarCell::INWARD)
+        } else if self.inward(b) == Some(a) {
+            (b, PolarCell::INWARD)
+        } else if self.clockwise(a) == Some(b) {
+            (a, PolarCell::CLOCKWISE)
+        } else if self.clockwise(b) == Some(a) {
+            (b, PolarCell::CLOCKWISE)
+        } else {
+            return false;
+        };
+
+        let (index, ring) = coords;
+        self.cells[self.offsets[ring] + index].insert(passage);
+        true
+    }
+
+    /// Returns an iterator over the coords of all the cells, ring by ring
+    pub fn coords(&self) -> impl Iterator<Item = Coords> + '_ {
+        self.ring_cells
+            .iter()
+            .enumerate()
+            .flat_map(|(ring, &count)| (0..count).map(move |index| (index, ring)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rings_split_as_they_grow() {
+        let grid = PolarGrid::new(5);
+        let counts: Vec<usize> = (0..grid.rings())
+            .map(|ring| grid.ring_cells(ring))
+            .collect();
+        assert_eq!(counts, vec![1, 6, 12, 24, 24]);
+        assert_eq!(grid.len(), 67);
+    }
+
+    #[test]
+    fn neighbors_across_a_split() {
+        let grid = PolarGrid::with_ring_cells(vec![1, 4, 8]);
+
+        assert_eq!(grid.neighbors((0, 0)), vec![(0, 1), (1, 1), (2, 1), (3, 1)]);
+        assert_eq!(
+            grid.neighbors((0, 1)),
+            vec![(0, 0), (0, 2), (1, 2), (1, 1), (3, 1)]
+        );
+        assert_eq!(grid.inward((5, 2)), Some((2, 1)));
+        assert_eq!(grid.neighbors((7, 2)), vec![(3, 1), (0, 2), (6, 2)]);
+    }
+
+    #[test]
+    fn link_both_ways() {
+        let mut grid = PolarGrid::with_ring_cells(vec![2, 4]);
+        assert!(grid.link((0, 0), (1, 1)));
+        assert!(grid.link((0, 0), (1, 0)));
+        assert!(grid.link((0, 1), (3, 1)));
+        assert!(!grid.link((0, 0), (2, 1)));
+
+        assert!(grid.is_linked((1, 1), (0, 0)));
+        assert!(grid.is_linked((1, 0), (0, 0)));
+        assert!(grid.is_linked((3, 1), (0, 1)));
+        assert_eq!(grid.neighbors((1, 0)), vec![(2, 1), (3, 1), (0, 0)]);
+        assert_eq!(grid.links((0, 1)), vec![(3, 1)]);
+    }
+
+    #[test]
+    fn invalid_ring_cells() {
+        assert!(PolarGrid::check_ring_cells(&[1, 6, 12]).is_none());
+        assert_eq!(
+            PolarGrid::check_ring_cells(&[1, 6, 8]).unwrap(),
+            "Ring 2 has 8 cells, which isn't a multiple of the 6 cells of ring 1"
+        );
+        assert_eq!(
+            PolarGrid::check_ring_cells(&[]).unwrap(),
+            "A theta maze must have at least one ring"
+        );
+    }
+}
@@ -1,9 +1,10 @@
 #![allow(missing_docs)]
 
-use super::cell::Cell;
+use super::{cell::Cell, direction::Direction};
 use crate::utils::types::Coords;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Topology {
     Orthogonal,
     HexOddR,
@@ -36,12 +37,9 @@ impl Topology {
     #[must_use]
     pub const fn opposite(self, direction: Cell) -> Option<Cell> {
         match self {
-            Self::Orthogonal => match direction {
-                Cell::NORTH => Some(Cell::SOUTH),
-                Cell::SOUTH => Some(Cell::NORTH),
-                Cell::EAST => Some(Cell::WEST),
-                Cell::WEST => Some(Cell::EAST),
-                _ => None,
+            Self::Orthogonal => match Direction::from_cell(direction) {
+                Some(direction) => Some(direction.opposite().to_cell()),
+                None => None,
             },
             Self::HexOddR => match direction {
                 Cell::EAST => Some(Cell::WEST),
@@ -64,12 +62,9 @@ impl Topology {
         height: usize,
     ) -> Option<Coords> {
         match self {
-            Self::Orthogonal => match direction {
-                Cell::NORTH if y > 0 => Some((x, y - 1)),
-                Cell::SOUTH if y + 1 < height => Some((x, y + 1)),
-                Cell::WEST if x > 0 => Some((x - 1, y)),
-                Cell::EAST if x + 1 < width => Some((x + 1, y)),
-                _ => None,
+            Self::Orthogonal => match Direction::from_cell(direction) {
+                Some(direction) => direction.step((x, y), width, height),
+                None => None,
             },
             Self::HexOddR => {
                 let is_odd_row = y % 2 == 1;
@@ -74,7 +74,10 @@ impl HexMazeBuilder {
             StdRng::seed_from_u64,
         );
         if self.start_coords.is_some() && !self.algorithm.has_start_coords() {
-            Err(BuildError::reason(self.algorithm.name()))
+            Err(BuildError::reason(format!(
+                "Algorithm `{}` doesn't support `start_coords`",
+                self.algorithm.name()
+            )))
         } else {
             self.algorithm
                 .generate(maze.get_grid_mut(), self.start_coords, &mut rng);
@@ -118,7 +118,7 @@ impl HexMaze {
                         "Invalid cell token at x={x}, y={y}: `{token}` ({err})"
                     ))
                 })?;
-                grid.cells[y * width + x] = Cell::from_bits_retain(bits);
+                grid.cells.set(y * width + x, Cell::from_bits_retain(bits));
             }
         }
 
@@ -185,7 +185,7 @@ impl<'a> Iterator for HexMazeIterator<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::maze::HexMazeBuilder;
+    use crate::maze::{HexMazeBuilder, algorithms::Prim};
 
     use super::HexMaze;
 
@@ -220,4 +220,32 @@ mod tests {
             "Cannot save maze to file. Reason: Invalid hex maze header"
         );
     }
+
+    #[test]
+    fn prim_builds_valid_hex_mazes() {
+        for seed in 0..5 {
+            let maze = HexMazeBuilder::new()
+                .width(7)
+                .height(6)
+                .seed(seed)
+                .algorithm(Box::new(Prim::new()))
+                .build()
+                .unwrap();
+            assert!(maze.is_valid());
+        }
+    }
+
+    #[test]
+    fn weighted_prim_builds_valid_hex_mazes() {
+        for seed in 0..5 {
+            let maze = HexMazeBuilder::new()
+                .width(7)
+                .height(6)
+                .seed(seed)
+                .algorithm(Box::new(Prim::weighted()))
+                .build()
+                .unwrap();
+            assert!(maze.is_valid());
+        }
+    }
 }
@@ -0,0 +1,238 @@
+use std::collections::VecDeque;
+
+use crate::utils::types::Coords;
+
+/// A mask of the cells that take part in a maze
+///
+/// Masked-off cells are excluded from generation, which allows generating mazes in non-rectangular
+/// shapes. All active cells must be 4-connected, otherwise some of them could never be reached.
+///
+/// # Example
+/// ```
+/// use bevy_knossos::maze::*;
+///
+/// let mut mask = Mask::new(5, 5);
+/// mask.set_active((2, 2), false);
+///
+/// let maze = OrthogonalMazeBuilder::new()
+///     .width(5)
+///     .height(5)
+///     .mask(mask)
+///     .build()
+///     .unwrap();
+/// assert!(maze.is_valid());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mask {
+    width: usize,
+    height: usize,
+    cells: Vec<bool>,
+}
+
+impl Mask {
+    /// Returns a new mask of a given size with all cells active
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![true; width * height],
+        }
+    }
+
+    /// Returns a new mask of a given size where active and masked-off cells alternate like on a
+    /// checkerboard, starting with an active top-left cell
+    ///
+    /// Since no two active cells of a checkerboard are neighbors, such a mask is disconnected
+    /// and can't be used to build a maze on its own. It's rather a base to adjust.
+    #[must_use]
+    pub fn checkerboard(width: usize, height: usize) -> Self {
+        let cells = (0..width * height)
+            .map(|index| (index % width + index / width).is_multiple_of(2))
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Returns a new mask drawn with ASCII characters, where `#` marks an active cell and any
+    /// other character marks a masked-off one
+    ///
+    /// Every line is a row of the mask, and leading spaces are masked-off cells like any other
+    /// character, so the rows stay aligned. Trailing whitespace is ignored, as well as blank lines
+    /// before the first row and after the last one. The mask is as wide as the longest line, so
+    /// shorter lines are padded with masked-off cells.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_knossos::maze::*;
+    ///
+    /// let mask = Mask::from_ascii(" ##\n####\n ##");
+    /// assert_eq!((mask.width(), mask.height()), (4, 3));
+    /// assert!(!mask.is_active((0, 0)));
+    /// assert!(mask.is_active((0, 1)));
+    /// assert!(!mask.is_active((3, 2)));
+    /// ```
+    #[must_use]
+    pub fn from_ascii(ascii: &str) -> Self {
+        let lines: Vec<&str> = ascii.lines().map(str::trim_end).collect();
+        let first = lines
+            .iter()
+            .position(|line| !line.is_empty())
+            .unwrap_or(lines.len());
+        let last = lines
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(first, |last| last + 1);
+        let rows = &lines[first..last];
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut mask = Self::new(width, rows.len());
+        mask.cells.fill(false);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                mask.set_active((x, y), c == '#');
+            }
+        }
+        mask
+    }
+
+    /// Returns the mask width
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the mask height
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if a cell takes part in a maze. Cells outside the mask are never active
+    #[must_use]
+    pub fn is_active(&self, (x, y): Coords) -> bool {
+        x < self.width && y < self.height && self.cells[y * self.width + x]
+    }
+
+    /// Activates or masks off a cell. Coords outside the mask are ignored
+    pub fn set_active(&mut self, (x, y): Coords, active: bool) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = active;
+        }
+    }
+
+    /// Returns the number of active cells
+    #[must_use]
+    pub fn active_count(&self) -> usize {
+        self.cells.iter().filter(|active| **active).count()
+    }
+
+    /// Returns `true` if there is at least one active cell and every active cell can be reached
+    /// from any other one through active neighbors
+    #[must_use]
+    pub fn is_connected(&self) -> bool {
+        let Some(start) = self.cells.iter().position(|active| *active) else {
+            return false;
+        };
+
+        let mut visited = vec![false; self.cells.len()];
+        let mut queue = VecDeque::from([(start % self.width, start / self.width)]);
+        let mut reached = 0;
+        visited[start] = true;
+
+        while let Some((x, y)) = queue.pop_front() {
+            reached += 1;
+
+            let neighbors = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbors {
+                if self.is_active((nx, ny)) && !visited[ny * self.width + nx] {
+                    visited[ny * self.width + nx] = true;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+
+        reached == self.active_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_mask_is_active() {
+        let mask = Mask::new(3, 2);
+        assert_eq!(mask.active_count(), 6);
+        assert!(mask.is_active((2, 1)));
+        assert!(!mask.is_active((3, 1)));
+        assert!(mask.is_connected());
+    }
+
+    #[test]
+    fn checkerboard_alternates() {
+        let mask = Mask::checkerboard(3, 3);
+
+        assert_eq!(mask.active_count(), 5);
+        assert!(mask.is_active((0, 0)));
+        assert!(!mask.is_active((1, 0)));
+        assert!(!mask.is_active((0, 1)));
+        assert!(mask.is_active((1, 1)));
+        assert!(!mask.is_connected());
+    }
+
+    #[test]
+    fn from_ascii() {
+        let mask = Mask::from_ascii(
+            "#.#\n\
+             ###\n\
+             #",
+        );
+
+        assert_eq!((mask.width(), mask.height()), (3, 3));
+        assert_eq!(mask.active_count(), 6);
+        assert!(!mask.is_active((1, 0)));
+        assert!(mask.is_active((0, 2)));
+        assert!(!mask.is_active((1, 2)));
+        assert!(mask.is_connected());
+    }
+
+    #[test]
+    fn from_ascii_keeps_leading_spaces() {
+        let mask = Mask::from_ascii("\n  #\r\n\n # \n#  \n\n");
+
+        assert_eq!((mask.width(), mask.height()), (3, 4));
+        assert_eq!(mask.active_count(), 3);
+        assert!(mask.is_active((2, 0)));
+        assert_eq!(mask.cells[3..6], [false; 3]);
+        assert!(mask.is_active((1, 2)));
+        assert!(mask.is_active((0, 3)));
+    }
+
+    #[test]
+    fn connectivity() {
+        let mut mask = Mask::new(3, 3);
+        mask.set_active((1, 0), false);
+        mask.set_active((1, 1), false);
+        assert!(mask.is_connected());
+
+        mask.set_active((1, 2), false);
+        assert!(!mask.is_connected());
+
+        assert!(Mask::checkerboard(1, 1).is_connected());
+        assert!(!Mask::new(0, 0).is_connected());
+    }
+}
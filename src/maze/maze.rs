@@ -1,20 +1,37 @@
+use ::image::RgbImage;
 use bevy::ecs::resource::Resource;
+use rand::prelude::*;
 
 use crate::utils::types::Coords;
 
 use super::{
-    errors::MazeSaveError,
-    formatters::{Formatter, Saveable},
-    grid::{Grid, cell::Cell},
+    algorithms::Algorithm,
+    border::Border,
+    errors::{BuildError, MazeDiffError, MazeLoadError, MazeSaveError, PathError, TransitError},
+    formatters::{Block, Formatter, Image, RenderScale, Saveable, block_at},
+    grid::{Grid, cell::Cell, cells::CellMut, direction::Direction, overpass::Overpass},
+    mask::Mask,
+    stats::MazeStats,
     validate::validate,
 };
-use std::fmt;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+/// Magic bytes at the start of a maze encoded with [`OrthogonalMaze::to_bytes`]
+const BYTES_MAGIC: [u8; 4] = *b"KNOS";
+/// Current version of the binary maze format
+const BYTES_VERSION: u8 = 1;
+/// Length of the magic bytes, the version, the width and the height
+const BYTES_HEADER_LEN: usize = BYTES_MAGIC.len() + 1 + 4 + 4;
 
 /// An orthogonal maze
 ///
 /// Represents a standard orthogonal maze where each cell is a square containing zero or maximum
 /// three walls
 #[derive(Debug, Clone, PartialEq, Eq, Resource)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrthogonalMaze {
     grid: Grid,
 }
@@ -28,17 +45,243 @@ impl OrthogonalMaze {
         }
     }
 
+    /// Returns a maze loaded from the JSON emitted by the [`Json`](super::Json) formatter
+    ///
+    /// # Errors
+    /// Returns a [`MazeLoadError`] if the JSON is malformed, the maze is too large, a cell
+    /// coordinate is missing, out of bounds or present more than once, or the walls of
+    /// neighboring cells don't match
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, MazeLoadError> {
+        super::formatters::parse_grid(json).map(|grid| Self { grid })
+    }
+
+    /// Returns the maze encoded as a compact binary blob
+    ///
+    /// The blob starts with the `KNOS` magic bytes and a format version, followed by the width and
+    /// the height as little-endian `u32`s and a single byte of open passages per cell in
+    /// row-major order. It can be loaded back with [`from_bytes`](Self::from_bytes).
+    ///
+    /// Only the passages are encoded, so masks and crossings of weave mazes can't be stored in
+    /// the blob.
+    ///
+    /// # Errors
+    /// Returns a [`MazeSaveError`] if the width or the height doesn't fit a `u32`, or the maze is
+    /// masked or has crossings
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MazeSaveError> {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        let (Ok(w), Ok(h)) = (u32::try_from(width), u32::try_from(height)) else {
+            return Err(MazeSaveError::reason(format!(
+                "A {width}x{height} maze is too large to be encoded as bytes"
+            )));
+        };
+        if self.grid.is_masked() {
+            return Err(MazeSaveError::reason(
+                "A masked maze can't be encoded as bytes",
+            ));
+        }
+        if self.grid.has_overpasses() {
+            return Err(MazeSaveError::reason(
+                "A weave maze with crossings can't be encoded as bytes",
+            ));
+        }
+
+        let mut bytes = Vec::with_capacity(BYTES_HEADER_LEN + self.grid.cells.len());
+        bytes.extend_from_slice(&BYTES_MAGIC);
+        bytes.push(BYTES_VERSION);
+        bytes.extend_from_slice(&w.to_le_bytes());
+        bytes.extend_from_slice(&h.to_le_bytes());
+        bytes.extend(self.iter().map(|(_, cell)| cell.to_bits()));
+        Ok(bytes)
+    }
+
+    /// Returns a maze loaded from the binary blob emitted by [`to_bytes`](Self::to_bytes)
+    ///
+    /// # Errors
+    /// Returns a [`MazeLoadError`] if the blob has an unknown header or
+    /// version, is truncated or too long, contains a cell with passages other than the orthogonal
+    /// ones, or the walls of neighboring cells don't match
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MazeLoadError> {
+        let Some((header, cells)) = bytes.split_first_chunk::<BYTES_HEADER_LEN>() else {
+            return Err(MazeLoadError::reason("Maze bytes are truncated"));
+        };
+        let [magic @ .., version, w0, w1, w2, w3, h0, h1, h2, h3] = *header;
+        if magic != BYTES_MAGIC {
+            return Err(MazeLoadError::reason("Maze bytes have an unknown header"));
+        }
+        if version != BYTES_VERSION {
+            return Err(MazeLoadError::reason(format!(
+                "Maze bytes have an unsupported version {version}"
+            )));
+        }
+
+        let width = u32::from_le_bytes([w0, w1, w2, w3]) as usize;
+        let height = u32::from_le_bytes([h0, h1, h2, h3]) as usize;
+        if width.checked_mul(height) != Some(cells.len()) {
+            return Err(MazeLoadError::reason(format!(
+                "Maze bytes must contain {width}x{height} cells, but contain {} bytes of cells",
+                cells.len()
+            )));
+        }
+
+        let cells = cells
+            .iter()
+            .enumerate()
+            .map(|(idx, bits)| {
+                let coords = (idx % width, idx / width);
+                let orthogonal = Cell::NORTH | Cell::SOUTH | Cell::EAST | Cell::WEST;
+                Cell::from_bits(*bits)
+                    .filter(|cell| orthogonal.contains(*cell))
+                    .ok_or_else(|| {
+                        MazeLoadError::reason(format!(
+                            "Cell {coords:?} has invalid passages {bits:#010b}"
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The length check above already bounds the maze by the size of the blob, so unlike the
+        // text formats it isn't limited to a number of cells
+        super::formatters::grid_from_row_major(width, height, &cells).map(|grid| Self { grid })
+    }
+
+    /// Attaches default sizes for the [`Image`](super::Image), [`Svg`](super::Svg) and
+    /// [`GameMap`](super::GameMap) formatters and returns itself
+    ///
+    /// Sizes set on a formatter explicitly take precedence over the scale. The scale is only a
+    /// rendering setting, so it's ignored when comparing mazes.
+    #[must_use]
+    pub const fn with_render_scale(mut self, scale: RenderScale) -> Self {
+        self.grid.set_render_scale(scale);
+        self
+    }
+
+    /// Returns the default formatter sizes attached to the maze, if any
+    #[must_use]
+    pub const fn render_scale(&self) -> Option<RenderScale> {
+        self.grid.render_scale()
+    }
+
+    /// Returns the maze width, i.e. the number of cells in a row
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    /// Returns the maze height, i.e. the number of rows
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// Returns the width and the height of the maze
+    #[must_use]
+    pub const fn dimensions(&self) -> (usize, usize) {
+        (self.grid.width(), self.grid.height())
+    }
+
+    /// Returns a new maze over a given grid
+    pub(crate) const fn from_grid(grid: Grid) -> Self {
+        Self { grid }
+    }
+
+    /// Returns a ref to a grid
+    pub(crate) const fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
     /// Returns a mutable ref to a grid
     pub const fn get_grid_mut(&mut self) -> &mut Grid {
         &mut self.grid
     }
 
+    /// Opens an entrance in the outer wall of the cell at a given offset along a given side and
+    /// returns the coords of that cell
+    ///
+    /// The offset is counted from the West for the northern and southern sides and from the North
+    /// for the eastern and western ones. Returns `None` if the offset is past the end of the side
+    /// or the cell is masked off.
+    ///
+    /// The [`Image`](super::Image), [`Svg`](super::Svg), [`AsciiNarrow`](super::AsciiNarrow),
+    /// [`AsciiBroad`](super::AsciiBroad) and [`LevelText`](super::LevelText) formatters render the
+    /// opening as a gap in the outer wall.
+    pub fn open_entrance(&mut self, side: Border, offset: usize) -> Option<Coords> {
+        let coords = side.cell(offset, self.grid.width(), self.grid.height())?;
+        self.grid
+            .open_border(coords, side.direction())
+            .then_some(coords)
+    }
+
+    /// Opens an exit in the outer wall of the cell at a given offset along a given side and
+    /// returns the coords of that cell
+    ///
+    /// Works the same way as [`open_entrance`](Self::open_entrance).
+    pub fn open_exit(&mut self, side: Border, offset: usize) -> Option<Coords> {
+        self.open_entrance(side, offset)
+    }
+
     /// Returns `true` if a maze is valid. Otherwise, returns `false`
+    ///
+    /// A maze is valid when it consists of a single [connected component](Self::connected_components).
     #[must_use]
     pub fn is_valid(&self) -> bool {
         validate(&self.grid)
     }
 
+    /// Returns the number of groups of cells connected by carved passages
+    ///
+    /// A valid maze has a single component, while edited or masked mazes may fall apart into
+    /// several ones. Masked-off cells are not counted.
+    #[must_use]
+    pub fn connected_components(&self) -> usize {
+        self.grid.components().0
+    }
+
+    /// Returns the number of independent loops in the maze, i.e. its circuit rank
+    ///
+    /// It's computed as `passages - cells + components` over the carved passages, so a perfect
+    /// maze, which is a tree, has none, while every passage added by [`braid`](Self::braid)
+    /// between cells already connected adds one. Masked-off cells are not counted.
+    #[must_use]
+    pub fn cycle_count(&self) -> usize {
+        let (components, _) = self.grid.components();
+        let passages = self
+            .iter()
+            .filter(|(coords, _)| self.grid.is_active(*coords))
+            .map(|((x, y), _)| {
+                // Each passage is counted once, from the cell on its west or north side
+                [
+                    ((x + 1, y), Cell::EAST, Cell::WEST),
+                    ((x, y + 1), Cell::SOUTH, Cell::NORTH),
+                ]
+                .into_iter()
+                .filter(|(next, direction, back)| {
+                    self.grid.is_carved((x, y), *direction)
+                        && self.grid.is_active(*next)
+                        && self.grid.is_carved(*next, *back)
+                })
+                .count()
+            })
+            .sum::<usize>();
+
+        (passages + components).saturating_sub(self.grid.active_count())
+    }
+
+    /// Returns the index of the connected component of every cell
+    ///
+    /// Components are numbered from `0` in the row-major order of their first cell. Masked-off
+    /// cells are left out.
+    #[must_use]
+    pub fn component_map(&self) -> HashMap<Coords, usize> {
+        let width = self.grid.width();
+        let (_, labels) = self.grid.components();
+        labels
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, label)| Some(((index % width, index / width), label?)))
+            .collect()
+    }
+
     /// Saves the maze data to a file at the specified path using the provided formatter.
     ///
     /// This method converts the internal grid representation of the maze into a format
@@ -76,6 +319,25 @@ impl OrthogonalMaze {
         }
     }
 
+    /// Returns the cell at given coords, or `None` if the coords are out of the maze
+    ///
+    /// Unlike indexing, it doesn't panic, so it's handy when looking around the cells near the
+    /// borders of the maze.
+    #[must_use]
+    pub fn get(&self, coords: Coords) -> Option<&Cell> {
+        self.grid.get(coords)
+    }
+
+    /// Returns a mutable reference to the cell at given coords, or `None` if the coords are out of
+    /// the maze
+    ///
+    /// The changes are stored in the maze once the returned [`CellMut`] is dropped. Only the given
+    /// cell is changed, so opening a passage this way leaves the wall of the neighboring cell in
+    /// place, unless it's opened as well.
+    pub fn get_mut(&mut self, coords: Coords) -> Option<CellMut<'_>> {
+        self.grid.get_mut(coords)
+    }
+
     /// Returns all cells that have 3 walls, means maze ends.
     #[must_use]
     pub fn ends(&self) -> Vec<((usize, usize), &Cell)> {
@@ -84,221 +346,1996 @@ impl OrthogonalMaze {
             .collect()
     }
 
-    /// Returns a formatted maze using the provided formatter.
-    ///
-    /// This method generates a formatted representation of the maze grid by applying
-    /// the specified formatter, which converts the internal maze structure into the
-    /// desired format `T`. This formatted result can then be further processed or saved
-    /// elsewhere.
-    ///
-    /// # Example
-    /// ```rust
-    /// use bevy_knossos::maze::*;
-    ///
-    /// let maze = OrthogonalMaze::new(5, 5);
-    /// let formatted_maze = maze.format(GameMap::new());
-    /// ```
-    ///
-    /// # Note
-    /// This function can be useful when the formatted maze needs to be used for further processing,
-    /// logging, or display.
-    pub fn format<F, T>(&self, formatter: F) -> T
-    where
-        F: Formatter<T>,
-        T: Saveable,
-    {
-        formatter.format(&self.grid)
+    /// Returns all cells that have at most 1 wall, means maze junctions.
+    #[must_use]
+    pub fn junctions(&self) -> Vec<((usize, usize), &Cell)> {
+        self.iter()
+            .filter(|maze_cell| maze_cell.1.walls_count_sq() <= 1)
+            .collect()
     }
-}
 
-impl std::ops::Index<Coords> for OrthogonalMaze {
-    type Output = Cell;
+    /// Returns all cells that have exactly 2 opposite passages, means straight maze corridors.
+    #[must_use]
+    pub fn corridors(&self) -> Vec<((usize, usize), &Cell)> {
+        self.iter()
+            .filter(|maze_cell| {
+                *maze_cell.1 == Cell::NORTH | Cell::SOUTH || *maze_cell.1 == Cell::EAST | Cell::WEST
+            })
+            .collect()
+    }
 
-    fn index(&self, index: Coords) -> &Self::Output {
-        &self.grid[index]
+    /// Removes a given fraction of dead ends by carving an extra passage from each of them into a
+    /// random neighbor, which adds loops to the maze.
+    ///
+    /// A `ratio` of `0.0` keeps the maze intact, while `1.0` removes every dead end that has a
+    /// neighbor to connect to. Neighbors that are dead ends themselves are preferred, so a single
+    /// passage may remove two dead ends at once.
+    pub fn braid(&mut self, rng: &mut StdRng, ratio: f64) {
+        let ratio = ratio.clamp(0.0, 1.0);
+        let dead_ends: Vec<Coords> = self.ends().into_iter().map(|(coords, _)| coords).collect();
+
+        for coords in dead_ends {
+            // A previously carved passage may have already removed this dead end
+            if !self.grid[coords].is_end_sq() || !rng.random_bool(ratio) {
+                continue;
+            }
+
+            let mut candidates: Vec<(Cell, Coords)> = self
+                .grid
+                .neighbor_coords(coords)
+                .into_iter()
+                .filter(|(dir, _)| !self.grid.is_carved(coords, *dir))
+                .collect();
+            candidates.shuffle(rng);
+
+            let candidate = candidates
+                .iter()
+                .find(|(_, next)| self.grid[*next].is_end_sq())
+                .or_else(|| candidates.first());
+            if let Some((dir, _)) = candidate {
+                let _ = self.grid.carve_passage(coords, *dir);
+            }
+        }
     }
-}
 
-impl fmt::Display for OrthogonalMaze {
-    /// Writes a formatted maze into a buffer
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.grid)?;
-        Ok(())
+    /// Carves a passage from a cell to its neighbor in a given direction and returns the coords of
+    /// the neighbor
+    ///
+    /// Both cells get the passage, so the maze stays consistent. It comes in handy for
+    /// hand-editing a generated maze, e.g. opening a shortcut.
+    ///
+    /// # Errors
+    /// Returns a [`TransitError`] if the cell is out of the maze or masked off, or if the direction
+    /// leads out of the maze or into a masked off cell
+    pub fn carve(&mut self, from: Coords, direction: Cell) -> Result<Coords, TransitError> {
+        self.check_active(from)?;
+        self.grid.carve_passage(from, direction)
     }
-}
 
-pub struct OrthogonalMazeIterator<'a> {
-    maze: &'a OrthogonalMaze,
-    index: usize,
-}
+    /// Closes a passage from a cell to its neighbor in a given direction and returns the coords of
+    /// the neighbor
+    ///
+    /// Walls are restored on both sides of the passage. Filling a wall that is already in place
+    /// leaves the maze unchanged.
+    ///
+    /// # Errors
+    /// Returns a [`TransitError`] for the same reasons as [`carve`](Self::carve)
+    pub fn fill_wall(&mut self, from: Coords, direction: Cell) -> Result<Coords, TransitError> {
+        self.check_active(from)?;
+        self.grid.fill_wall(from, direction)
+    }
 
-impl<'a> Iterator for OrthogonalMazeIterator<'a> {
-    type Item = (Coords, &'a Cell);
-    fn next(&mut self) -> Option<Self::Item> {
-        let width = self.maze.grid.width();
-        if self.index < self.maze.grid.cells.len() {
-            let result = Some((
-                (self.index % width, self.index / width),
-                &self.maze.grid.cells[self.index],
-            ));
-            self.index += 1;
-            result
+    fn check_active(&self, coords: Coords) -> Result<(), TransitError> {
+        if self.grid.is_active(coords) {
+            Ok(())
         } else {
-            None
+            Err(TransitError::reason(
+                "The cell is out of the maze or masked off",
+                coords,
+            ))
         }
     }
-}
 
-pub struct OrthogonalMazeIntoIterator {
-    maze: OrthogonalMaze,
-    index: usize,
-    width: usize,
-}
+    /// Returns the corridor on top if a cell is a crossing of a weave maze
+    #[must_use]
+    pub fn overpass(&self, coords: Coords) -> Option<Overpass> {
+        self.grid.overpass(coords)
+    }
 
-impl IntoIterator for OrthogonalMaze {
-    type Item = (Coords, Cell);
-    type IntoIter = OrthogonalMazeIntoIterator;
+    /// Returns the number of cells reachable from the `start` cell, `start` included.
+    #[must_use]
+    pub fn reachable_count(&self, start: Coords) -> usize {
+        let mut count = 0;
+        self.grid.flood_fill(start, |_, _| count += 1);
+        count
+    }
 
-    fn into_iter(self) -> OrthogonalMazeIntoIterator {
-        OrthogonalMazeIntoIterator {
-            width: self.grid.width(),
-            maze: self,
-            index: 0,
+    /// Returns the coords of all cells grouped by their wall configuration, i.e. by the value of
+    /// [`Cell::to_bits`].
+    ///
+    /// Only the configurations present in the maze are included.
+    #[must_use]
+    pub fn cells_by_config(&self) -> HashMap<u8, Vec<Coords>> {
+        let mut groups: HashMap<u8, Vec<Coords>> = HashMap::new();
+        for (coords, cell) in self.iter() {
+            groups.entry(cell.to_bits()).or_default().push(coords);
         }
+        groups
     }
-}
 
-impl Iterator for OrthogonalMazeIntoIterator {
-    type Item = (Coords, Cell);
+    /// Returns the number of cells that participate in the maze.
+    ///
+    /// Cells masked off with a [`Mask`] are not counted, so without a mask this is the number of
+    /// cells of the grid. Useful as a denominator for statistics of mazes that don't fill their
+    /// whole grid.
+    #[must_use]
+    pub fn active_cell_count(&self) -> usize {
+        self.iter()
+            .filter(|(coords, _)| self.grid.is_active(*coords))
+            .count()
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let width = self.width;
-        if self.maze.grid.cells.is_empty() {
-            return None;
+    /// Returns the number of cells with 0, 1, 2, 3 and 4 open passages.
+    ///
+    /// The value at index 1 is the number of dead ends.
+    #[must_use]
+    pub fn degree_histogram(&self) -> [usize; 5] {
+        let mut histogram = [0; 5];
+        for (_, cell) in self.iter() {
+            histogram[usize::from(4 - cell.walls_count_sq())] += 1;
         }
-        let cell = self.maze.get_grid_mut().cells.remove(0);
-        let coords = (self.index % width, self.index / width);
-        self.index += 1;
-
-        Some((coords, cell))
+        histogram
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::maze::grid::cell::Cell;
 
-    use super::*;
+    /// Returns the number of dead ends, junctions, straight corridors and turns in the maze
+    ///
+    /// Openings in the outer wall count as passages.
+    #[must_use]
+    pub fn stats(&self) -> MazeStats {
+        let mut stats = MazeStats::default();
+        for (_, cell) in self.iter() {
+            stats.add(*cell);
+        }
+        stats
+    }
 
-    #[test]
-    fn iterators_have_size() {
-        let grid = generate_valid_maze();
-        let maze = OrthogonalMaze { grid };
+    /// Returns the cells whose walls differ from the same cells of another maze
+    ///
+    /// Each entry holds the coords, the cell of this maze and the cell of the other maze. Entries
+    /// are ordered row by row.
+    ///
+    /// # Errors
+    /// Returns a [`MazeDiffError`] if the mazes have different dimensions
+    pub fn diff(&self, other: &Self) -> Result<Vec<(Coords, Cell, Cell)>, MazeDiffError> {
+        if self.dimensions() != other.dimensions() {
+            return Err(MazeDiffError {
+                dimensions: self.dimensions(),
+                other_dimensions: other.dimensions(),
+            });
+        }
 
-        let iter_count = maze.iter().count();
+        Ok(self
+            .iter()
+            .zip(other.iter())
+            .filter(|((_, cell), (_, other_cell))| cell != other_cell)
+            .map(|((coords, cell), (_, other_cell))| (coords, *cell, *other_cell))
+            .collect())
+    }
 
-        assert_eq!(iter_count, 16);
-        assert_eq!(
-            maze.iter().nth(5).unwrap(),
-            ((1usize, 1usize), &(Cell::SOUTH | Cell::EAST | Cell::WEST))
-        );
+    /// Returns the number of dead ends (cells with 3 walls) reachable from the `start` cell.
+    ///
+    /// In a valid maze this is the same as the number of [`ends`](Self::ends), but it may be
+    /// fewer when some parts of the grid are not connected to `start`.
+    #[must_use]
+    pub fn reachable_dead_ends(&self, start: Coords) -> usize {
+        self.grid
+            .reachable_cells(start)
+            .into_iter()
+            .filter(|coords| self.grid[*coords].is_end_sq())
+            .count()
     }
 
-    #[test]
-    fn into_iterators_have_size() {
-        let grid = generate_valid_maze();
-        let maze = OrthogonalMaze { grid };
+    /// Returns the shortest path from `start` to `goal`, both included, or `None` if `goal` cannot
+    /// be reached.
+    #[must_use]
+    pub fn solve(&self, start: Coords, goal: Coords) -> Option<Vec<Coords>> {
+        self.grid.shortest_path(start, goal)
+    }
 
-        let iter_count = maze.clone().into_iter().count();
+    /// Checks that a path is a legal walk through the maze
+    ///
+    /// Every cell of the path must lie within the maze and each pair of consecutive cells must be
+    /// neighbors connected by a carved passage. A path with a single cell is valid.
+    ///
+    /// # Errors
+    /// Returns a [`PathError`] describing the first illegal step of the path
+    pub fn is_valid_path(&self, path: &[Coords]) -> Result<(), PathError> {
+        let Some(&first) = path.first() else {
+            return Err(PathError::Empty);
+        };
+        if !self.grid.contains(first) {
+            return Err(PathError::OutOfBounds {
+                step: 0,
+                coords: first,
+            });
+        }
 
-        assert_eq!(iter_count, 16);
-        assert_eq!(
-            maze.into_iter().nth(5).unwrap(),
-            ((1usize, 1usize), (Cell::SOUTH | Cell::EAST | Cell::WEST))
-        );
-    }
+        for (index, pair) in path.windows(2).enumerate() {
+            let (from, to) = (pair[0], pair[1]);
+            let step = index + 1;
+            if !self.grid.contains(to) {
+                return Err(PathError::OutOfBounds { step, coords: to });
+            }
 
-    #[test]
-    fn display_orthogonal_maze() {
-        let mut expected = String::new();
-        expected.push_str(" _______ \n");
-        expected.push_str("| |___  |\n");
-        expected.push_str("|_   _| |\n");
-        expected.push_str("|  _____|\n");
-        expected.push_str("|_______|\n");
+            let Some((direction, _)) = self
+                .grid
+                .neighbor_coords(from)
+                .into_iter()
+                .find(|(_, next)| *next == to)
+            else {
+                return Err(PathError::NotAdjacent { step, from, to });
+            };
+            if !self.grid.is_carved(from, direction) {
+                return Err(PathError::Blocked { step, from, to });
+            }
+        }
 
-        let grid = generate_valid_maze();
-        let maze = OrthogonalMaze { grid };
-        let actual = maze.to_string();
+        Ok(())
+    }
 
-        assert_eq!(actual, expected);
+    /// Returns `true` if `to` can be reached from `from` through carved passages
+    ///
+    /// Unlike [`is_valid`](Self::is_valid), which requires the whole maze to be connected, it
+    /// only checks a pair of cells, so it stays useful after masking or editing a maze. Cells out
+    /// of the maze or masked off are never reachable.
+    #[must_use]
+    pub fn is_reachable(&self, from: Coords, to: Coords) -> bool {
+        self.grid.is_reachable(from, to)
     }
 
-    #[test]
-    fn valid_maze() {
-        let grid = generate_valid_maze();
-        let maze = OrthogonalMaze { grid };
-        assert!(maze.is_valid());
+    /// Returns the number of steps on the shortest path between two cells, or `None` if they are
+    /// not connected.
+    ///
+    /// Unlike [`solve`](Self::solve), this method doesn't allocate the path itself.
+    #[must_use]
+    pub fn distance_between(&self, a: Coords, b: Coords) -> Option<u32> {
+        self.grid.distance(a, b)
     }
 
-    #[test]
-    fn invalid_maze() {
+    /// Returns the ratio of the shortest path distance from `start` to the Euclidean distance for
+    /// every cell reachable from `start`
+    ///
+    /// High values flag cells that are close to `start`, but can only be reached through a long
+    /// detour. The `start` cell itself is not included.
+    #[must_use]
+    pub fn detour_factor(&self, start: Coords) -> HashMap<Coords, f64> {
+        let mut factors = HashMap::new();
+        self.grid.flood_fill(start, |(x, y), distance| {
+            if (x, y) == start {
+                return;
+            }
+            let dx = x.abs_diff(start.0) as f64;
+            let dy = y.abs_diff(start.1) as f64;
+            factors.insert((x, y), f64::from(distance) / dx.hypot(dy));
+        });
+        factors
+    }
+
+    /// Returns the ratio of the longest to the shortest solution length across `entrances` leading
+    /// to `goal`
+    ///
+    /// `1.0` means every entrance is equally far from the goal, which helps to balance races on
+    /// maps with multiple starts. Returns [`f64::INFINITY`] if an entrance can't reach the goal or
+    /// only some entrances coincide with it, and `1.0` if there are no entrances.
+    #[must_use]
+    pub fn entrance_fairness(&self, entrances: &[Coords], goal: Coords) -> f64 {
+        let mut distances = HashMap::new();
+        self.grid.flood_fill(goal, |coords, distance| {
+            distances.insert(coords, distance);
+        });
+
+        let mut lengths = Vec::with_capacity(entrances.len());
+        for entrance in entrances {
+            let Some(length) = distances.get(entrance) else {
+                return f64::INFINITY;
+            };
+            lengths.push(*length);
+        }
+
+        let (Some(min), Some(max)) = (lengths.iter().min(), lengths.iter().max()) else {
+            return 1.0;
+        };
+        match (*min, *max) {
+            (_, 0) => 1.0,
+            (0, _) => f64::INFINITY,
+            (min, max) => f64::from(max) / f64::from(min),
+        }
+    }
+
+    /// Returns all cells orthogonally adjacent to any cell of `path`, excluding the path cells
+    ///
+    /// Walls are ignored, so the result flanks the path like the sides of a corridor, which is
+    /// handy for placing guards or scenery along a route.
+    #[must_use]
+    pub fn path_neighbors(&self, path: &[Coords]) -> HashSet<Coords> {
+        let on_path: HashSet<&Coords> = path.iter().collect();
+        path.iter()
+            .flat_map(|coords| self.grid.neighbor_coords(*coords))
+            .map(|(_, next)| next)
+            .filter(|next| !on_path.contains(next))
+            .collect()
+    }
+
+    /// Returns a new maze containing a copy of the `width` x `height` rectangle that starts at the
+    /// `top_left` cell.
+    ///
+    /// Passages crossing the cut boundary are sealed, so the resulting maze is self-contained. The
+    /// rectangle is clamped to the bounds of this maze.
+    #[must_use]
+    pub fn sub_maze(&self, top_left: Coords, width: usize, height: usize) -> Self {
+        let (left, top) = top_left;
+        let width = width.min(self.grid.width().saturating_sub(left));
+        let height = height.min(self.grid.height().saturating_sub(top));
+        let mut maze = Self::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let mut cell = self.grid[(left + x, top + y)];
+                if y == 0 {
+                    cell.remove(Cell::NORTH);
+                }
+                if y + 1 == height {
+                    cell.remove(Cell::SOUTH);
+                }
+                if x == 0 {
+                    cell.remove(Cell::WEST);
+                }
+                if x + 1 == width {
+                    cell.remove(Cell::EAST);
+                }
+                maze.grid.cells.set(y * width + x, cell);
+            }
+        }
+
+        maze
+    }
+
+    /// Generates a maze with a given algorithm inside the region from the `top_left` cell to the
+    /// `bottom_right` cell inclusive, leaving the rest of the maze untouched
+    ///
+    /// The region is generated as a separate maze of its own size, so the algorithm can't carve
+    /// outside of it, and then copied over the cells of the region, replacing their crossings too.
+    /// Passages that already cross the region border are kept, since they belong to the cells
+    /// outside of it too. This allows
+    /// composing several mazes side by side and joining them afterwards.
+    ///
+    /// # Errors
+    /// Returns a [`BuildError`] if the region doesn't fit the maze or contains masked-off cells
+    /// while the algorithm doesn't support masks
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_knossos::maze::*;
+    /// use rand::{SeedableRng, rngs::StdRng};
+    ///
+    /// let mut rng = StdRng::seed_from_u64(1);
+    /// let mut maze = OrthogonalMaze::new(8, 4);
+    /// maze.generate_region(&mut RecursiveBacktracking, ((0, 0), (3, 3)), &mut rng).unwrap();
+    /// maze.generate_region(&mut BinaryTree::new(Bias::NorthEast), ((4, 0), (7, 3)), &mut rng).unwrap();
+    /// maze.get_grid_mut().carve_passage((3, 0), Cell::EAST).unwrap();
+    /// assert!(maze.is_valid());
+    /// ```
+    pub fn generate_region(
+        &mut self,
+        algorithm: &mut dyn Algorithm,
+        (top_left, bottom_right): (Coords, Coords),
+        rng: &mut StdRng,
+    ) -> Result<(), BuildError> {
+        let ((left, top), (right, bottom)) = (top_left, bottom_right);
+        if left > right || top > bottom || !self.grid.contains(bottom_right) {
+            return Err(BuildError::reason(format!(
+                "Region from {top_left:?} to {bottom_right:?} doesn't fit a {}x{} maze",
+                self.grid.width(),
+                self.grid.height()
+            )));
+        }
+
+        let (width, height) = (right - left + 1, bottom - top + 1);
+        let mut grid = Grid::new(width, height);
+        if self.grid.is_masked() {
+            let mut mask = Mask::new(width, height);
+            for y in 0..height {
+                for x in 0..width {
+                    mask.set_active((x, y), self.grid.is_active((left + x, top + y)));
+                }
+            }
+            if mask.active_count() < width * height && !algorithm.supports_mask() {
+                return Err(BuildError::reason(format!(
+                    "Algorithm `{}` doesn't support masks",
+                    algorithm.name()
+                )));
+            }
+            grid.apply_mask(mask);
+        }
+        algorithm.generate(&mut grid, None, rng);
+
+        for y in 0..height {
+            for x in 0..width {
+                let coords = (left + x, top + y);
+                let mut border = Cell::empty();
+                border.set(Cell::NORTH, y == 0);
+                border.set(Cell::SOUTH, y + 1 == height);
+                border.set(Cell::WEST, x == 0);
+                border.set(Cell::EAST, x + 1 == width);
+
+                let cell = grid[(x, y)] | (self.grid[coords] & border);
+                self.grid
+                    .cells
+                    .set(coords.1 * self.grid.width() + coords.0, cell);
+                match grid.overpass((x, y)) {
+                    Some(overpass) => self.grid.set_overpass(coords, overpass),
+                    None => self.grid.clear_overpass(coords),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the coords of the center cell, i.e. `(width / 2, height / 2)`
+    ///
+    /// For even dimensions this is the bottom-right one of the [`center_cells`](Self::center_cells).
+    #[must_use]
+    pub const fn center(&self) -> Coords {
+        (self.grid.width() / 2, self.grid.height() / 2)
+    }
+
+    /// Returns the central cells in row-major order
+    ///
+    /// A dimension of an odd size has a single central cell and a dimension of an even size has two
+    /// of them, so 1, 2 or 4 cells are returned.
+    #[must_use]
+    pub fn center_cells(&self) -> Vec<Coords> {
+        let middle = |size: usize| {
+            if size.is_multiple_of(2) {
+                size.saturating_sub(1) / 2..=size / 2
+            } else {
+                size / 2..=size / 2
+            }
+        };
+        let (xs, ys) = (middle(self.grid.width()), middle(self.grid.height()));
+
+        ys.flat_map(|y| xs.clone().map(move |x| (x, y))).collect()
+    }
+
+    /// Returns the border cells with their open outward-facing passages
+    ///
+    /// Cells are listed once, clockwise starting from the top-left corner. The returned [`Cell`]
+    /// contains only the passages leading outside the maze, so a fully closed border cell is
+    /// returned with an empty [`Cell`].
+    #[must_use]
+    pub fn perimeter(&self) -> Vec<(Coords, Cell)> {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        if width == 0 || height == 0 {
+            return vec![];
+        }
+
+        let top = (0..width).map(|x| (x, 0));
+        let right = (1..height).map(|y| (width - 1, y));
+        let bottom = (0..width - 1).rev().map(|x| (x, height - 1));
+        let left = (1..height - 1).rev().map(|y| (0, y));
+
+        let mut seen = HashSet::new();
+        top.chain(right)
+            .chain(bottom)
+            .chain(left)
+            .filter(|coords| seen.insert(*coords))
+            .map(|(x, y)| {
+                let mut outward = Cell::empty();
+                outward.set(Cell::NORTH, y == 0);
+                outward.set(Cell::EAST, x + 1 == width);
+                outward.set(Cell::SOUTH, y + 1 == height);
+                outward.set(Cell::WEST, x == 0);
+                ((x, y), self.grid[(x, y)] & outward)
+            })
+            .collect()
+    }
+
+    /// Returns the start cell, the direction and the length in cells of the longest straight
+    /// passage
+    ///
+    /// Horizontal passages start at their western end and lead to `EAST`, while vertical ones
+    /// start at their northern end and lead to `SOUTH`. When several passages have the same length,
+    /// the first one in row-major order is returned, preferring horizontal ones.
+    #[must_use]
+    pub fn longest_straight(&self) -> (Coords, Cell, usize) {
+        let mut longest = ((0, 0), Cell::EAST, usize::from(self.grid.cells.len() > 0));
+
+        for direction in [Direction::East, Direction::South] {
+            let opposite = Cell::from(direction.opposite());
+            let direction = Cell::from(direction);
+
+            for (start, cell) in self.iter() {
+                if cell.contains(opposite) {
+                    continue;
+                }
+
+                let mut length = 1;
+                let mut coords = start;
+                while self.grid.is_carved(coords, direction) {
+                    let Ok(next) = self.grid.get_next_cell_coords(coords, direction) else {
+                        break;
+                    };
+                    coords = next;
+                    length += 1;
+                }
+
+                if length > longest.2 {
+                    longest = (start, direction, length);
+                }
+            }
+        }
+
+        longest
+    }
+
+    /// Returns the maze expanded into a grid of `2 * width + 1` columns and `2 * height + 1` rows,
+    /// where `true` is a wall and `false` is a passage
+    ///
+    /// Cells are at odd rows and columns, the walls between them are in between and the outer
+    /// rows and columns are the border. Rows are ordered from north to south, so a cell at
+    /// `(x, y)` is at `wall_grid[2 * y + 1][2 * x + 1]`. Masked-off cells are walls.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_knossos::maze::OrthogonalMaze;
+    ///
+    /// let walls = OrthogonalMaze::new(3, 2).to_wall_grid();
+    /// assert_eq!((walls[0].len(), walls.len()), (7, 5));
+    /// ```
+    #[must_use]
+    pub fn to_wall_grid(&self) -> Vec<Vec<bool>> {
+        (0..=self.grid.height() * 2)
+            .map(|y| {
+                (0..=self.grid.width() * 2)
+                    .map(|x| matches!(block_at(&self.grid, x, y), Block::Wall))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns a new maze where every cell is subdivided into a `factor` x `factor` block of open
+    /// cells and every passage opens the whole edge between two blocks
+    ///
+    /// The walls between blocks are kept, so the resulting corridors are `factor` cells wide,
+    /// while walls stay thin. Since the blocks contain loops, the resulting maze is no longer a
+    /// perfect one, but it stays connected if this maze is. A `factor` of 0 is treated as 1.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_knossos::maze::OrthogonalMaze;
+    ///
+    /// let maze = OrthogonalMaze::new(5, 4).upscale(3);
+    /// assert_eq!(maze.dimensions(), (15, 12));
+    /// ```
+    #[must_use]
+    pub fn upscale(&self, factor: usize) -> Self {
+        let factor = factor.max(1);
+        let mut maze = Self::new(self.grid.width() * factor, self.grid.height() * factor);
+
+        let fine_width = maze.grid.width();
+        for y in 0..maze.grid.height() {
+            for x in 0..fine_width {
+                let coarse = self.grid[(x / factor, y / factor)];
+                let (inner_x, inner_y) = (x % factor, y % factor);
+
+                // Cells inside a block are always connected, while the block edges are open only
+                // where the coarse cell has a passage
+                let mut cell = Cell::empty();
+                cell.set(Cell::NORTH, inner_y > 0 || coarse.contains(Cell::NORTH));
+                cell.set(
+                    Cell::SOUTH,
+                    inner_y + 1 < factor || coarse.contains(Cell::SOUTH),
+                );
+                cell.set(Cell::WEST, inner_x > 0 || coarse.contains(Cell::WEST));
+                cell.set(
+                    Cell::EAST,
+                    inner_x + 1 < factor || coarse.contains(Cell::EAST),
+                );
+                maze.grid.cells.set(y * fine_width + x, cell);
+            }
+        }
+
+        maze
+    }
+
+    /// Returns a new maze rotated by 90 degrees clockwise
+    ///
+    /// The width and the height of the rotated maze are swapped and every passage is turned
+    /// accordingly, e.g. a passage to `NORTH` becomes a passage to `EAST`.
+    #[must_use]
+    pub fn rotate_90(&self) -> Self {
+        let height = self.grid.height();
+        self.transform(
+            (height, self.grid.width()),
+            |(x, y)| (height - 1 - y, x),
+            &[
+                (Cell::NORTH, Cell::EAST),
+                (Cell::EAST, Cell::SOUTH),
+                (Cell::SOUTH, Cell::WEST),
+                (Cell::WEST, Cell::NORTH),
+            ],
+        )
+    }
+
+    /// Returns a new maze rotated by 180 degrees
+    ///
+    /// The maze keeps its dimensions, while every passage is turned to the opposite direction.
+    #[must_use]
+    pub fn rotate_180(&self) -> Self {
+        let (width, height) = self.dimensions();
+        self.transform(
+            (width, height),
+            |(x, y)| (width - 1 - x, height - 1 - y),
+            &[
+                (Cell::NORTH, Cell::SOUTH),
+                (Cell::EAST, Cell::WEST),
+                (Cell::SOUTH, Cell::NORTH),
+                (Cell::WEST, Cell::EAST),
+            ],
+        )
+    }
+
+    /// Returns a new maze mirrored along its vertical axis, i.e. its columns are reversed
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.grid.width();
+        self.transform(
+            (width, self.grid.height()),
+            |(x, y)| (width - 1 - x, y),
+            &[
+                (Cell::NORTH, Cell::NORTH),
+                (Cell::EAST, Cell::WEST),
+                (Cell::SOUTH, Cell::SOUTH),
+                (Cell::WEST, Cell::EAST),
+            ],
+        )
+    }
+
+    /// Returns a new maze mirrored along its horizontal axis, i.e. its rows are reversed
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.grid.height();
+        self.transform(
+            (self.grid.width(), height),
+            |(x, y)| (x, height - 1 - y),
+            &[
+                (Cell::NORTH, Cell::SOUTH),
+                (Cell::EAST, Cell::EAST),
+                (Cell::SOUTH, Cell::NORTH),
+                (Cell::WEST, Cell::WEST),
+            ],
+        )
+    }
+
+    /// Returns `true` if `other` has the same passages as this maze or any of its rotations and
+    /// reflections
+    ///
+    /// Square mazes are compared against all the 8 dihedral transforms. Only the transforms that
+    /// keep the maze dimensions can match for rectangular mazes, so effectively 4 of them are
+    /// checked when both mazes have the same width and height.
+    #[must_use]
+    pub fn is_isomorphic(&self, other: &Self) -> bool {
+        let mut rotated = self.clone();
+        for _ in 0..4 {
+            if rotated.has_same_passages(other)
+                || rotated.flip_horizontal().has_same_passages(other)
+            {
+                return true;
+            }
+            rotated = rotated.rotate_90();
+        }
+        false
+    }
+
+    fn has_same_passages(&self, other: &Self) -> bool {
+        self.grid.width() == other.grid.width()
+            && self.grid.height() == other.grid.height()
+            && self.iter().eq(other.iter())
+    }
+
+    /// Returns a new maze of a given size, where each cell of this maze is moved to the coords
+    /// returned by `map_coords` and its passages are remapped by the `(from, to)` direction pairs
+    fn transform(
+        &self,
+        (width, height): (usize, usize),
+        map_coords: impl Fn(Coords) -> Coords,
+        directions: &[(Cell, Cell)],
+    ) -> Self {
+        let mut maze = Self::new(width, height);
+
+        for (coords, cell) in self.iter() {
+            let cell = directions
+                .iter()
+                .filter(|(from, _)| cell.contains(*from))
+                .fold(Cell::empty(), |cell, (_, to)| cell | *to);
+            let (x, y) = map_coords(coords);
+            maze.grid.cells.set(y * width + x, cell);
+        }
+
+        maze
+    }
+
+    /// Returns a formatted maze using the provided formatter.
+    ///
+    /// This method generates a formatted representation of the maze grid by applying
+    /// the specified formatter, which converts the internal maze structure into the
+    /// desired format `T`. This formatted result can then be further processed or saved
+    /// elsewhere.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy_knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMaze::new(5, 5);
+    /// let formatted_maze = maze.format(GameMap::new());
+    /// ```
+    ///
+    /// # Note
+    /// This function can be useful when the formatted maze needs to be used for further processing,
+    /// logging, or display.
+    pub fn format<F, T>(&self, formatter: F) -> T
+    where
+        F: Formatter<T>,
+        T: Saveable,
+    {
+        formatter.format(&self.grid)
+    }
+
+    /// Renders the maze with a given [`Image`] formatter and returns the image in memory
+    ///
+    /// This is a shortcut for `maze.format(image).into_inner()`, which is handy for uploading
+    /// the maze as a texture without saving it to a file first. The image is
+    /// `margin * 2 + cell_width * width - wall * (width - 1)` pixels wide, where `cell_width` is
+    /// `wall * 2 + passage`, and as tall for the height of the maze, plus the room of a legend.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy_knossos::maze::*;
+    ///
+    /// let maze = OrthogonalMaze::new(5, 4);
+    /// let image = maze.to_rgb_image(Image::new().wall(2).passage(4).margin(3));
+    /// assert_eq!(image.dimensions(), (38, 32));
+    /// ```
+    #[must_use]
+    pub fn to_rgb_image(&self, image: Image) -> RgbImage {
+        self.format(image).into_inner()
+    }
+}
+
+impl std::ops::Index<Coords> for OrthogonalMaze {
+    type Output = Cell;
+
+    fn index(&self, index: Coords) -> &Self::Output {
+        &self.grid[index]
+    }
+}
+
+impl fmt::Display for OrthogonalMaze {
+    /// Writes a formatted maze into a buffer
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.grid)?;
+        Ok(())
+    }
+}
+
+pub struct OrthogonalMazeIterator<'a> {
+    maze: &'a OrthogonalMaze,
+    index: usize,
+}
+
+impl<'a> Iterator for OrthogonalMazeIterator<'a> {
+    type Item = (Coords, &'a Cell);
+    fn next(&mut self) -> Option<Self::Item> {
+        let width = self.maze.grid.width();
+        if self.index < self.maze.grid.cells.len() {
+            let result = Some((
+                (self.index % width, self.index / width),
+                &self.maze.grid.cells[self.index],
+            ));
+            self.index += 1;
+            result
+        } else {
+            None
+        }
+    }
+}
+
+pub struct OrthogonalMazeIntoIterator {
+    maze: OrthogonalMaze,
+    index: usize,
+    width: usize,
+}
+
+impl IntoIterator for OrthogonalMaze {
+    type Item = (Coords, Cell);
+    type IntoIter = OrthogonalMazeIntoIterator;
+
+    fn into_iter(self) -> OrthogonalMazeIntoIterator {
+        OrthogonalMazeIntoIterator {
+            width: self.grid.width(),
+            maze: self,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for OrthogonalMazeIntoIterator {
+    type Item = (Coords, Cell);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let width = self.width;
+        let cell = *self.maze.grid.cells.get(self.index)?;
+        let coords = (self.index % width, self.index / width);
+        self.index += 1;
+
+        Some((coords, cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::grid::cell::Cell;
+    use crate::utils::types::direction_between;
+
+    use super::*;
+
+    #[test]
+    fn carve_then_fill_wall_restores_cells() {
+        let mut maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let original = maze.clone();
+        let (from, direction) = maze
+            .iter()
+            .find_map(|(coords, cell)| {
+                [Cell::EAST, Cell::SOUTH]
+                    .into_iter()
+                    .find(|dir| {
+                        !cell.contains(*dir) && maze.grid.get_next_cell_coords(coords, *dir).is_ok()
+                    })
+                    .map(|dir| (coords, dir))
+            })
+            .unwrap();
+
+        let to = maze.carve(from, direction).unwrap();
+        assert!(maze[from].contains(direction));
+        assert_eq!(maze.diff(&original).unwrap().len(), 2);
+
+        let back = direction_between(to, from).unwrap();
+        assert_eq!(maze.fill_wall(to, back).unwrap(), from);
+        assert_eq!(maze, original);
+        assert!(maze.is_valid());
+    }
+
+    #[test]
+    fn reachability_on_a_disconnected_maze() {
+        // Two corridors split by a wall: the top row and the two bottom rows joined on the west
+        let mut maze = OrthogonalMaze::new(3, 3);
+        for (from, direction) in [
+            ((0, 0), Cell::EAST),
+            ((1, 0), Cell::EAST),
+            ((0, 1), Cell::EAST),
+            ((1, 1), Cell::EAST),
+            ((0, 1), Cell::SOUTH),
+            ((0, 2), Cell::EAST),
+        ] {
+            maze.carve(from, direction).unwrap();
+        }
+
+        assert!(maze.is_reachable((0, 0), (2, 0)));
+        assert!(maze.is_reachable((1, 2), (2, 1)));
+        assert!(maze.is_reachable((1, 1), (1, 1)));
+        assert!(!maze.is_reachable((0, 0), (0, 1)));
+        assert!(!maze.is_reachable((2, 0), (2, 2)));
+        assert!(!maze.is_reachable((1, 2), (2, 2)));
+        assert!(!maze.is_reachable((2, 2), (2, 3)));
+        assert!(!maze.is_reachable((3, 0), (3, 0)));
+        assert!(!maze.is_valid());
+    }
+
+    #[test]
+    fn carve_out_of_the_maze() {
+        let mut maze = OrthogonalMaze::new(2, 2);
+
+        assert!(maze.carve((0, 0), Cell::NORTH).is_err());
+        assert!(maze.carve((2, 0), Cell::WEST).is_err());
+        assert!(maze.fill_wall((1, 1), Cell::EAST).is_err());
+        assert_eq!(maze, OrthogonalMaze::new(2, 2));
+    }
+
+    #[test]
+    fn iterators_have_size() {
+        let grid = generate_valid_maze();
+        let maze = OrthogonalMaze { grid };
+
+        let iter_count = maze.iter().count();
+
+        assert_eq!(iter_count, 16);
+        assert_eq!(
+            maze.iter().nth(5).unwrap(),
+            ((1usize, 1usize), &(Cell::SOUTH | Cell::EAST | Cell::WEST))
+        );
+    }
+
+    #[test]
+    fn into_iterators_have_size() {
+        let grid = generate_valid_maze();
+        let maze = OrthogonalMaze { grid };
+
+        let iter_count = maze.clone().into_iter().count();
+
+        assert_eq!(iter_count, 16);
+        assert_eq!(
+            maze.into_iter().nth(5).unwrap(),
+            ((1usize, 1usize), (Cell::SOUTH | Cell::EAST | Cell::WEST))
+        );
+    }
+
+    #[test]
+    fn display_orthogonal_maze() {
+        let mut expected = String::new();
+        expected.push_str(" _______ \n");
+        expected.push_str("| |___  |\n");
+        expected.push_str("|_   _| |\n");
+        expected.push_str("|  _____|\n");
+        expected.push_str("|_______|\n");
+
+        let grid = generate_valid_maze();
+        let maze = OrthogonalMaze { grid };
+        let actual = maze.to_string();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn valid_maze() {
+        let grid = generate_valid_maze();
+        let maze = OrthogonalMaze { grid };
+        assert!(maze.is_valid());
+    }
+
+    #[test]
+    fn invalid_maze() {
         let grid = generate_invalid_maze();
         let maze = OrthogonalMaze { grid };
-        assert!(!maze.is_valid());
+        assert!(!maze.is_valid());
+    }
+
+    #[test]
+    fn validation_is_deterministic_on_large_maze() {
+        let mut maze = crate::maze::OrthogonalMazeBuilder::new()
+            .width(500)
+            .height(500)
+            .seed(2)
+            .build()
+            .unwrap();
+        assert!((0..3).all(|_| maze.is_valid()));
+
+        maze.get_grid_mut().cells.set(0, Cell::empty());
+        assert!((0..3).all(|_| !maze.is_valid()));
+    }
+
+    #[test]
+    #[ignore = "slow in debug builds, run with `cargo test -- --ignored`"]
+    fn validation_of_a_1000_x_1000_maze() {
+        let mut maze = crate::maze::OrthogonalMazeBuilder::new()
+            .width(1000)
+            .height(1000)
+            .seed(2)
+            .build()
+            .unwrap();
+        assert!(maze.is_valid());
+
+        maze.get_grid_mut().cells.set(0, Cell::empty());
+        assert!(!maze.is_valid());
+    }
+
+    #[test]
+    fn openings_remove_a_single_outer_wall() {
+        let mut maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let before = maze.clone();
+
+        assert_eq!(maze.open_entrance(Border::West, 0), Some((0, 0)));
+        assert_eq!(maze.open_exit(Border::South, 3), Some((3, 3)));
+        assert_eq!(maze.open_exit(Border::East, 4), None);
+
+        for (coords, cell) in maze.iter() {
+            let opened = cell
+                .walls_count_sq()
+                .abs_diff(before[coords].walls_count_sq());
+            let expected = usize::from(coords == (0, 0) || coords == (3, 3));
+            assert_eq!(opened as usize, expected, "{coords:?}");
+        }
+        assert!(maze[(0, 0)].contains(Cell::WEST));
+        assert!(maze[(3, 3)].contains(Cell::SOUTH));
+        assert!(maze.is_valid());
+    }
+
+    #[test]
+    fn dimensions() {
+        let maze = OrthogonalMaze::new(7, 3);
+        assert_eq!(maze.width(), 7);
+        assert_eq!(maze.height(), 3);
+        assert_eq!(maze.dimensions(), (7, 3));
+    }
+
+    #[test]
+    fn get_cells_with_bounds_checking() {
+        let mut maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let (width, height) = (maze.grid.width(), maze.grid.height());
+
+        assert_eq!(maze.get((0, 0)), Some(&maze[(0, 0)]));
+        assert_eq!(
+            maze.get((width - 1, height - 1)),
+            Some(&maze[(width - 1, height - 1)])
+        );
+        assert_eq!(maze.get((width, 0)), None);
+        assert_eq!(maze.get((0, height)), None);
+        assert!(maze.get_mut((width, height)).is_none());
+
+        *maze.get_mut((1, 1)).unwrap() = Cell::NORTH | Cell::EAST;
+        assert!(maze[(1, 0)].contains(Cell::EAST));
+        maze.get_mut((1, 0)).unwrap().remove(Cell::EAST);
+        assert_eq!(maze[(1, 1)], Cell::NORTH | Cell::EAST);
+        assert!(!maze[(1, 0)].contains(Cell::EAST));
+    }
+
+    #[test]
+    fn access_by_index_maze() {
+        let grid = generate_valid_maze();
+        let maze = OrthogonalMaze { grid };
+
+        let cell = maze[(3, 1)];
+        assert_eq!(cell, Cell::from_bits(0b0011).unwrap());
+    }
+
+    #[test]
+    fn into_iterators_correct_index() {
+        let grid = generate_valid_maze();
+        let maze = OrthogonalMaze { grid };
+        let width = maze.grid.width();
+
+        maze.into_iter()
+            .enumerate()
+            .for_each(|(idx, (coord, cell))| {
+                assert_eq!(idx, coord.1 * width + coord.0);
+                assert!(!cell.is_empty());
+            });
+    }
+
+    #[test]
+    fn iterators_correct_index() {
+        let grid = generate_valid_maze();
+        let maze = OrthogonalMaze { grid };
+        let width = maze.grid.width();
+
+        maze.iter().enumerate().for_each(|(idx, (coord, cell))| {
+            assert_eq!(idx, coord.1 * width + coord.0);
+            assert!(!cell.is_empty());
+        });
+    }
+
+    #[test]
+    fn maze_ends() {
+        let grid = generate_valid_maze();
+        let maze = OrthogonalMaze { grid };
+
+        let ends = maze.ends();
+
+        assert_eq!(
+            ends,
+            vec![
+                ((0, 0), &Cell::SOUTH),
+                ((1, 0), &Cell::EAST),
+                ((2, 1), &Cell::WEST),
+                ((3, 3), &Cell::WEST)
+            ]
+        );
+    }
+
+    #[test]
+    fn to_rgb_image_matches_formatted_image() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let formatter = || Image::new().wall(3).passage(5).margin(7);
+
+        let image = maze.to_rgb_image(formatter());
+
+        // 4 cells of 11 pixels share 3 walls of 3 pixels
+        assert_eq!(
+            image.dimensions(),
+            (7 * 2 + 4 * 11 - 3 * 3, 7 * 2 + 4 * 11 - 3 * 3)
+        );
+        assert_eq!(image, maze.format(formatter()).into_inner());
+    }
+
+    #[test]
+    fn maze_junctions_and_corridors() {
+        let grid = generate_valid_maze();
+        let maze = OrthogonalMaze { grid };
+
+        assert_eq!(
+            maze.junctions(),
+            vec![
+                ((1, 1), &(Cell::EAST | Cell::SOUTH | Cell::WEST)),
+                ((1, 2), &(Cell::NORTH | Cell::EAST | Cell::WEST)),
+            ]
+        );
+        let corridors: Vec<Coords> = maze
+            .corridors()
+            .into_iter()
+            .map(|(coords, _)| coords)
+            .collect();
+        assert_eq!(corridors, vec![(2, 0), (3, 1), (2, 2), (1, 3), (2, 3)]);
+    }
+
+    #[test]
+    fn reachable_dead_ends_on_split_grid() {
+        let mut grid = Grid::new(4, 2);
+        // Top row: a corridor with a dead end at each side
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::EAST).unwrap();
+        grid.carve_passage((2, 0), Cell::EAST).unwrap();
+        // Bottom row: a separate corridor that cannot be reached from the top row
+        grid.carve_passage((0, 1), Cell::EAST).unwrap();
+        grid.carve_passage((1, 1), Cell::EAST).unwrap();
+        grid.carve_passage((2, 1), Cell::EAST).unwrap();
+        let maze = OrthogonalMaze { grid };
+
+        assert_eq!(maze.ends().len(), 4);
+        assert_eq!(maze.reachable_dead_ends((0, 0)), 2);
+        assert_eq!(maze.reachable_dead_ends((2, 1)), 2);
+    }
+
+    #[test]
+    fn reachable_dead_ends_on_valid_maze() {
+        let grid = generate_valid_maze();
+        let maze = OrthogonalMaze { grid };
+
+        assert_eq!(maze.reachable_dead_ends((0, 0)), maze.ends().len());
     }
 
     #[test]
-    fn access_by_index_maze() {
+    fn solve_maze() {
         let grid = generate_valid_maze();
         let maze = OrthogonalMaze { grid };
 
-        let cell = maze[(3, 1)];
-        assert_eq!(cell, Cell::from_bits(0b0011).unwrap());
+        let path = maze.solve((0, 0), (3, 3)).unwrap();
+
+        assert_eq!(
+            path,
+            vec![
+                (0, 0),
+                (0, 1),
+                (1, 1),
+                (1, 2),
+                (0, 2),
+                (0, 3),
+                (1, 3),
+                (2, 3),
+                (3, 3)
+            ]
+        );
+        assert_eq!(maze.solve((0, 0), (0, 0)), Some(vec![(0, 0)]));
+        assert_eq!(maze.solve((0, 0), (4, 4)), None);
     }
 
     #[test]
-    fn into_iterators_correct_index() {
+    fn distance_between_matches_solution() {
         let grid = generate_valid_maze();
         let maze = OrthogonalMaze { grid };
-        let width = maze.grid.width();
 
-        maze.into_iter()
-            .enumerate()
-            .for_each(|(idx, (coord, cell))| {
-                assert_eq!(idx, coord.1 * width + coord.0);
-                assert!(!cell.is_empty());
-            });
+        for goal in [(0, 0), (3, 0), (2, 1), (3, 3), (1, 2)] {
+            let steps = maze.solve((0, 0), goal).unwrap().len() - 1;
+            assert_eq!(maze.distance_between((0, 0), goal), Some(steps as u32));
+        }
     }
 
     #[test]
-    fn iterators_correct_index() {
+    fn distance_between_unreachable_cells() {
+        let mut grid = Grid::new(2, 2);
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        let maze = OrthogonalMaze { grid };
+
+        assert_eq!(maze.distance_between((0, 0), (1, 0)), Some(1));
+        assert_eq!(maze.distance_between((0, 0), (1, 1)), None);
+        assert_eq!(maze.solve((0, 0), (1, 1)), None);
+    }
+
+    #[test]
+    fn sub_maze_seals_cut_edges() {
         let grid = generate_valid_maze();
         let maze = OrthogonalMaze { grid };
-        let width = maze.grid.width();
 
-        maze.iter().enumerate().for_each(|(idx, (coord, cell))| {
-            assert_eq!(idx, coord.1 * width + coord.0);
-            assert!(!cell.is_empty());
-        });
+        let sub = maze.sub_maze((1, 1), 2, 2);
+
+        assert_eq!(sub.grid.width(), 2);
+        assert_eq!(sub.grid.height(), 2);
+        assert_eq!(sub[(0, 0)], Cell::SOUTH | Cell::EAST);
+        assert_eq!(sub[(1, 0)], Cell::WEST);
+        assert_eq!(sub[(0, 1)], Cell::NORTH | Cell::EAST);
+        assert_eq!(sub[(1, 1)], Cell::WEST);
+        assert!(sub.is_valid());
     }
 
     #[test]
-    fn maze_ends() {
+    fn sub_maze_is_clamped_to_bounds() {
         let grid = generate_valid_maze();
         let maze = OrthogonalMaze { grid };
 
-        let ends = maze.ends();
+        let sub = maze.sub_maze((2, 3), 5, 5);
+
+        assert_eq!(sub.grid.width(), 2);
+        assert_eq!(sub.grid.height(), 1);
+        assert_eq!(sub[(0, 0)], Cell::EAST);
+        assert_eq!(sub[(1, 0)], Cell::WEST);
+    }
+
+    #[test]
+    fn reachable_count_of_cut_maze() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        assert_eq!(maze.reachable_count((0, 0)), 16);
+        assert_eq!(maze.reachable_count((4, 4)), 0);
+
+        // The bottom row of the cut is only connected to the rest through the sealed western edge
+        let cut = maze.sub_maze((1, 1), 3, 3);
+        assert_eq!(cut.reachable_count((2, 0)), 6);
+        assert_eq!(cut.reachable_count((0, 2)), 3);
+    }
+
+    #[test]
+    fn cells_grouped_by_config() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let groups = maze.cells_by_config();
+
+        assert_eq!(groups.values().map(Vec::len).sum::<usize>(), 16);
+        let corridors = &groups[&(Cell::EAST | Cell::WEST).to_bits()];
+        assert!(!corridors.is_empty());
+        assert!(
+            corridors
+                .iter()
+                .all(|coords| maze[*coords] == Cell::EAST | Cell::WEST)
+        );
+    }
 
+    #[test]
+    fn degree_histogram_counts_passages() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let histogram = maze.degree_histogram();
+
+        assert_eq!(histogram.iter().sum::<usize>(), 16);
+        assert_eq!(histogram[1], maze.ends().len());
+        assert_eq!(histogram[0], 0);
         assert_eq!(
-            ends,
+            OrthogonalMaze::new(2, 3).degree_histogram(),
+            [6, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn valid_path() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let path = maze.solve((0, 0), (3, 3)).unwrap();
+
+        assert_eq!(maze.is_valid_path(&path), Ok(()));
+        assert_eq!(maze.is_valid_path(&[(2, 2)]), Ok(()));
+        assert_eq!(maze.is_valid_path(&[]), Err(PathError::Empty));
+    }
+
+    #[test]
+    fn path_through_wall() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+
+        assert_eq!(
+            maze.is_valid_path(&[(0, 0), (0, 1), (0, 2)]),
+            Err(PathError::Blocked {
+                step: 2,
+                from: (0, 1),
+                to: (0, 2)
+            })
+        );
+    }
+
+    #[test]
+    fn disconnected_path() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+
+        assert_eq!(
+            maze.is_valid_path(&[(0, 0), (0, 1), (2, 1)]),
+            Err(PathError::NotAdjacent {
+                step: 2,
+                from: (0, 1),
+                to: (2, 1)
+            })
+        );
+        assert_eq!(
+            maze.is_valid_path(&[(0, 0), (0, 4)]),
+            Err(PathError::OutOfBounds {
+                step: 1,
+                coords: (0, 4)
+            })
+        );
+    }
+
+    #[test]
+    fn active_cells_exclude_masked_cells() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        assert_eq!(maze.active_cell_count(), 16);
+        assert_eq!(OrthogonalMaze::new(1, 1).active_cell_count(), 1);
+        assert_eq!(OrthogonalMaze::new(3, 3).active_cell_count(), 9);
+
+        // Only the top left 2x2 corner takes part in the maze, even before it's carved
+        let mut mask = Mask::new(3, 3);
+        for coords in [(2, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            mask.set_active(coords, false);
+        }
+        let mut maze = OrthogonalMaze::new(3, 3);
+        maze.grid.apply_mask(mask);
+        assert_eq!(maze.active_cell_count(), 4);
+        maze.grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        assert_eq!(maze.active_cell_count(), 4);
+    }
+
+    #[test]
+    fn braid_removes_dead_ends() {
+        let mut maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let original = maze.clone();
+        maze.braid(&mut rng, 0.0);
+        assert_eq!(maze, original);
+
+        maze.braid(&mut rng, 1.0);
+        assert!(!original.ends().is_empty());
+        assert!(maze.ends().is_empty());
+        assert_eq!(maze.reachable_count((0, 0)), 16);
+    }
+
+    #[test]
+    fn cycle_count_after_braiding() {
+        let mut maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let passages = |maze: &OrthogonalMaze| {
+            maze.iter()
+                .map(|(_, cell)| cell.bits().count_ones() as usize)
+                .sum::<usize>()
+                / 2
+        };
+        assert_eq!(maze.cycle_count(), 0);
+
+        maze.carve((2, 1), Cell::EAST).unwrap();
+        assert_eq!(maze.cycle_count(), 1);
+        maze.carve((0, 0), Cell::EAST).unwrap();
+        assert_eq!(maze.cycle_count(), 2);
+
+        let before = passages(&maze);
+        maze.braid(&mut StdRng::seed_from_u64(1), 1.0);
+        assert_eq!(maze.cycle_count(), 2 + passages(&maze) - before);
+        assert!(maze.cycle_count() > 2);
+    }
+
+    #[test]
+    fn braid_partially_keeps_passages_symmetric() {
+        let mut maze = crate::maze::OrthogonalMazeBuilder::new()
+            .width(12)
+            .height(12)
+            .seed(5)
+            .build()
+            .unwrap();
+        let dead_ends = maze.ends().len();
+
+        maze.braid(&mut StdRng::seed_from_u64(5), 0.5);
+
+        let remaining = maze.ends().len();
+        assert!(remaining > 0);
+        assert!(remaining < dead_ends);
+        for ((x, y), cell) in maze.iter() {
+            if x + 1 < 12 {
+                assert_eq!(
+                    cell.contains(Cell::EAST),
+                    maze[(x + 1, y)].contains(Cell::WEST)
+                );
+            }
+            if y + 1 < 12 {
+                assert_eq!(
+                    cell.contains(Cell::SOUTH),
+                    maze[(x, y + 1)].contains(Cell::NORTH)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn generate_region_leaves_other_cells_untouched() {
+        let mut maze = crate::maze::OrthogonalMazeBuilder::new()
+            .width(10)
+            .height(10)
+            .seed(5)
+            .build()
+            .unwrap();
+        let original = maze.clone();
+        let region = ((2, 3), (6, 8));
+        maze.generate_region(
+            &mut crate::maze::Kruskal,
+            region,
+            &mut StdRng::seed_from_u64(5),
+        )
+        .unwrap();
+
+        let inside = |(x, y): Coords| (2..=6).contains(&x) && (3..=8).contains(&y);
+        for (coords, cell) in maze.iter() {
+            if !inside(coords) {
+                assert_eq!(*cell, original[coords], "{coords:?}");
+            }
+        }
+        assert!(
+            maze.diff(&original)
+                .unwrap()
+                .iter()
+                .all(|(c, ..)| inside(*c))
+        );
+        assert!(maze.sub_maze((2, 3), 5, 6).is_valid());
+        assert!(maze.is_valid());
+    }
+
+    #[test]
+    fn generate_region_clears_stale_overpasses() {
+        let mut maze = OrthogonalMaze::new(6, 6);
+        maze.get_grid_mut()
+            .set_overpass((2, 2), Overpass::NorthSouth);
+        maze.get_grid_mut().set_overpass((5, 5), Overpass::EastWest);
+        maze.generate_region(
+            &mut crate::maze::RecursiveBacktracking,
+            ((1, 1), (3, 3)),
+            &mut StdRng::seed_from_u64(3),
+        )
+        .unwrap();
+
+        assert_eq!(maze.grid.overpass((2, 2)), None);
+        assert_eq!(maze.grid.overpass((5, 5)), Some(Overpass::EastWest));
+        assert!(maze.sub_maze((1, 1), 3, 3).is_valid());
+    }
+
+    #[test]
+    fn generate_region_out_of_bounds() {
+        let mut maze = OrthogonalMaze::new(4, 4);
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut algorithm = crate::maze::Kruskal;
+
+        assert!(
+            maze.generate_region(&mut algorithm, ((1, 1), (4, 2)), &mut rng)
+                .is_err()
+        );
+        assert!(
+            maze.generate_region(&mut algorithm, ((2, 2), (1, 3)), &mut rng)
+                .is_err()
+        );
+        assert_eq!(maze, OrthogonalMaze::new(4, 4));
+    }
+
+    #[test]
+    fn wall_grid() {
+        let mut maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        assert!(maze.grid.open_border((0, 0), Cell::WEST));
+        let walls = maze.to_wall_grid();
+
+        assert_eq!(walls.len(), 9);
+        assert!(walls.iter().all(|row| row.len() == 9));
+        for (x, y) in maze.iter().map(|(coords, _)| coords) {
+            assert!(!walls[2 * y + 1][2 * x + 1]);
+        }
+        // Wall corners are always walls
+        assert!(
+            (0..9)
+                .step_by(2)
+                .all(|y| (0..9).step_by(2).all(|x| walls[y][x]))
+        );
+        // The cell (0, 0) has passages to SOUTH and out of the maze to WEST
+        assert_eq!(walls[1][..3], [false, false, true]);
+        assert!(!walls[2][1]);
+        assert!(walls[0].iter().all(|wall| *wall));
+        let passages = walls.iter().flatten().filter(|wall| !**wall).count();
+        // 16 cells, 15 passages between them and the opening
+        assert_eq!(passages, 16 + 15 + 1);
+    }
+
+    #[test]
+    fn upscale_keeps_a_maze_valid() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let upscaled = maze.upscale(3);
+
+        assert_eq!(upscaled.dimensions(), (12, 12));
+        assert!(upscaled.is_valid());
+        assert_eq!(upscaled.reachable_count((0, 0)), 144);
+        // The cell (0, 0) only has a passage to SOUTH, so its block is walled on the east side
+        // and open on the south one
+        assert!((0..3).all(|y| !upscaled[(2, y)].contains(Cell::EAST)));
+        assert!((0..3).all(|x| upscaled[(x, 2)].contains(Cell::SOUTH)));
+        assert_eq!(
+            upscaled[(1, 1)],
+            Cell::NORTH | Cell::SOUTH | Cell::EAST | Cell::WEST
+        );
+        assert_eq!(maze.upscale(1), maze.sub_maze((0, 0), 4, 4));
+    }
+
+    #[test]
+    fn rotate_90_turns_passages() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        }
+        .sub_maze((0, 0), 4, 2);
+        let rotated = maze.rotate_90();
+
+        assert_eq!(rotated.grid.width(), 2);
+        assert_eq!(rotated.grid.height(), 4);
+        assert_eq!(rotated.is_valid(), maze.is_valid());
+        // The cell (0, 0) has a passage to SOUTH and moves to the top right corner
+        assert_eq!(rotated[(1, 0)], Cell::WEST);
+        assert_eq!(maze.rotate_90().rotate_90().rotate_90().rotate_90(), maze);
+    }
+
+    #[test]
+    fn flip_horizontal_mirrors_passages() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let flipped = maze.flip_horizontal();
+
+        assert!(flipped.is_valid());
+        assert_eq!(flipped[(3, 0)], Cell::SOUTH);
+        assert_eq!(flipped[(0, 1)], Cell::NORTH | Cell::SOUTH);
+        assert_eq!(flipped[(3, 1)], Cell::NORTH | Cell::WEST);
+        assert!(flipped.flip_horizontal().has_same_passages(&maze));
+    }
+
+    #[test]
+    fn rotate_180_is_a_double_rotate_90() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        }
+        .sub_maze((0, 0), 4, 2);
+        let rotated = maze.rotate_180();
+
+        assert_eq!(rotated.dimensions(), (4, 2));
+        assert_eq!(rotated.is_valid(), maze.is_valid());
+        // The cell (0, 0) has a passage to SOUTH and moves to the bottom right corner
+        assert_eq!(rotated[(3, 1)], Cell::NORTH);
+        assert_eq!(rotated, maze.rotate_90().rotate_90());
+        assert_eq!(rotated.rotate_180(), maze);
+    }
+
+    #[test]
+    fn flip_vertical_mirrors_passages() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let flipped = maze.flip_vertical();
+
+        assert!(flipped.is_valid());
+        assert_eq!(flipped[(0, 3)], Cell::NORTH);
+        for (coords, cell) in maze.iter() {
+            let mirrored = flipped[(coords.0, 3 - coords.1)];
+            assert_eq!(cell.contains(Cell::NORTH), mirrored.contains(Cell::SOUTH));
+            assert_eq!(cell.contains(Cell::EAST), mirrored.contains(Cell::EAST));
+        }
+        assert!(flipped.flip_vertical().has_same_passages(&maze));
+        assert_eq!(flipped.flip_horizontal(), maze.rotate_180());
+    }
+
+    #[test]
+    fn rotated_maze_is_isomorphic() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let mut unrelated = OrthogonalMaze::new(4, 4);
+        for y in 0..4 {
+            for x in 0..3 {
+                unrelated.grid.carve_passage((x, y), Cell::EAST).unwrap();
+            }
+            if y > 0 {
+                unrelated.grid.carve_passage((0, y), Cell::NORTH).unwrap();
+            }
+        }
+
+        assert!(maze.is_isomorphic(&maze.rotate_90()));
+        assert!(maze.rotate_90().is_isomorphic(&maze));
+        assert!(maze.is_isomorphic(&maze.flip_horizontal().rotate_90()));
+        assert!(!maze.is_isomorphic(&unrelated));
+        assert!(!maze.is_isomorphic(&OrthogonalMaze::new(4, 4)));
+    }
+
+    #[test]
+    fn perimeter_lists_border_openings() {
+        let mut maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let entrance = maze[(3, 1)] | Cell::EAST;
+        maze.grid.cells.set(7, entrance);
+
+        let perimeter = maze.perimeter();
+        let coords: Vec<Coords> = perimeter.iter().map(|(coords, _)| *coords).collect();
+
+        assert_eq!(
+            coords,
             vec![
-                ((0, 0), &Cell::SOUTH),
-                ((1, 0), &Cell::EAST),
-                ((2, 1), &Cell::WEST),
-                ((3, 3), &Cell::WEST)
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (3, 0),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (2, 3),
+                (1, 3),
+                (0, 3),
+                (0, 2),
+                (0, 1)
             ]
         );
+        for (coords, cell) in perimeter {
+            if coords == (3, 1) {
+                assert_eq!(cell, Cell::EAST);
+            } else {
+                assert_eq!(cell, Cell::empty());
+            }
+        }
+    }
+
+    #[test]
+    fn longest_straight_passage() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        assert_eq!(maze.longest_straight(), ((0, 2), Cell::EAST, 4));
+
+        let rotated = maze.sub_maze((0, 0), 4, 2).rotate_90();
+        assert_eq!(rotated.longest_straight(), ((0, 0), Cell::SOUTH, 3));
+        assert_eq!(
+            OrthogonalMaze::new(2, 2).longest_straight(),
+            ((0, 0), Cell::EAST, 1)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn json_roundtrip() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let json = maze.format(crate::maze::Json).into_inner();
+
+        assert_eq!(OrthogonalMaze::from_json(&json).unwrap(), maze);
+        assert!(OrthogonalMaze::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn center_cells_for_odd_and_even_dimensions() {
+        let maze = OrthogonalMaze::new(5, 7);
+        assert_eq!(maze.center(), (2, 3));
+        assert_eq!(maze.center_cells(), vec![(2, 3)]);
+
+        let maze = OrthogonalMaze::new(4, 5);
+        assert_eq!(maze.center(), (2, 2));
+        assert_eq!(maze.center_cells(), vec![(1, 2), (2, 2)]);
+
+        let maze = OrthogonalMaze::new(5, 4);
+        assert_eq!(maze.center_cells(), vec![(2, 1), (2, 2)]);
+
+        let maze = OrthogonalMaze::new(4, 6);
+        assert_eq!(maze.center(), (2, 3));
+        assert_eq!(maze.center_cells(), vec![(1, 2), (2, 2), (1, 3), (2, 3)]);
+    }
+
+    #[test]
+    fn detour_factor_flags_long_detours() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let factors = maze.detour_factor((0, 0));
+
+        assert_eq!(factors.len(), 15);
+        assert!(!factors.contains_key(&(0, 0)));
+        assert_eq!(factors[&(0, 1)], 1.0);
+        // (1, 0) is right next to the start, but is reached only after a walk around the maze
+        assert_eq!(factors[&(1, 0)], 9.0);
+        assert!(factors.values().all(|factor| *factor <= 9.0));
+    }
+
+    #[test]
+    fn entrance_fairness() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+
+        assert!((maze.entrance_fairness(&[(0, 1), (1, 2)], (1, 1)) - 1.0).abs() < f64::EPSILON);
+        assert!(maze.entrance_fairness(&[(0, 0), (0, 1)], (1, 1)) > 1.0);
+        assert!((maze.entrance_fairness(&[(0, 0), (0, 1)], (1, 1)) - 2.0).abs() < f64::EPSILON);
+        assert!((maze.entrance_fairness(&[], (1, 1)) - 1.0).abs() < f64::EPSILON);
+        assert!(
+            maze.entrance_fairness(&[(1, 1), (0, 1)], (1, 1))
+                .is_infinite()
+        );
+    }
+
+    #[test]
+    fn path_neighbors_flank_the_path() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let path = maze.solve((0, 0), (1, 2)).unwrap();
+        assert_eq!(path, vec![(0, 0), (0, 1), (1, 1), (1, 2)]);
+
+        let neighbors = maze.path_neighbors(&path);
+
+        assert_eq!(
+            neighbors,
+            HashSet::from([(1, 0), (0, 2), (2, 1), (2, 2), (1, 3)])
+        );
+        assert!(path.iter().all(|coords| !neighbors.contains(coords)));
+        assert!(maze.path_neighbors(&[]).is_empty());
+    }
+
+    #[test]
+    fn components_of_an_edited_maze() {
+        let mut maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        assert_eq!(maze.connected_components(), 1);
+        assert!(maze.component_map().values().all(|label| *label == 0));
+
+        // Cutting the passages of (3, 0) and (3, 3) splits two parts off the maze
+        maze.get_mut((3, 0)).unwrap().remove(Cell::SOUTH);
+        maze.get_mut((3, 1)).unwrap().remove(Cell::NORTH);
+        maze.get_mut((3, 3)).unwrap().remove(Cell::WEST);
+        maze.get_mut((2, 3)).unwrap().remove(Cell::EAST);
+
+        assert_eq!(maze.connected_components(), 3);
+        assert!(!maze.is_valid());
+        let map = maze.component_map();
+        assert_eq!(map.len(), 16);
+        assert_eq!(map[&(0, 0)], 0);
+        assert_eq!(map[&(3, 1)], 0);
+        assert_eq!(map[&(1, 0)], 1);
+        assert_eq!(map[&(3, 0)], 1);
+        assert_eq!(map[&(3, 3)], 2);
+    }
+
+    #[test]
+    fn diff_with_braided_copy() {
+        let maze = crate::maze::OrthogonalMazeBuilder::new()
+            .width(12)
+            .height(12)
+            .seed(3)
+            .build()
+            .unwrap();
+        let mut braided = maze.clone();
+        braided.braid(&mut StdRng::seed_from_u64(3), 1.0);
+
+        assert!(maze.diff(&maze).unwrap().is_empty());
+        let diff = maze.diff(&braided).unwrap();
+        assert!(!diff.is_empty());
+        assert!(
+            diff.windows(2)
+                .all(|pair| (pair[0].0.1, pair[0].0.0) < (pair[1].0.1, pair[1].0.0))
+        );
+        for (coords, cell, braided_cell) in &diff {
+            // Braiding only carves passages, each of them changing a single direction of a cell
+            assert_eq!(maze[*coords], *cell);
+            assert_eq!(braided[*coords], *braided_cell);
+            assert_eq!(braided_cell.to_bits() & cell.to_bits(), cell.to_bits());
+        }
+        // Every new passage changes the cells on both of its sides
+        let carved: u32 = diff
+            .iter()
+            .map(|(_, cell, braided_cell)| (braided_cell.to_bits() ^ cell.to_bits()).count_ones())
+            .sum();
+        assert_eq!(carved % 2, 0);
+        assert!(braided.ends().len() < maze.ends().len());
+    }
+
+    #[test]
+    fn diff_of_mazes_with_different_dimensions() {
+        let error = OrthogonalMaze::new(4, 4)
+            .diff(&OrthogonalMaze::new(5, 3))
+            .unwrap_err();
+
+        assert_eq!(error.dimensions, (4, 4));
+        assert_eq!(error.other_dimensions, (5, 3));
+    }
+
+    #[test]
+    fn stats() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+
+        assert_eq!(
+            maze.stats(),
+            MazeStats {
+                dead_ends: 4,
+                junctions: 2,
+                straight_corridors: 5,
+                turns: 5,
+                total_cells: 16,
+            }
+        );
+    }
+
+    #[test]
+    fn stats_of_a_perfect_maze() {
+        let maze = crate::maze::OrthogonalMazeBuilder::new()
+            .width(20)
+            .height(15)
+            .seed(7)
+            .build()
+            .unwrap();
+        let stats = maze.stats();
+
+        assert_eq!(stats.dead_ends, maze.ends().len());
+        assert_eq!(stats.total_cells, 300);
+        assert_eq!(
+            stats.dead_ends + stats.junctions + stats.straight_corridors + stats.turns,
+            stats.total_cells
+        );
+    }
+
+    #[test]
+    fn openings_round_trip() {
+        let mut maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        maze.open_entrance(Border::North, 2).unwrap();
+        maze.open_exit(Border::East, 3).unwrap();
+
+        assert_eq!(
+            OrthogonalMaze::from_bytes(&maze.to_bytes().unwrap()).unwrap(),
+            maze
+        );
+        let csv = maze.format(crate::maze::Csv).into_inner();
+        assert_eq!(crate::maze::Csv::parse(&csv).unwrap(), maze);
+        #[cfg(feature = "serde")]
+        {
+            let json = maze.format(crate::maze::Json).into_inner();
+            assert_eq!(OrthogonalMaze::from_json(&json).unwrap(), maze);
+        }
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+
+        let bytes = maze.to_bytes().unwrap();
+        assert_eq!(&bytes[..5], b"KNOS\x01");
+        assert_eq!(&bytes[5..13], &[4, 0, 0, 0, 4, 0, 0, 0]);
+        assert_eq!(bytes.len(), 13 + 16);
+        assert_eq!(bytes[13], maze.grid[(0, 0)].to_bits());
+
+        let loaded = OrthogonalMaze::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded, maze);
+        assert_eq!(loaded.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn oversized_maze_to_bytes() {
+        let maze = OrthogonalMaze::new(1 << 32, 0);
+
+        assert_eq!(
+            maze.to_bytes().unwrap_err().reason,
+            "A 4294967296x0 maze is too large to be encoded as bytes"
+        );
+    }
+
+    #[test]
+    #[ignore = "slow in debug builds, run with `cargo test -- --ignored`"]
+    fn bytes_round_trip_beyond_the_text_format_limit() {
+        let side = 4097;
+        assert!(side * side > crate::maze::formatters::MAX_LOADED_CELLS);
+        let mut maze = OrthogonalMaze::new(side, side);
+        maze.grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        maze.grid
+            .carve_passage((side - 1, side - 2), Cell::SOUTH)
+            .unwrap();
+
+        let loaded = OrthogonalMaze::from_bytes(&maze.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded, maze);
+    }
+
+    #[test]
+    fn masked_and_weave_mazes_to_bytes() {
+        let mut mask = Mask::new(4, 4);
+        mask.set_active((1, 1), false);
+        let mut masked = OrthogonalMaze::new(4, 4);
+        masked.grid.apply_mask(mask);
+        assert_eq!(
+            masked.to_bytes().unwrap_err().reason,
+            "A masked maze can't be encoded as bytes"
+        );
+
+        let mut weave = OrthogonalMaze::new(4, 4);
+        weave.grid.set_overpass((1, 1), Overpass::NorthSouth);
+        assert_eq!(
+            weave.to_bytes().unwrap_err().reason,
+            "A weave maze with crossings can't be encoded as bytes"
+        );
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        let maze = OrthogonalMaze {
+            grid: generate_valid_maze(),
+        };
+        let bytes = maze.to_bytes().unwrap();
+
+        for len in 0..bytes.len() {
+            assert!(OrthogonalMaze::from_bytes(&bytes[..len]).is_err());
+        }
+
+        let mut invalid = bytes.clone();
+        invalid[0] = b'X';
+        assert_eq!(
+            OrthogonalMaze::from_bytes(&invalid).unwrap_err().reason,
+            "Maze bytes have an unknown header"
+        );
+
+        let mut invalid = bytes.clone();
+        invalid[4] = 2;
+        assert_eq!(
+            OrthogonalMaze::from_bytes(&invalid).unwrap_err().reason,
+            "Maze bytes have an unsupported version 2"
+        );
+
+        let mut invalid = bytes.clone();
+        invalid[13] = Cell::SOUTH_EAST.to_bits();
+        assert_eq!(
+            OrthogonalMaze::from_bytes(&invalid).unwrap_err().reason,
+            "Cell (0, 0) has invalid passages 0b01000000"
+        );
+
+        let mut invalid = bytes;
+        invalid[13] = 0;
+        assert!(OrthogonalMaze::from_bytes(&invalid).is_err());
+        invalid.push(0);
+        assert!(OrthogonalMaze::from_bytes(&invalid).is_err());
     }
 
     fn generate_valid_maze() -> Grid {
@@ -3,25 +3,59 @@
 //! Acts as a prelude module with all the imports that are necessary for generating and saving
 //! mazes.
 
+#[cfg(feature = "serde")]
+mod archive;
+#[cfg(feature = "parallel")]
+mod batch;
+mod border;
 mod builder;
+mod config;
 mod errors;
 mod grid;
 mod hex_builder;
 mod hex_maze;
+mod mask;
 #[allow(clippy::module_inception)]
 mod maze;
+mod registry;
+mod stats;
+mod steps;
+mod theta_builder;
+mod theta_maze;
 mod validate;
 
 pub mod algorithms;
 pub mod formatters;
 
 pub use algorithms::*;
+#[cfg(feature = "serde")]
+pub use archive::{MazeArchive, load_archive, save_archive};
+#[cfg(feature = "parallel")]
+pub use batch::generate_batch;
+pub use border::Border;
 pub use builder::OrthogonalMazeBuilder;
-pub use errors::MazeSaveError;
-pub use formatters::HexText;
-pub use formatters::{AsciiBroad, AsciiNarrow, GameMap, Image};
+pub use config::GenerationConfig;
+pub use errors::{
+    MazeDiffError, MazeLoadError, MazeSaveError, PathError, StartGoalError, TransitError,
+};
+#[cfg(feature = "serde")]
+pub use formatters::Json;
+pub use formatters::{
+    AnsiColor, AsciiBroad, AsciiNarrow, Csv, Dot, GameMap, Image, LevelText, RenderScale,
+    StreamFormatter, StyledAsciiBroad, Svg, ThetaSvg, Tmx, UnicodeBox,
+};
+pub use formatters::{HexImage, HexText};
 pub use grid::cell::Cell;
+pub use grid::cells::CellMut;
+pub use grid::direction::Direction;
+pub use grid::overpass::Overpass;
 pub use grid::topology::Topology;
 pub use hex_builder::HexMazeBuilder;
 pub use hex_maze::HexMaze;
+pub use mask::Mask;
 pub use maze::OrthogonalMaze;
+pub use registry::{algorithm_by_name, register_algorithm};
+pub use stats::MazeStats;
+pub use steps::{CarveStep, CarveSteps};
+pub use theta_builder::ThetaMazeBuilder;
+pub use theta_maze::ThetaMaze;
@@ -0,0 +1,109 @@
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, RwLock},
+};
+
+use super::{algorithms::Algorithm, config::GenerationConfig};
+
+type AlgorithmFactory = Box<dyn Fn() -> Box<dyn Algorithm> + Send + Sync>;
+
+static REGISTRY: LazyLock<RwLock<HashMap<String, AlgorithmFactory>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Registers a custom algorithm under a given name, so it can be looked up with
+/// [`algorithm_by_name`], referenced from a [`GenerationConfig`] or selected in the CLI
+///
+/// Registering a name twice replaces the previous factory. Built-in algorithm names always resolve
+/// to the built-in algorithms.
+///
+/// # Example
+/// ```
+/// use bevy_knossos::maze::*;
+///
+/// register_algorithm("Backtracker", || Box::new(RecursiveBacktracking));
+///
+/// let maze = GenerationConfig::new("Backtracker").build().unwrap();
+/// assert!(maze.is_valid());
+/// ```
+pub fn register_algorithm<F>(name: impl Into<String>, factory: F)
+where
+    F: Fn() -> Box<dyn Algorithm> + Send + Sync + 'static,
+{
+    let mut registry = REGISTRY
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    registry.insert(name.into(), Box::new(factory));
+}
+
+/// Returns a new instance of a built-in or registered algorithm with a given name
+///
+/// Built-in algorithms are created with their default settings.
+#[must_use]
+pub fn algorithm_by_name(name: &str) -> Option<Box<dyn Algorithm>> {
+    GenerationConfig::new(name).algorithm()
+}
+
+pub(super) fn registered_algorithm(name: &str) -> Option<Box<dyn Algorithm>> {
+    let registry = REGISTRY
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    registry.get(name).map(|factory| factory())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        maze::grid::{Grid, cell::Cell},
+        utils::types::Coords,
+    };
+    use rand::rngs::StdRng;
+
+    /// Carves every row into a corridor and joins the rows along the western border
+    #[derive(Clone)]
+    struct Comb;
+
+    impl Algorithm for Comb {
+        fn generate(&mut self, grid: &mut Grid, _: Option<Coords>, _: &mut StdRng) {
+            for y in 0..grid.height() {
+                for x in 1..grid.width() {
+                    grid.carve_passage((x, y), Cell::WEST).unwrap();
+                }
+                if y > 0 {
+                    grid.carve_passage((0, y), Cell::NORTH).unwrap();
+                }
+            }
+        }
+
+        fn has_start_coords(&self) -> bool {
+            false
+        }
+
+        fn name(&self) -> &'static str {
+            "Comb"
+        }
+    }
+
+    #[test]
+    fn build_registered_algorithm_by_name() {
+        register_algorithm("Comb", || Box::new(Comb));
+
+        let algorithm = algorithm_by_name("Comb").unwrap();
+        assert_eq!(algorithm.name(), "Comb");
+
+        let maze = GenerationConfig::new("Comb").build().unwrap();
+        assert!(maze.is_valid());
+        assert_eq!(maze.ends().len(), 10);
+    }
+
+    #[test]
+    fn built_in_algorithm_by_name() {
+        let algorithm = algorithm_by_name("Sidewinder").unwrap();
+        assert_eq!(algorithm.name(), "Sidewinder");
+    }
+
+    #[test]
+    fn unknown_algorithm_by_name() {
+        assert!(algorithm_by_name("Unregistered").is_none());
+    }
+}
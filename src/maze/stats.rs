@@ -0,0 +1,38 @@
+use super::grid::cell::Cell;
+
+/// Counts of cells of an orthogonal maze grouped by the shape of their passages
+///
+/// Returned by [`OrthogonalMaze::stats`](super::OrthogonalMaze::stats). Cells without any passage
+/// are only counted in [`total_cells`](Self::total_cells).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct MazeStats {
+    /// Cells with a single open passage
+    pub dead_ends: usize,
+    /// Cells with three or four open passages
+    pub junctions: usize,
+    /// Cells with two open passages on opposite sides
+    pub straight_corridors: usize,
+    /// Cells with two open passages on adjacent sides
+    pub turns: usize,
+    /// All the cells of the maze
+    pub total_cells: usize,
+}
+
+impl MazeStats {
+    /// Counts a cell into the statistics
+    pub(crate) fn add(&mut self, cell: Cell) {
+        self.total_cells += 1;
+
+        match 4 - cell.walls_count_sq() {
+            1 => self.dead_ends += 1,
+            2 if cell.contains(Cell::NORTH | Cell::SOUTH)
+                || cell.contains(Cell::EAST | Cell::WEST) =>
+            {
+                self.straight_corridors += 1;
+            }
+            2 => self.turns += 1,
+            3.. => self.junctions += 1,
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use crate::maze::{OrthogonalMaze, grid::cell::Cell};
+use crate::utils::types::Coords;
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+thread_local! {
+    /// Passages carved on the current thread while a recording is in progress
+    static RECORDED: RefCell<Option<Vec<(Coords, Cell)>>> = const { RefCell::new(None) };
+}
+
+/// Restores the recording in progress before a nested one, even if the nested one panics
+struct Recording(Option<Vec<(Coords, Cell)>>);
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        RECORDED.set(self.0.take());
+    }
+}
+
+/// Runs `f` and returns its result with the passages carved on the current thread meanwhile, as
+/// the carving cell and direction in the order they were carved
+pub fn record_carves<T>(f: impl FnOnce() -> T) -> (T, Vec<(Coords, Cell)>) {
+    let recording = Recording(RECORDED.replace(Some(Vec::new())));
+    let result = f();
+    let carves = RECORDED.take().unwrap_or_default();
+    drop(recording);
+    (result, carves)
+}
+
+/// Adds a passage carved by [`Grid::carve_passage`](crate::maze::grid::Grid::carve_passage) to
+/// the recording in progress on the current thread, if any
+pub fn observe_carve(coords: Coords, direction: Cell) {
+    RECORDED.with_borrow_mut(|carves| {
+        if let Some(carves) = carves {
+            carves.push((coords, direction));
+        }
+    });
+}
+
+/// Returns `true` if carving the `steps` into a blank maze results in the passages of `maze`
+///
+/// A passage opens a direction of both of its cells, so that's the case if all the steps are
+/// carved in `maze` and its cells have no other open directions.
+pub fn carves_only(maze: &OrthogonalMaze, steps: &[(Coords, Cell)]) -> bool {
+    let grid = maze.grid();
+    let mut passages = HashSet::new();
+    for &(coords, direction) in steps {
+        let (Ok(next), Some(opposite)) = (
+            grid.get_next_cell_coords(coords, direction),
+            grid.topology().opposite(direction),
+        ) else {
+            return false;
+        };
+        if !grid.is_carved(coords, direction) || !grid.is_carved(next, opposite) {
+            return false;
+        }
+        passages.insert((coords.min(next), coords.max(next)));
+    }
+
+    let open: usize = maze
+        .iter()
+        .map(|(_, cell)| cell.bits().count_ones() as usize)
+        .sum();
+    open == 2 * passages.len()
+}
+
+/// A single passage carved while generating a maze
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CarveStep {
+    /// Coords of the cell the passage was carved from
+    pub from: Coords,
+    /// Coords of the cell the passage leads to
+    pub to: Coords,
+}
+
+/// An iterator over the passages of a maze in the order they were carved by an algorithm
+///
+/// Returned by [`OrthogonalMazeBuilder::build_steps`](crate::maze::OrthogonalMazeBuilder::build_steps).
+/// Every call to `next` carves one more passage into the [`maze`](Self::maze), so it can be used to
+/// animate the generation, e.g. by taking a step on every tick of a timer. Once all the steps are
+/// taken, the maze is equal to the one returned by
+/// [`OrthogonalMazeBuilder::build`](crate::maze::OrthogonalMazeBuilder::build) for the same
+/// parameters.
+///
+/// # Example
+/// ```
+/// use bevy_knossos::maze::*;
+///
+/// let mut steps = OrthogonalMazeBuilder::new()
+///     .width(4)
+///     .height(4)
+///     .seed(1)
+///     .build_steps()
+///     .unwrap();
+/// // A perfect maze of 16 cells has 15 passages
+/// assert_eq!(steps.len(), 15);
+///
+/// let step = steps.next().unwrap();
+/// assert_ne!(steps.maze()[step.from], Cell::empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CarveSteps {
+    maze: OrthogonalMaze,
+    steps: std::vec::IntoIter<(Coords, Cell)>,
+    generated: Option<OrthogonalMaze>,
+}
+
+impl CarveSteps {
+    /// Returns the steps of carving the `generated` maze into a `blank` one
+    pub(crate) fn new(
+        blank: OrthogonalMaze,
+        steps: Vec<(Coords, Cell)>,
+        generated: OrthogonalMaze,
+    ) -> Self {
+        if steps.is_empty() {
+            return Self {
+                maze: generated,
+                steps: steps.into_iter(),
+                generated: None,
+            };
+        }
+
+        Self {
+            maze: blank,
+            steps: steps.into_iter(),
+            generated: Some(generated),
+        }
+    }
+
+    /// Returns the maze with all the passages carved so far
+    #[must_use]
+    pub const fn maze(&self) -> &OrthogonalMaze {
+        &self.maze
+    }
+
+    /// Consumes the iterator and returns the maze with all the passages carved so far
+    #[must_use]
+    pub fn into_maze(self) -> OrthogonalMaze {
+        self.maze
+    }
+}
+
+impl Iterator for CarveSteps {
+    type Item = CarveStep;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (from, direction) = self.steps.next()?;
+        let to = self
+            .maze
+            .get_grid_mut()
+            .carve_passage(from, direction)
+            .ok()?;
+        // The generated maze also keeps the state of its cells, which the carved one is missing
+        if self.steps.len() == 0
+            && let Some(generated) = self.generated.take()
+        {
+            self.maze = generated;
+        }
+
+        Some(CarveStep { from, to })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.steps.size_hint()
+    }
+}
+
+impl ExactSizeIterator for CarveSteps {}
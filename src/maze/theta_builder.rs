@@ -0,0 +1,153 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::maze::ThetaMaze;
+use crate::maze::algorithms::RecursiveBacktracking;
+use crate::utils::types::Coords;
+
+use super::{errors::BuildError, grid::polar::PolarGrid};
+
+/// A theta maze builder for constructing a circular maze step by step
+///
+/// Passages are carved with the [`RecursiveBacktracking`] algorithm, the only one that supports
+/// polar grids for now.
+pub struct ThetaMazeBuilder {
+    rings: usize,
+    ring_cells: Option<Vec<usize>>,
+    start_coords: Option<Coords>,
+    seed: Option<u64>,
+}
+
+impl ThetaMazeBuilder {
+    /// Returns a new instance of a builder for a maze of 10 rings
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            rings: 10,
+            ring_cells: None,
+            start_coords: None,
+            seed: None,
+        }
+    }
+
+    /// Sets a number of rings and returns itself
+    ///
+    /// The maze has a single cell in its center. Every other ring has a multiple of the cells of
+    /// the ring inside it, where the multiplier is the ring circumference divided by the number
+    /// of inner cells, rounded, so cells stay about as wide as they're tall. For example, the
+    /// first 5 rings have 1, 6, 12, 24 and 24 cells.
+    #[must_use]
+    pub const fn rings(mut self, rings: usize) -> Self {
+        self.rings = rings;
+        self
+    }
+
+    /// Sets the number of cells of every ring, starting from the innermost one, and returns itself
+    ///
+    /// Overrides the number of rings set with [`Self::rings`]. The number of cells in a ring must
+    /// be a multiple of the number of cells in the ring inside it.
+    #[must_use]
+    pub fn ring_cells(mut self, ring_cells: Vec<usize>) -> Self {
+        self.ring_cells = Some(ring_cells);
+        self
+    }
+
+    /// Sets a seed value for deterministic generation and returns itself
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the `(index, ring)` coords of the cell to start carving passages from and returns itself
+    #[must_use]
+    pub fn start_coords(mut self, coords: impl Into<Coords>) -> Self {
+        self.start_coords = Some(coords.into());
+        self
+    }
+
+    /// Builds a theta maze
+    ///
+    /// # Errors
+    /// Fails if the maze has no rings, the ring sizes don't split evenly or the start coords are
+    /// outside of the maze
+    pub fn build(self) -> Result<ThetaMaze, BuildError> {
+        let grid = match self.ring_cells {
+            Some(ring_cells) => {
+                if let Some(reason) = PolarGrid::check_ring_cells(&ring_cells) {
+                    return Err(BuildError::reason(reason));
+                }
+                PolarGrid::with_ring_cells(ring_cells)
+            }
+            None if self.rings == 0 => {
+                return Err(BuildError::reason(
+                    "A theta maze must have at least one ring",
+                ));
+            }
+            None => PolarGrid::new(self.rings),
+        };
+
+        let start_coords = self.start_coords.unwrap_or((0, 0));
+        if !grid.contains(start_coords) {
+            return Err(BuildError::reason(format!(
+                "Start coords {start_coords:?} are outside of the maze"
+            )));
+        }
+
+        let mut rng = self.seed.map_or_else(
+            || {
+                let mut rng = rand::rng();
+                StdRng::from_rng(&mut rng)
+            },
+            StdRng::seed_from_u64,
+        );
+
+        let mut grid = grid;
+        RecursiveBacktracking::generate_polar(&mut grid, start_coords, &mut rng);
+        Ok(ThetaMaze::from_grid(grid))
+    }
+}
+
+impl Default for ThetaMazeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_with_ring_cells() {
+        let maze = ThetaMazeBuilder::new()
+            .ring_cells(vec![3, 6, 6, 12])
+            .start_coords((5, 3))
+            .seed(1)
+            .build()
+            .unwrap();
+
+        assert_eq!(maze.rings(), 4);
+        assert_eq!(maze.ring_cells(0), 3);
+        assert_eq!(maze.ring_cells(3), 12);
+        assert!(maze.is_valid());
+    }
+
+    #[test]
+    fn build_errors() {
+        let reason = |builder: ThetaMazeBuilder| builder.build().unwrap_err().reason;
+
+        assert_eq!(
+            reason(ThetaMazeBuilder::new().rings(0)),
+            "A theta maze must have at least one ring"
+        );
+        assert_eq!(
+            reason(ThetaMazeBuilder::new().ring_cells(vec![1, 0])),
+            "Ring 1 must have at least one cell"
+        );
+        assert_eq!(
+            reason(ThetaMazeBuilder::new().rings(3).start_coords((20, 2))),
+            "Start coords (20, 2) are outside of the maze"
+        );
+    }
+}
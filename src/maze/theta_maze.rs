@@ -0,0 +1,124 @@
+use bevy::ecs::resource::Resource;
+use std::collections::{HashSet, VecDeque};
+
+use crate::utils::types::Coords;
+
+use super::{
+    errors::MazeSaveError,
+    formatters::{Saveable, StringWrapper, ThetaSvg},
+    grid::polar::PolarGrid,
+};
+
+/// A circular maze with cells arranged in concentric rings
+///
+/// Cells are addressed by `(index, ring)` coords, where ring `0` is the innermost one and the index
+/// grows clockwise within a ring. Rings get split into more cells as they grow, so a cell may have
+/// several outward neighbors, but always a single inward one.
+#[derive(Debug, Clone, PartialEq, Eq, Resource)]
+pub struct ThetaMaze {
+    grid: PolarGrid,
+}
+
+impl ThetaMaze {
+    pub(crate) const fn from_grid(grid: PolarGrid) -> Self {
+        Self { grid }
+    }
+
+    pub(crate) const fn grid(&self) -> &PolarGrid {
+        &self.grid
+    }
+
+    /// Returns the number of rings
+    #[must_use]
+    pub const fn rings(&self) -> usize {
+        self.grid.rings()
+    }
+
+    /// Returns the number of cells in a given ring, or `0` if the maze has no such ring
+    #[must_use]
+    pub fn ring_cells(&self, ring: usize) -> usize {
+        self.grid.ring_cells(ring)
+    }
+
+    /// Returns all the cells adjacent to a given one, regardless of the walls between them
+    #[must_use]
+    pub fn neighbors(&self, coords: Coords) -> Vec<Coords> {
+        if self.grid.contains(coords) {
+            self.grid.neighbors(coords)
+        } else {
+            vec![]
+        }
+    }
+
+    /// Returns the cells connected to a given one by carved passages
+    #[must_use]
+    pub fn links(&self, coords: Coords) -> Vec<Coords> {
+        if self.grid.contains(coords) {
+            self.grid.links(coords)
+        } else {
+            vec![]
+        }
+    }
+
+    /// Returns `true` if there is a passage between two given cells
+    #[must_use]
+    pub fn is_linked(&self, a: Coords, b: Coords) -> bool {
+        self.grid.contains(a) && self.grid.contains(b) && self.grid.is_linked(a, b)
+    }
+
+    /// Returns `true` if all the cells are reachable from the center of the maze
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        let start = (0, 0);
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(coords) = queue.pop_front() {
+            for next in self.grid.links(coords) {
+                if visited.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        visited.len() == self.grid.len()
+    }
+
+    /// Returns the maze rendered with a given [`ThetaSvg`] formatter
+    #[must_use]
+    pub fn format(&self, formatter: &ThetaSvg) -> StringWrapper {
+        formatter.format(self)
+    }
+
+    /// Saves the maze rendered with a given [`ThetaSvg`] formatter into a file
+    ///
+    /// # Errors
+    /// Returns a [`MazeSaveError`] if the file could not be written
+    pub fn save(&self, path: &str, formatter: &ThetaSvg) -> Result<String, MazeSaveError> {
+        self.format(formatter).save(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::ThetaMazeBuilder;
+
+    #[test]
+    fn passages_connect_neighbors() {
+        let maze = ThetaMazeBuilder::new().rings(6).seed(3).build().unwrap();
+
+        assert!(maze.is_valid());
+        for ring in 0..maze.rings() {
+            for index in 0..maze.ring_cells(ring) {
+                let neighbors = maze.neighbors((index, ring));
+                for next in maze.links((index, ring)) {
+                    assert!(neighbors.contains(&next));
+                    assert!(maze.is_linked(next, (index, ring)));
+                }
+            }
+        }
+
+        assert!(maze.neighbors((0, 6)).is_empty());
+        assert!(!maze.is_linked((0, 0), (100, 1)));
+    }
+}
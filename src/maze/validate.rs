@@ -1,36 +1,10 @@
 use super::grid::Grid;
-use crate::utils::types::Coords;
-use rand::prelude::*;
 
 /// A utility to validate if a given grid is valid, i.e. all the cells are reachable.
 ///
-/// The recursive backtracker is one of the simplest and most efficient algorithms
-/// for this kind of work. If an algorithm does not visit all the cells, we make a
-/// conclusion that it's not valid.
+/// Joins the cells connected by carved passages with a union-find, so the result doesn't depend on
+/// randomness and the call stack doesn't grow with the maze. If the cells form more than one
+/// connected component, the grid is not valid. Masked-off cells are not taken into account.
 pub fn validate(grid: &Grid) -> bool {
-    let mut visited: Vec<Coords> = Vec::new();
-    visited.push((0, 0));
-    visit((0, 0), grid, &mut visited);
-    visited.len() == grid.width() * grid.height()
-}
-
-fn visit(coords: Coords, grid: &Grid, visited: &mut Vec<Coords>) {
-    let mut dirs = grid.directions().to_vec();
-    dirs.shuffle(&mut rand::rng());
-
-    for dir in dirs {
-        let Ok(next) = grid.get_next_cell_coords(coords, dir) else {
-            continue;
-        };
-        if visited.contains(&next) {
-            continue;
-        }
-
-        if !grid.is_carved(coords, dir) {
-            continue;
-        }
-
-        visited.push(next);
-        visit(next, grid, visited);
-    }
+    grid.components().0 <= 1
 }
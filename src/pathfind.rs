@@ -1,9 +1,11 @@
 use crate::{
-    Cell, CoordsComponent,
+    Cell, Coords, CoordsComponent,
+    maze::OrthogonalMaze,
     utils::types::{Goal, Start},
 };
 use bevy::{platform::collections::HashMap, prelude::*};
-use pathfinding::prelude::astar;
+use pathfinding::prelude::{astar, build_path, dijkstra_all};
+use std::hash::Hash;
 
 #[cfg(not(feature = "single_end"))]
 pub(crate) mod all_ends;
@@ -12,6 +14,9 @@ pub(crate) mod all_ends;
 pub use all_ends::{MazeEnd, MazeEndsPaths, find_maze_ends_paths};
 
 /// Associated cost to the path [`Cell`]. Default is 1
+///
+/// It's the cost of every step out of the cell, so cells like mud or water can be made expensive
+/// to cross and the A* systems of this module route around them when a cheaper way exists.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Component, Reflect)]
 pub struct Cost(pub u32);
 
@@ -27,8 +32,21 @@ pub enum Algorithm {
     /// [Pathfinding A*](https://docs.rs/pathfinding/4.14.0/pathfinding/directed/astar/fn.astar.html)
     #[default]
     Astar,
+    /// [Pathfinding A*](https://docs.rs/pathfinding/4.14.0/pathfinding/directed/astar/fn.astar.html)
+    /// that also moves diagonally around the corners of the maze
+    ///
+    /// A diagonal move is allowed when both passages of a corner between the two cells are open,
+    /// so paths cut across staircases instead of zigzagging. To approximate the `√2` length of a
+    /// diagonal step with integers, costs are measured in tenths of a step: an orthogonal step
+    /// costs `10` times the [`Cost`] of its cell and a diagonal one `14` times.
+    AstarDiagonal,
 }
 
+/// Cost of an orthogonal step with [`Algorithm::AstarDiagonal`]
+const STEP_COST: u32 = 10;
+/// Cost of a diagonal step with [`Algorithm::AstarDiagonal`], roughly `√2` times [`STEP_COST`]
+const DIAGONAL_STEP_COST: u32 = 14;
+
 /// Auxiliary struct that holds knowledge for path finding on each [`Cell`]
 #[derive(Debug, Clone, PartialEq, Eq, Resource, Reflect, Default)]
 pub struct MazePath {
@@ -43,6 +61,30 @@ impl MazePath {
         (coords.coord.0.abs_diff(other.coord.0) + coords.coord.1.abs_diff(other.coord.1)) as u32
     }
 
+    /// Heuristic distance function to maze [`Goal`] for cells with a given lowest [`Cost`]
+    ///
+    /// Every step costs at least `min_cost`, so the estimate never exceeds the real cost of a path,
+    /// which keeps A* optimal even when some cells are cheaper than the default cost.
+    #[must_use]
+    pub const fn weighted_distance(
+        coords: &CoordsComponent,
+        other: &CoordsComponent,
+        min_cost: u32,
+    ) -> u32 {
+        Self::distance(coords, other).saturating_mul(min_cost)
+    }
+
+    /// Octile heuristic distance function to maze [`Goal`] for [`Algorithm::AstarDiagonal`]
+    ///
+    /// Measured in tenths of a step, like the costs of the diagonal search.
+    #[must_use]
+    pub const fn octile_distance(coords: &CoordsComponent, other: &CoordsComponent) -> u32 {
+        let dx = coords.coord.0.abs_diff(other.coord.0) as u32;
+        let dy = coords.coord.1.abs_diff(other.coord.1) as u32;
+        let (min, max) = if dx < dy { (dx, dy) } else { (dy, dx) };
+        STEP_COST * (max - min) + DIAGONAL_STEP_COST * min
+    }
+
     /// Successor function of maze position
     #[must_use]
     pub fn successors(
@@ -53,18 +95,347 @@ impl MazePath {
             return Vec::default();
         };
 
-        open_passages
-            .iter()
-            .map(|c| match c {
-                Cell::EAST => (current.coord.0 + 1, current.coord.1),
-                Cell::NORTH => (current.coord.0, current.coord.1 + 1),
-                Cell::SOUTH => (current.coord.0, current.coord.1 - 1),
-                Cell::WEST => (current.coord.0 - 1, current.coord.1),
-                _ => current.coord,
+        passage_successors(current, **open_passages, cost.cloned().unwrap_or_default())
+    }
+
+    /// Successor function of maze position for [`Algorithm::AstarDiagonal`]
+    #[must_use]
+    pub fn diagonal_successors(
+        current: &CoordsComponent,
+        cells: &HashMap<&CoordsComponent, (&Cell, Option<&Cost>)>,
+    ) -> Vec<(CoordsComponent, u32)> {
+        let Some((open_passages, cost)) = cells.get(&current) else {
+            return Vec::default();
+        };
+
+        diagonal_successors(
+            current,
+            **open_passages,
+            cost.cloned().unwrap_or_default(),
+            |coords| cells.get(coords).map(|(cell, _)| **cell),
+        )
+    }
+}
+
+/// Message written by the pathfinding system every time it finds a path between the [`Start`] and
+/// the [`Goal`] cells, i.e. when [`MazePath`] changes to `Some`
+///
+/// Registered by the [`KnossosPlugin`](crate::KnossosPlugin), so gameplay systems can react to a
+/// solved route, e.g. by starting an animation:
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_knossos::pathfind::PathFound;
+///
+/// fn celebrate(mut found: MessageReader<PathFound>) {
+///     for path in found.read() {
+///         println!("Solved in {} steps at the cost of {}", path.length, path.cost);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Message)]
+pub struct PathFound {
+    /// Coords of the [`Start`] cell
+    pub start: CoordsComponent,
+    /// Coords of the [`Goal`] cell
+    pub goal: CoordsComponent,
+    /// Number of steps between the cells of the path
+    pub length: usize,
+    /// Sum of the [`Cost`] of the steps
+    pub cost: u32,
+}
+
+/// Number of steps from the [`Start`] cell to every cell reachable from it, e.g. to color the
+/// tiles of a maze by their distance
+///
+/// Populated by the [`compute_distance_map`] system. Every passage counts as a single step,
+/// regardless of the [`Cost`] of the cells.
+#[derive(Debug, Clone, PartialEq, Eq, Resource, Reflect, Default)]
+pub struct DistanceMap {
+    /// Distances of the reachable cells
+    pub distances: HashMap<CoordsComponent, u32>,
+}
+
+/// Returns the cells behind the open passages of the `current` cell, each reached at a given cost
+///
+/// Passages leading out of the maze, like its entrance and exit, have no successor.
+fn passage_successors(
+    current: &CoordsComponent,
+    open_passages: Cell,
+    cost: Cost,
+) -> Vec<(CoordsComponent, u32)> {
+    open_passages
+        .iter()
+        .filter_map(|c| step(current, c).map(|next| (next, cost.0)))
+        .collect()
+}
+
+/// Returns the cells behind the open passages of the `current` cell and the cells diagonally
+/// adjacent to it that can be reached around a corner through two open passages
+fn diagonal_successors(
+    current: &CoordsComponent,
+    open_passages: Cell,
+    cost: Cost,
+    cell_at: impl Fn(&CoordsComponent) -> Option<Cell>,
+) -> Vec<(CoordsComponent, u32)> {
+    let mut successors: Vec<(CoordsComponent, u32)> = open_passages
+        .iter()
+        .filter_map(|c| step(current, c).map(|next| (next, cost.0 * STEP_COST)))
+        .collect();
+
+    let around = |first: Cell, second: Cell| {
+        open_passages.contains(first)
+            && step(current, first)
+                .and_then(|next| cell_at(&next))
+                .is_some_and(|cell| cell.contains(second))
+    };
+    for (vertical, horizontal) in [
+        (Cell::NORTH, Cell::EAST),
+        (Cell::NORTH, Cell::WEST),
+        (Cell::SOUTH, Cell::EAST),
+        (Cell::SOUTH, Cell::WEST),
+    ] {
+        let corner = step(current, vertical).and_then(|next| step(&next, horizontal));
+        if let Some(corner) = corner
+            && (around(vertical, horizontal) || around(horizontal, vertical))
+        {
+            successors.push((corner, cost.0 * DIAGONAL_STEP_COST));
+        }
+    }
+    successors
+}
+
+/// Returns the coords of the cell next to the `current` one in a given direction, or `None` if the
+/// step leaves the coordinate space
+fn step(current: &CoordsComponent, direction: Cell) -> Option<CoordsComponent> {
+    let (x, y) = current.coord;
+    let coord = match direction {
+        Cell::EAST => (x.checked_add(1)?, y),
+        Cell::NORTH => (x, y.checked_add(1)?),
+        Cell::SOUTH => (x, y.checked_sub(1)?),
+        Cell::WEST => (x.checked_sub(1)?, y),
+        _ => (x, y),
+    };
+    Some(CoordsComponent { coord })
+}
+
+/// Query data of the cells used by the pathfinding systems
+pub type CellRefs = (
+    Ref<'static, CoordsComponent>,
+    Ref<'static, Cell>,
+    Option<Ref<'static, Cost>>,
+);
+
+/// Owned copy of the open passages and costs of all cells kept between the pathfinding system runs
+///
+/// It's rebuilt only when a cell is added, removed or changed, so large mazes aren't rehashed on
+/// every search.
+#[derive(Default)]
+pub struct CellCache {
+    cells: HashMap<CoordsComponent, (Cell, Cost)>,
+    entities: usize,
+    min_cost: u32,
+}
+
+impl CellCache {
+    /// Rebuilds the cache if any of the `cells` changed since the last system run and returns
+    /// whether it was rebuilt
+    ///
+    /// Must be called on every run, as change detection only looks back to the previous one.
+    pub(crate) fn refresh(&mut self, cells: &Query<CellRefs>, costs_removed: bool) -> bool {
+        let entities = cells.iter().len();
+        let stale = costs_removed
+            || entities != self.entities
+            || cells.iter().any(|(coords, cell, cost)| {
+                coords.is_changed() || cell.is_changed() || cost.is_some_and(|c| c.is_changed())
+            });
+        if !stale {
+            return false;
+        }
+
+        let mut map = HashMap::with_capacity(entities);
+        map.extend(cells.iter().map(|(coords, cell, cost)| {
+            let cost = cost.map(|cost| cost.clone()).unwrap_or_default();
+            (coords.clone(), (*cell, cost))
+        }));
+        self.min_cost = map.values().map(|(_, cost)| cost.0).min().unwrap_or(1);
+        self.cells = map;
+        self.entities = entities;
+        true
+    }
+
+    /// Returns the lowest [`Cost`] among the cached cells
+    pub(crate) const fn min_cost(&self) -> u32 {
+        self.min_cost
+    }
+
+    /// Successor function of maze position over the cached cells
+    pub(crate) fn successors(&self, current: &CoordsComponent) -> Vec<(CoordsComponent, u32)> {
+        self.cells
+            .get(current)
+            .map(|(open_passages, cost)| passage_successors(current, *open_passages, cost.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Diagonal successor function of maze position over the cached cells
+    pub(crate) fn diagonal_successors(
+        &self,
+        current: &CoordsComponent,
+    ) -> Vec<(CoordsComponent, u32)> {
+        self.cells
+            .get(current)
+            .map(|(open_passages, cost)| {
+                diagonal_successors(current, *open_passages, cost.clone(), |coords| {
+                    self.cells.get(coords).map(|(cell, _)| *cell)
+                })
             })
-            .map(|coord| (coord.into(), cost.cloned().unwrap_or_default().0))
-            .collect()
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the number of steps from `origin` to every cell reachable through carved passages
+///
+/// Every passage has the same cost, so the distances are computed with a breadth-first search.
+/// Unreachable cells are not included. Doesn't require a Bevy `App`, so it can be used to build
+/// heat maps or to pick the farthest goal in plain library code.
+#[must_use]
+pub fn distance_field(
+    maze: &OrthogonalMaze,
+    origin: Coords,
+) -> std::collections::HashMap<Coords, u32> {
+    let mut distances = std::collections::HashMap::new();
+    maze.grid().flood_fill(origin, |coords, distance| {
+        distances.insert(coords, distance);
+    });
+    distances
+}
+
+/// Returns the two cells farthest apart from each other and the shortest path connecting them
+///
+/// Uses a double breadth-first search: the first flood finds the cell farthest from an arbitrary
+/// one, and the second one finds the cell farthest from it. In a perfect maze the result is the
+/// diameter of the maze, a natural start and goal of the hardest puzzle. In a maze with loops it is
+/// a cheap approximation. A maze without cells yields `((0, 0), (0, 0), vec![])`.
+#[must_use]
+pub fn longest_path(maze: &OrthogonalMaze) -> (Coords, Coords, Vec<Coords>) {
+    let grid = maze.grid();
+    let Some(origin) = grid.first_active_cell() else {
+        return ((0, 0), (0, 0), Vec::new());
+    };
+
+    let farthest = |from: Coords| {
+        let mut farthest = (from, 0);
+        grid.flood_fill(from, |coords, distance| {
+            if distance > farthest.1 {
+                farthest = (coords, distance);
+            }
+        });
+        farthest.0
+    };
+
+    let a = farthest(origin);
+    let b = farthest(a);
+    let path = grid.shortest_path(a, b).unwrap_or_default();
+    (a, b, path)
+}
+
+/// Returns the shortest path from `start` to `goal` through carved passages, or `None` if the
+/// goal is unreachable
+///
+/// Walks the maze breadth-first, so unlike the A* systems of this module it ignores [`Cost`]
+/// components and doesn't require a Bevy `App`. The path includes both `start` and `goal`, and
+/// crossings of weave mazes are taken into account. Coords outside of the maze yield `None`.
+#[must_use]
+pub fn solve_bfs(maze: &OrthogonalMaze, start: Coords, goal: Coords) -> Option<Vec<Coords>> {
+    maze.grid().shortest_path(start, goal)
+}
+
+/// Returns the shortest paths from `start` to each of the `goals` through carved passages
+///
+/// Runs a single Dijkstra flood from `start` with every step costing 1, ignoring [`Cost`]
+/// components, and reconstructs every path from the map of predecessors, which is much cheaper
+/// than searching for each goal separately. The paths include both `start` and the goal.
+/// Unreachable goals are not included.
+#[must_use]
+pub fn paths_to_many(
+    maze: &OrthogonalMaze,
+    start: Coords,
+    goals: &[Coords],
+) -> std::collections::HashMap<Coords, Vec<Coords>> {
+    let grid = maze.grid();
+    if !grid.contains(start) {
+        return std::collections::HashMap::new();
+    }
+
+    flood_paths(
+        &start,
+        |&coords| grid.steps(coords).map(|next| (next, 1)),
+        goals.iter().copied(),
+    )
+    .into_iter()
+    .map(|(goal, (path, _))| (goal, path))
+    .collect()
+}
+
+/// Returns the cheapest paths and their costs from `start` to each of the reachable `goals`,
+/// flooding the graph given by `successors` only once
+fn flood_paths<N, FN, IN>(
+    start: &N,
+    successors: FN,
+    goals: impl IntoIterator<Item = N>,
+) -> Vec<(N, (Vec<N>, u32))>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u32)>,
+{
+    let parents = dijkstra_all(start, successors);
+
+    goals
+        .into_iter()
+        .filter_map(|goal| {
+            if &goal == start {
+                return Some((goal.clone(), (vec![goal], 0)));
+            }
+
+            let &(_, cost) = parents.get(&goal)?;
+            let path = build_path(&goal, &parents);
+            Some((goal, (path, cost)))
+        })
+        .collect()
+}
+
+/// Floods the maze from the [`Start`] cell and stores the distances in the [`DistanceMap`]
+/// resource
+///
+/// The distances are only recomputed when the [`Start`] cell moves or the cells of the maze change,
+/// so running it every frame is cheap.
+pub fn compute_distance_map(
+    mut commands: Commands,
+    start: Query<(Ref<CoordsComponent>, Ref<Start>), With<Cell>>,
+    cells: Query<CellRefs>,
+    mut removed_costs: RemovedComponents<Cost>,
+    mut cache: Local<CellCache>,
+) {
+    let maze_changed = cache.refresh(&cells, removed_costs.read().count() > 0);
+
+    let Ok((coords, marker)) = start.single() else {
+        return;
+    };
+    if !maze_changed && !coords.is_changed() && !marker.is_added() {
+        return;
     }
+
+    let origin = coords.clone();
+    let mut distances: HashMap<CoordsComponent, u32> = dijkstra_all(&origin, |p| {
+        cache.successors(p).into_iter().map(|(next, _)| (next, 1))
+    })
+    .into_iter()
+    .map(|(coords, (_, distance))| (coords, distance))
+    .collect();
+    distances.insert(origin, 0);
+
+    commands.insert_resource(DistanceMap { distances });
 }
 
 #[cfg(not(tarpaulin_include))]
@@ -88,31 +459,52 @@ pub(crate) fn find_path(
             Changed<Cell>,
         ),
     >,
-    cells: Query<(&CoordsComponent, &Cell, Option<&Cost>)>,
-    _algorithm: Res<Algorithm>,
+    cells: Query<CellRefs>,
+    mut removed_costs: RemovedComponents<Cost>,
+    mut cache: Local<CellCache>,
+    mut found: MessageWriter<PathFound>,
+    algorithm: Res<Algorithm>,
 ) {
+    cache.refresh(&cells, removed_costs.read().count() > 0);
+
     let Ok(start) = start.single().cloned() else {
         return;
     };
     let Ok(goal) = goal.single().cloned() else {
         return;
     };
-    let cells: HashMap<&CoordsComponent, (&Cell, Option<&Cost>)> =
-        cells.iter().map(|(k, v1, v2)| (k, (v1, v2))).collect();
 
-    let path: Option<(Vec<CoordsComponent>, u32)> = astar(
-        &start,
-        |p| MazePath::successors(p, &cells),
-        |p| MazePath::distance(p, &goal),
-        |p| p == &goal,
-    );
+    let path: Option<(Vec<CoordsComponent>, u32)> = match *algorithm {
+        Algorithm::Astar => astar(
+            &start,
+            |p| cache.successors(p),
+            |p| MazePath::weighted_distance(p, &goal, cache.min_cost()),
+            |p| p == &goal,
+        ),
+        Algorithm::AstarDiagonal => astar(
+            &start,
+            |p| cache.diagonal_successors(p),
+            |p| MazePath::octile_distance(p, &goal).saturating_mul(cache.min_cost()),
+            |p| p == &goal,
+        ),
+    };
 
+    if let Some((steps, cost)) = &path {
+        found.write(PathFound {
+            start,
+            goal,
+            length: steps.len().saturating_sub(1),
+            cost: *cost,
+        });
+    }
     commands.insert_resource(MazePath { path });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::maze::OrthogonalMazeBuilder;
+    use rand::{SeedableRng, rngs::StdRng};
 
     #[test]
     fn coords_distance() {
@@ -124,6 +516,157 @@ mod tests {
         assert_eq!(distance, 20);
     }
 
+    #[test]
+    fn weighted_distance_scales_by_min_cost() {
+        let start: CoordsComponent = (0, 0).into();
+        let goal: CoordsComponent = (3, 4).into();
+
+        assert_eq!(MazePath::weighted_distance(&start, &goal, 1), 7);
+        assert_eq!(MazePath::weighted_distance(&start, &goal, 3), 21);
+        assert_eq!(MazePath::weighted_distance(&start, &goal, 0), 0);
+    }
+
+    #[test]
+    fn costly_corridor_is_avoided() {
+        // The goal is 2 steps to the east through a costly cell or 6 steps around it
+        let mut maze = OrthogonalMaze::new(3, 3);
+        for (from, direction) in [
+            ((0, 0), Cell::EAST),
+            ((1, 0), Cell::EAST),
+            ((0, 0), Cell::SOUTH),
+            ((0, 1), Cell::SOUTH),
+            ((0, 2), Cell::EAST),
+            ((1, 2), Cell::EAST),
+            ((2, 2), Cell::NORTH),
+            ((2, 1), Cell::NORTH),
+        ] {
+            maze.carve(from, direction).unwrap();
+        }
+        // Pathfinding components are Y-up, while the maze grid is Y-down
+        let flip = |(x, y): Coords| CoordsComponent::new(x, 2 - y);
+
+        let mut app = App::new();
+        app.init_resource::<Algorithm>()
+            .add_message::<PathFound>()
+            .add_systems(Update, find_path);
+        for (coords, cell) in maze.iter() {
+            let mut entity = app.world_mut().spawn((flip(coords), *cell));
+            match coords {
+                (0, 0) => entity.insert(Start),
+                (2, 0) => entity.insert(Goal),
+                (1, 0) => entity.insert(Cost(10)),
+                (0, 2) => entity.insert(Cost(0)),
+                _ => &mut entity,
+            };
+        }
+
+        app.update();
+        let (path, cost) = app.world().resource::<MazePath>().path.clone().unwrap();
+        assert_eq!(cost, 5);
+        assert_eq!(path.len(), 7);
+        assert!(!path.contains(&flip((1, 0))));
+    }
+
+    #[test]
+    fn diagonal_path_cuts_across_a_staircase() {
+        let mut maze = OrthogonalMaze::new(3, 3);
+        for (from, direction) in [
+            ((0, 0), Cell::EAST),
+            ((1, 0), Cell::SOUTH),
+            ((1, 1), Cell::EAST),
+            ((2, 1), Cell::SOUTH),
+        ] {
+            maze.carve(from, direction).unwrap();
+        }
+        // Pathfinding components are Y-up, while the maze grid is Y-down
+        let flip = |(x, y): Coords| CoordsComponent::new(x, 2 - y);
+        let solve = |algorithm: Algorithm| {
+            let mut app = App::new();
+            app.insert_resource(algorithm)
+                .add_message::<PathFound>()
+                .add_systems(Update, find_path);
+            for (coords, cell) in maze.iter() {
+                let mut entity = app.world_mut().spawn((flip(coords), *cell));
+                match coords {
+                    (0, 0) => entity.insert(Start),
+                    (2, 2) => entity.insert(Goal),
+                    _ => &mut entity,
+                };
+            }
+            app.update();
+            app.world().resource::<MazePath>().path.clone().unwrap()
+        };
+
+        let (staircase, steps) = solve(Algorithm::Astar);
+        let (diagonal, cost) = solve(Algorithm::AstarDiagonal);
+
+        assert_eq!((staircase.len(), steps), (5, 4));
+        assert_eq!(diagonal, vec![flip((0, 0)), flip((1, 1)), flip((2, 2))]);
+        assert_eq!(cost, 2 * DIAGONAL_STEP_COST);
+        assert!(cost < steps * STEP_COST);
+    }
+
+    #[test]
+    fn octile_distance_prefers_diagonals() {
+        let start: CoordsComponent = (0, 0).into();
+
+        assert_eq!(MazePath::octile_distance(&start, &(3, 0).into()), 30);
+        assert_eq!(MazePath::octile_distance(&start, &(2, 2).into()), 28);
+        assert_eq!(MazePath::octile_distance(&(1, 5).into(), &start), 54);
+    }
+
+    #[test]
+    fn diagonal_successors_need_an_open_corner() {
+        let start = CoordsComponent::new(0, 0);
+        let east = CoordsComponent::new(1, 0);
+        let north = CoordsComponent::new(0, 1);
+        // The passages turn from the start to the east and then to the north
+        let (first, second) = (Cell::EAST, Cell::NORTH | Cell::WEST);
+        let cells = [(&start, (&first, None::<&Cost>)), (&east, (&second, None))]
+            .into_iter()
+            .collect();
+
+        let successors = MazePath::diagonal_successors(&start, &cells);
+
+        assert_eq!(
+            successors,
+            vec![
+                (east.clone(), STEP_COST),
+                ((1, 1).into(), DIAGONAL_STEP_COST)
+            ]
+        );
+        assert!(MazePath::diagonal_successors(&north, &cells).is_empty());
+    }
+
+    #[test]
+    fn diagonal_successors_skip_border_openings() {
+        let start = CoordsComponent::new(0, 0);
+        let east = CoordsComponent::new(1, 0);
+        // The start has openings to the south and west of the bottom-left corner of the maze, and
+        // the passage to the east cell turns both north and out of the maze to the south
+        let (first, second) = (
+            Cell::SOUTH | Cell::WEST | Cell::EAST,
+            Cell::NORTH | Cell::SOUTH | Cell::WEST,
+        );
+        let cells = [(&start, (&first, None::<&Cost>)), (&east, (&second, None))]
+            .into_iter()
+            .collect();
+
+        let successors = MazePath::diagonal_successors(&start, &cells);
+
+        assert_eq!(
+            successors,
+            vec![
+                (east.clone(), STEP_COST),
+                ((1, 1).into(), DIAGONAL_STEP_COST)
+            ]
+        );
+        assert_eq!(
+            MazePath::diagonal_successors(&east, &cells),
+            vec![((1, 1).into(), STEP_COST), (start.clone(), STEP_COST)]
+        );
+    }
+
     #[test]
     fn successors() {
         let start: CoordsComponent = (12, 12).into();
@@ -152,6 +695,262 @@ mod tests {
         assert_eq!(successor[1], ((9, 10).into(), 2));
     }
 
+    #[test]
+    fn successors_skip_border_openings() {
+        let start: CoordsComponent = (0, 0).into();
+        // Openings to the south and west of the bottom-left cell lead out of the maze
+        let cell = Cell::SOUTH | Cell::WEST | Cell::EAST;
+        let key = CoordsComponent::new(0, 0);
+        let cells = std::iter::once((&key, (&cell, None::<&Cost>))).collect();
+
+        let successor = MazePath::successors(&start, &cells);
+
+        assert_eq!(successor, vec![((1, 0).into(), 1)]);
+    }
+
+    #[test]
+    fn distance_field_from_origin() {
+        let mut maze = OrthogonalMaze::new(3, 2);
+        let grid = maze.get_grid_mut();
+        grid.carve_passage((0, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::EAST).unwrap();
+        grid.carve_passage((1, 0), Cell::SOUTH).unwrap();
+        grid.carve_passage((2, 1), Cell::NORTH).unwrap();
+
+        let field = distance_field(&maze, (1, 0));
+
+        assert_eq!(field.len(), 5);
+        assert_eq!(field[&(1, 0)], 0);
+        assert_eq!(field[&(0, 0)], 1);
+        assert_eq!(field[&(2, 0)], 1);
+        assert_eq!(field[&(1, 1)], 1);
+        assert_eq!(field[&(2, 1)], 2);
+        assert!(!field.contains_key(&(0, 1)));
+    }
+
+    #[test]
+    fn longest_path_is_diameter() {
+        let maze = OrthogonalMazeBuilder::new()
+            .width(6)
+            .height(5)
+            .seed(7)
+            .build()
+            .unwrap();
+        let diameter = maze
+            .iter()
+            .flat_map(|(coords, _)| distance_field(&maze, coords).into_values())
+            .max()
+            .unwrap();
+
+        let (a, b, path) = longest_path(&maze);
+
+        assert_eq!(path.len() as u32 - 1, diameter);
+        assert_eq!(path.first(), Some(&a));
+        assert_eq!(path.last(), Some(&b));
+        assert_eq!(distance_field(&maze, a)[&b], diameter);
+    }
+
+    #[test]
+    fn bfs_matches_astar_length() {
+        let mut maze = OrthogonalMazeBuilder::new()
+            .width(12)
+            .height(9)
+            .seed(11)
+            .build()
+            .unwrap();
+        // Loops give the search several paths to choose from
+        maze.braid(&mut StdRng::seed_from_u64(11), 0.5);
+        let (start, goal) = ((0, 0), (11, 8));
+
+        let bfs = solve_bfs(&maze, start, goal).unwrap();
+        let (_, astar_cost) = astar(
+            &start,
+            |&coords| {
+                let grid = maze.grid();
+                grid.neighbor_coords(coords)
+                    .into_iter()
+                    .filter(move |&(dir, _)| grid.is_carved(coords, dir))
+                    .map(|(_, next)| (next, 1))
+            },
+            |&(x, y)| (x.abs_diff(goal.0) + y.abs_diff(goal.1)) as u32,
+            |&coords| coords == goal,
+        )
+        .unwrap();
+
+        assert_eq!(bfs.len() as u32 - 1, astar_cost);
+        assert_eq!(bfs.first(), Some(&start));
+        assert_eq!(bfs.last(), Some(&goal));
+        assert!(maze.is_valid_path(&bfs).is_ok());
+    }
+
+    #[test]
+    fn bfs_without_path() {
+        let maze = OrthogonalMaze::new(3, 3);
+
+        assert_eq!(solve_bfs(&maze, (0, 0), (2, 2)), None);
+        assert_eq!(solve_bfs(&maze, (1, 1), (1, 1)), Some(vec![(1, 1)]));
+        assert_eq!(solve_bfs(&maze, (0, 0), (3, 0)), None);
+    }
+
+    #[test]
+    fn paths_to_many_match_astar() {
+        let mut maze = OrthogonalMazeBuilder::new()
+            .width(10)
+            .height(8)
+            .seed(5)
+            .build()
+            .unwrap();
+        maze.braid(&mut StdRng::seed_from_u64(5), 0.3);
+        let start = (4, 3);
+        let mut goals: Vec<Coords> = maze.ends().into_iter().map(|(coords, _)| coords).collect();
+        goals.extend([start, (10, 0)]);
+
+        let paths = paths_to_many(&maze, start, &goals);
+
+        assert_eq!(paths.len(), goals.len() - 1);
+        assert_eq!(paths[&start], vec![start]);
+        assert!(!paths.contains_key(&(10, 0)));
+        for goal in &goals[..goals.len() - 1] {
+            let (_, cost) = astar(
+                &start,
+                |&coords| maze.grid().steps(coords).map(|next| (next, 1)),
+                |&(x, y)| (x.abs_diff(goal.0) + y.abs_diff(goal.1)) as u32,
+                |coords| coords == goal,
+            )
+            .unwrap();
+
+            let path = &paths[goal];
+            assert_eq!(path.len() as u32 - 1, cost);
+            assert_eq!(path.first(), Some(&start));
+            assert_eq!(path.last(), Some(goal));
+            assert!(maze.is_valid_path(path).is_ok());
+        }
+    }
+
+    #[test]
+    fn cached_cells_find_shortest_paths_on_medium_maze() {
+        let (width, height) = (40, 30);
+        let maze = OrthogonalMazeBuilder::new()
+            .width(width)
+            .height(height)
+            .seed(11)
+            .build()
+            .unwrap();
+        // Pathfinding components are Y-up, while the maze grid is Y-down
+        let flip = |(x, y): Coords| CoordsComponent::new(x, height - 1 - y);
+        let goal = (width - 1, height - 1);
+
+        let mut app = App::new();
+        app.init_resource::<Algorithm>()
+            .add_message::<PathFound>()
+            .add_systems(Update, find_path);
+        for (coords, cell) in maze.iter() {
+            let mut entity = app.world_mut().spawn((flip(coords), *cell));
+            if coords == (0, 0) {
+                entity.insert(Start);
+            }
+            if coords == goal {
+                entity.insert(Goal);
+            }
+        }
+
+        app.update();
+        let expected = maze.distance_between((0, 0), goal).unwrap();
+        let path = app.world().resource::<MazePath>().path.clone().unwrap();
+        assert_eq!(path.1, expected);
+        assert_eq!(path.0.len() as u32, expected + 1);
+        assert_eq!(path.0.last(), Some(&flip(goal)));
+
+        let messages = app.world().resource::<Messages<PathFound>>();
+        let found: Vec<&PathFound> = messages.iter_current_update_messages().collect();
+        assert_eq!(
+            found,
+            [&PathFound {
+                start: flip((0, 0)),
+                goal: flip(goal),
+                length: expected as usize,
+                cost: expected,
+            }]
+        );
+
+        app.update();
+        assert_eq!(app.world().resource::<MazePath>().path, Some(path));
+        // The path wasn't searched for again, so no new message is written
+        let messages = app.world().resource::<Messages<PathFound>>();
+        assert_eq!(messages.iter_current_update_messages().count(), 0);
+
+        #[cfg(not(feature = "single_end"))]
+        {
+            app.add_systems(Update, find_maze_ends_paths);
+            app.update();
+
+            let ends = app.world().resource::<MazeEndsPaths>();
+            assert_eq!(ends.paths.len(), maze.ends().len());
+            for ((_, end), (_, cost)) in &ends.paths {
+                let end = (end.0, height - 1 - end.1);
+                assert_eq!(Some(*cost), maze.distance_between((0, 0), end));
+            }
+        }
+    }
+
+    #[test]
+    fn distance_map_follows_start() {
+        let (width, height) = (8, 6);
+        let maze = OrthogonalMazeBuilder::new()
+            .width(width)
+            .height(height)
+            .seed(3)
+            .build()
+            .unwrap();
+        // Pathfinding components are Y-up, while the maze grid is Y-down
+        let flip = |(x, y): Coords| CoordsComponent::new(x, height - 1 - y);
+        let expected = |origin: Coords| -> HashMap<CoordsComponent, u32> {
+            distance_field(&maze, origin)
+                .into_iter()
+                .map(|(coords, distance)| (flip(coords), distance))
+                .collect()
+        };
+
+        let mut app = App::new();
+        app.init_resource::<DistanceMap>()
+            .add_systems(Update, compute_distance_map);
+        let mut entities = HashMap::new();
+        for (coords, cell) in maze.iter() {
+            let entity = app.world_mut().spawn((flip(coords), *cell)).id();
+            entities.insert(coords, entity);
+        }
+        app.world_mut().entity_mut(entities[&(0, 0)]).insert(Start);
+
+        app.update();
+        let distances = &app.world().resource::<DistanceMap>().distances;
+        assert_eq!(distances.len(), width * height);
+        assert_eq!(distances, &expected((0, 0)));
+
+        // Nothing changed, so the map is left as is
+        app.world_mut()
+            .resource_mut::<DistanceMap>()
+            .distances
+            .clear();
+        app.update();
+        assert!(app.world().resource::<DistanceMap>().distances.is_empty());
+
+        app.world_mut()
+            .entity_mut(entities[&(0, 0)])
+            .remove::<Start>();
+        app.world_mut().entity_mut(entities[&(5, 4)]).insert(Start);
+        app.update();
+        assert_eq!(
+            app.world().resource::<DistanceMap>().distances,
+            expected((5, 4))
+        );
+    }
+
+    #[test]
+    fn longest_path_of_empty_maze() {
+        let maze = OrthogonalMaze::new(0, 0);
+        assert_eq!(longest_path(&maze), ((0, 0), (0, 0), Vec::new()));
+    }
+
     #[test]
     fn empty_successors() {
         let goal: CoordsComponent = (0, 0).into();
@@ -1,10 +1,9 @@
 use crate::{
     Coords, CoordsComponent, Start,
     maze::Cell,
-    pathfind::{Cost, MazePath},
+    pathfind::{CellCache, CellRefs, Cost, flood_paths},
 };
 use bevy::{platform::collections::HashMap, prelude::*};
-use pathfinding::prelude::astar;
 
 /// Auxiliary struct that holds knowledge for path finding on each [`Cell`]
 #[derive(Debug, Clone, PartialEq, Eq, Resource, Default, Reflect)]
@@ -31,49 +30,40 @@ pub struct MazeEnd;
 /// Creates resource [`MazeEndsPaths`] that defines paths for all ends in the maze.
 /// This function should be called on demand and is not scheduled to run.
 ///
-/// # Warning
-/// This operation is quite slow for large mazes, as it needs to pathfind over all ends.
-/// issue
+/// All the paths are reconstructed from a single Dijkstra flood from the [`Start`], so the cost
+/// doesn't grow with the number of ends.
 #[cfg(not(tarpaulin_include))]
 pub fn find_maze_ends_paths(
     mut commands: Commands,
     start: Query<&CoordsComponent, (With<Cell>, With<Start>)>,
-    cells: Query<(Entity, &CoordsComponent, &Cell, Option<&Cost>)>,
+    ends: Query<(Entity, &CoordsComponent, &Cell)>,
+    cells: Query<CellRefs>,
+    mut removed_costs: RemovedComponents<Cost>,
+    mut cache: Local<CellCache>,
 ) {
+    cache.refresh(&cells, removed_costs.read().count() > 0);
+
     let Ok(start) = start.single().cloned() else {
         return;
     };
 
-    let mut ends: Vec<((usize, usize), &Cell)> = Vec::default();
+    let mut goals: Vec<Coords> = Vec::default();
 
-    for (entity, coords, cell, ..) in &cells {
+    for (entity, coords, cell) in &ends {
         if cell.is_end_sq() {
             commands.entity(entity).insert(MazeEnd);
-            ends.push((coords.coord, cell));
+            goals.push(coords.coord);
         }
     }
 
-    let cells: HashMap<&CoordsComponent, (&Cell, Option<&Cost>)> = cells
-        .iter()
-        .map(|(_entity, k, v1, v2)| (k, (v1, v2)))
-        .collect();
-
-    let paths = ends
-        .into_iter()
-        .filter_map(|(goal, _cell)| {
-            let goal_comp: CoordsComponent = goal.into();
-
-            Some((
-                (start.clone().into(), goal),
-                astar(
-                    &start,
-                    |p| MazePath::successors(p, &cells),
-                    |p| MazePath::distance(p, &goal_comp),
-                    |p| p == &goal_comp,
-                )?,
-            ))
-        })
-        .collect();
+    let paths = flood_paths(
+        &start,
+        |p| cache.successors(p),
+        goals.into_iter().map(CoordsComponent::from),
+    )
+    .into_iter()
+    .map(|(goal, path)| ((start.coord, goal.coord), path))
+    .collect();
 
     commands.insert_resource(MazeEndsPaths { paths });
 }
@@ -0,0 +1,214 @@
+use crate::{Cell, CellSize, Coords, CoordsComponent, KnossosPlugin, maze::OrthogonalMaze};
+use bevy::{
+    app::{App, Plugin, Startup},
+    ecs::system::SystemId,
+    prelude::*,
+};
+
+/// Plugin spawning an entity for every cell of the [`OrthogonalMaze`] resource on startup
+///
+/// Returned by [`KnossosPlugin::with_auto_spawn`]. It adds [`KnossosPlugin`], inserts the given
+/// [`CellSize`] and runs [`spawn_maze_tiles`] on [`Startup`]. The maze itself has to be inserted as
+/// a resource by the app, so it can be built with any parameters of
+/// [`OrthogonalMazeBuilder`](crate::maze::OrthogonalMazeBuilder).
+///
+/// Spawning tiles automatically saves the boilerplate of iterating over the maze, but it's less
+/// flexible than spawning them manually: tiles are spawned once on startup in a fixed layout and
+/// only get the components listed in [`spawn_maze_tiles`], so anything visual is left to a
+/// [`TileSpawner`]. Apps that regenerate mazes at runtime, spawn them in chunks or use a tilemap
+/// crate are better off spawning their tiles manually.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_knossos::{CellSize, KnossosPlugin, maze::*, spawn::*};
+///
+/// let maze = OrthogonalMazeBuilder::new().width(5).height(5).build().unwrap();
+///
+/// let mut app = App::new();
+/// // Sprites or meshes are usually inserted here, e.g. with an `AssetServer`
+/// let spawner = app.register_system(|In(tile): In<SpawnedTile>, mut commands: Commands| {
+///     let name = format!("tile_{}", tile.cell.to_bits_str());
+///     commands.entity(tile.entity).insert(Name::new(name));
+/// });
+/// app.insert_resource(maze)
+///     .insert_resource(TileSpawner(spawner))
+///     .add_plugins(KnossosPlugin::with_auto_spawn(CellSize(45.)));
+/// app.update();
+///
+/// let world = app.world_mut();
+/// assert_eq!(world.query::<(&Cell, &Name)>().iter(world).count(), 25);
+/// ```
+pub struct AutoSpawnPlugin {
+    cell_size: f32,
+}
+
+impl KnossosPlugin {
+    /// Returns a plugin that also spawns the tiles of the [`OrthogonalMaze`] resource on startup
+    ///
+    /// Read more about the trade-offs of spawning tiles automatically in [`AutoSpawnPlugin`].
+    #[must_use]
+    pub const fn with_auto_spawn(cell_size: CellSize) -> AutoSpawnPlugin {
+        AutoSpawnPlugin {
+            cell_size: cell_size.0,
+        }
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+impl Plugin for AutoSpawnPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(KnossosPlugin)
+            .insert_resource(CellSize(self.cell_size))
+            .add_systems(Startup, spawn_maze_tiles);
+    }
+}
+
+/// Input of a [`TileSpawner`] system describing a tile that was just spawned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpawnedTile {
+    /// Entity of the tile
+    pub entity: Entity,
+    /// Coords of the cell the tile represents in the maze, which are Y-down unlike the
+    /// [`CoordsComponent`] of the tile
+    pub coords: Coords,
+    /// Passages of the cell
+    pub cell: Cell,
+}
+
+/// Resource holding a user-provided system that's run for every tile spawned by
+/// [`spawn_maze_tiles`]
+///
+/// The system takes a [`SpawnedTile`] as its input and usually inserts a sprite or a mesh into its
+/// entity. Being a regular system, it can access any other resources, e.g. an `AssetServer` or
+/// preloaded handles. Register it with `App::register_system` or `Commands::register_system`.
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct TileSpawner(pub SystemId<In<SpawnedTile>>);
+
+/// Spawns an entity for every cell of the [`OrthogonalMaze`] resource
+///
+/// Each entity gets a [`CoordsComponent`], a [`Cell`] and a [`Transform`] placing the cell on a
+/// grid of [`CellSize`] spacing: the cell at `(0, 0)` is at the origin, `x` grows to the right and
+/// `y` grows downwards, i.e. towards negative world `y`. The [`TileSpawner`] system, if there's
+/// one, is run for every spawned entity. Does nothing without a maze resource.
+///
+/// Like the pathfinding systems, the [`CoordsComponent`] is Y-up, so the maze cell at `(x, y)`
+/// gets `(x, height - 1 - y)`. [`SpawnedTile::coords`] keeps the maze coords.
+pub fn spawn_maze_tiles(
+    mut commands: Commands,
+    maze: Option<Res<OrthogonalMaze>>,
+    cell_size: Option<Res<CellSize>>,
+    spawner: Option<Res<TileSpawner>>,
+) {
+    let Some(maze) = maze else {
+        return;
+    };
+    let size = cell_size.map_or(1., |cell_size| cell_size.0);
+
+    for (coords, cell) in maze.iter() {
+        let entity = commands
+            .spawn((
+                CoordsComponent::new(coords.0, maze.height() - 1 - coords.1),
+                *cell,
+                Transform::from_xyz(coords.0 as f32 * size, -(coords.1 as f32) * size, 0.),
+            ))
+            .id();
+        if let Some(spawner) = &spawner {
+            commands.run_system_with(
+                spawner.0,
+                SpawnedTile {
+                    entity,
+                    coords,
+                    cell: *cell,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::OrthogonalMazeBuilder;
+
+    /// Marks the tiles visited by a [`TileSpawner`]
+    #[derive(Component)]
+    struct Visited(Coords);
+
+    #[test]
+    fn spawns_a_tile_for_every_cell() {
+        let maze = OrthogonalMazeBuilder::new()
+            .width(4)
+            .height(3)
+            .seed(1)
+            .build()
+            .unwrap();
+        let mut app = App::new();
+        let spawner = app.register_system(|In(tile): In<SpawnedTile>, mut commands: Commands| {
+            commands.entity(tile.entity).insert(Visited(tile.coords));
+        });
+        app.insert_resource(maze.clone())
+            .insert_resource(CellSize(10.))
+            .insert_resource(TileSpawner(spawner))
+            .add_systems(Startup, spawn_maze_tiles);
+
+        app.update();
+        let world = app.world_mut();
+        let mut tiles = world.query::<(&CoordsComponent, &Cell, &Transform, &Visited)>();
+        assert_eq!(tiles.iter(world).count(), 12);
+        for (coords, cell, transform, visited) in tiles.iter(world) {
+            let (x, y) = visited.0;
+            assert_eq!(coords.xy(), (x, 2 - y));
+            assert_eq!(cell, &maze[(x, y)]);
+            assert_eq!(
+                transform.translation,
+                Vec3::new(x as f32 * 10., -(y as f32) * 10., 0.)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "pathfinding")]
+    fn pathfinding_follows_the_passages_of_spawned_tiles() {
+        use crate::{
+            pathfind::MazePath,
+            utils::types::{Goal, Start},
+        };
+
+        let maze = OrthogonalMazeBuilder::new()
+            .width(5)
+            .height(4)
+            .seed(3)
+            .build()
+            .unwrap();
+        let mut app = App::new();
+        let spawner = app.register_system(|In(tile): In<SpawnedTile>, mut commands: Commands| {
+            match tile.coords {
+                (0, 0) => commands.entity(tile.entity).insert(Start),
+                (4, 3) => commands.entity(tile.entity).insert(Goal),
+                _ => return,
+            };
+        });
+        app.insert_resource(maze.clone())
+            .insert_resource(TileSpawner(spawner))
+            .add_plugins(KnossosPlugin::with_auto_spawn(CellSize(1.)));
+
+        app.update();
+        let (path, _) = app.world().resource::<MazePath>().path.clone().unwrap();
+        let path: Vec<Coords> = path
+            .iter()
+            .map(|coords| (coords.xy().0, 3 - coords.xy().1))
+            .collect();
+        assert_eq!(Some(path), maze.solve((0, 0), (4, 3)));
+    }
+
+    #[test]
+    fn nothing_is_spawned_without_a_maze() {
+        let mut app = App::new();
+        app.add_systems(Startup, spawn_maze_tiles);
+
+        app.update();
+        let world = app.world_mut();
+        assert_eq!(world.query::<&CoordsComponent>().iter(world).count(), 0);
+    }
+}
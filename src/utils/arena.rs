@@ -5,6 +5,7 @@ pub struct ArenaTree {
 struct Node {
     _id: NodeId,
     parent: Option<NodeId>,
+    size: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -21,29 +22,41 @@ impl ArenaTree {
         self.nodes.push(Node {
             _id: next_idx,
             parent: None,
+            size: 1,
         });
 
         next_idx
     }
 
+    /// Joins the trees of two nodes, hanging the smaller tree under the root of the larger one so
+    /// the trees stay shallow
     pub fn connect(&mut self, id1: NodeId, id2: NodeId) {
         let (Some(root1), Some(root2)) = (self.root(id1), self.root(id2)) else {
             return;
         };
-
-        if let Some(node) = self.nodes.get_mut(root2.0) {
-            node.parent = Some(root1);
+        if root1 == root2 {
+            return;
         }
+
+        let (parent, child) = if self.nodes[root1.0].size < self.nodes[root2.0].size {
+            (root2, root1)
+        } else {
+            (root1, root2)
+        };
+        self.nodes[child.0].parent = Some(parent);
+        self.nodes[parent.0].size += self.nodes[child.0].size;
     }
 
     pub fn connected(&self, id1: NodeId, id2: NodeId) -> bool {
         self.root(id1) == self.root(id2) && self.root(id1).is_some()
     }
 
-    fn root(&self, id: NodeId) -> Option<NodeId> {
-        let node = self.nodes.get(id.0)?;
-
-        node.parent.map_or(Some(id), |parent| self.root(parent))
+    pub fn root(&self, id: NodeId) -> Option<NodeId> {
+        let mut id = id;
+        while let Some(parent) = self.nodes.get(id.0)?.parent {
+            id = parent;
+        }
+        Some(id)
     }
 }
 
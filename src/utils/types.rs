@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::maze::{Cell, Direction};
+
 use bevy::{
     ecs::{component::Component, resource::Resource},
     math::{U8Vec2, U64Vec2},
@@ -69,6 +71,49 @@ impl From<U64Vec2> for CoordsComponent {
     }
 }
 
+/// Returns the coords of the cell next to `coords` in a given direction of an orthogonal maze
+///
+/// Returns `None` if the direction isn't a single one of [`Cell::NORTH`], [`Cell::SOUTH`],
+/// [`Cell::EAST`] or [`Cell::WEST`], or if the neighbor would be out of the top or left edge. Other
+/// edges depend on the maze size, so they are left to the caller.
+///
+/// # Example
+/// ```
+/// use bevy_knossos::{maze::Cell, neighbor};
+///
+/// assert_eq!(neighbor((2, 2), Cell::NORTH), Some((2, 1)));
+/// assert_eq!(neighbor((0, 2), Cell::WEST), None);
+/// ```
+#[must_use]
+pub const fn neighbor(coords: Coords, direction: Cell) -> Option<Coords> {
+    match Direction::from_cell(direction) {
+        Some(direction) => direction.step(coords, usize::MAX, usize::MAX),
+        None => None,
+    }
+}
+
+/// Returns the direction leading from cell `from` to an adjacent cell `to` of an orthogonal maze
+///
+/// Returns `None` if the cells aren't adjacent, so that it's the inverse of [`neighbor`].
+///
+/// # Example
+/// ```
+/// use bevy_knossos::{direction_between, maze::Cell};
+///
+/// assert_eq!(direction_between((2, 2), (3, 2)), Some(Cell::EAST));
+/// assert_eq!(direction_between((2, 2), (3, 3)), None);
+/// ```
+#[must_use]
+pub const fn direction_between((x1, y1): Coords, (x2, y2): Coords) -> Option<Cell> {
+    if x1 == x2 && y1.abs_diff(y2) == 1 {
+        Some(if y2 > y1 { Cell::SOUTH } else { Cell::NORTH })
+    } else if y1 == y2 && x1.abs_diff(x2) == 1 {
+        Some(if x2 > x1 { Cell::EAST } else { Cell::WEST })
+    } else {
+        None
+    }
+}
+
 /// Auxiliary Bevy component that holds the Start Coords of Pathfinding
 #[derive(Clone, Debug, PartialEq, Eq, Reflect, Component)]
 pub struct Start;
@@ -115,4 +160,33 @@ mod tests {
 
         assert_eq!(component, expected);
     }
+
+    #[test]
+    fn neighbors_in_all_directions() {
+        let coords = (5, 5);
+
+        assert_eq!(neighbor(coords, Cell::NORTH), Some((5, 4)));
+        assert_eq!(neighbor(coords, Cell::SOUTH), Some((5, 6)));
+        assert_eq!(neighbor(coords, Cell::WEST), Some((4, 5)));
+        assert_eq!(neighbor(coords, Cell::EAST), Some((6, 5)));
+        assert_eq!(neighbor((0, 0), Cell::NORTH), None);
+        assert_eq!(neighbor((0, 0), Cell::WEST), None);
+        assert_eq!(neighbor(coords, Cell::NORTH | Cell::EAST), None);
+        assert_eq!(neighbor(coords, Cell::empty()), None);
+    }
+
+    #[test]
+    fn direction_between_adjacent_cells() {
+        let coords = (5, 5);
+
+        for direction in [Cell::NORTH, Cell::SOUTH, Cell::WEST, Cell::EAST] {
+            let next = neighbor(coords, direction).unwrap();
+            assert_eq!(direction_between(coords, next), Some(direction));
+        }
+        assert_eq!(direction_between(coords, coords), None);
+        assert_eq!(direction_between(coords, (6, 6)), None);
+        assert_eq!(direction_between(coords, (7, 5)), None);
+        assert_eq!(direction_between(coords, (5, 3)), None);
+        assert_eq!(direction_between((0, 0), (usize::MAX, 0)), None);
+    }
 }
@@ -0,0 +1,50 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::fixture::TempDir;
+
+#[test]
+fn convert_binary_to_ascii() {
+    let output_dir = TempDir::new().unwrap();
+    let path = |name: &str| format!("{}/{name}", output_dir.path().display());
+    let generate = |output: &[&str]| {
+        let mut cmd = cargo_bin_cmd!();
+        cmd.args(["generate", "--seed", "7", "-W", "6", "-H", "4"])
+            .args(output)
+            .assert()
+            .success();
+    };
+
+    generate(&["binary", "--output-path", &path("maze.bin")]);
+    generate(&["ascii", "-T=broad", "--output-path", &path("generated.txt")]);
+    let mut cmd = cargo_bin_cmd!();
+    cmd.args([
+        "convert",
+        "--input-path",
+        &path("maze.bin"),
+        "ascii",
+        "-T=broad",
+    ])
+    .args(["--output-path", &path("converted.txt")])
+    .assert()
+    .success()
+    .stdout(format!(
+        "Maze was successfully written to a file: {}\n",
+        path("converted.txt")
+    ));
+
+    let generated = std::fs::read_to_string(path("generated.txt")).unwrap();
+    let converted = std::fs::read_to_string(path("converted.txt")).unwrap();
+    assert_eq!(converted, generated);
+}
+
+#[test]
+fn convert_missing_file_fails() {
+    let output_dir = TempDir::new().unwrap();
+    let input_path = format!("{}/missing.bin", output_dir.path().display());
+    let output_path = format!("{}/maze.txt", output_dir.path().display());
+
+    let mut cmd = cargo_bin_cmd!();
+    cmd.args(["convert", "--input-path", &input_path, "ascii"])
+        .args(["--output-path", &output_path])
+        .assert()
+        .failure();
+}
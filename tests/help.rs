@@ -4,6 +4,8 @@ Usage: bevy_knossos <COMMAND>
 
 Commands:
   generate  Generates a maze
+  convert   Converts a saved maze into another format
+  solve     Prints a maze with the shortest path between two cells marked
   help      Print this message or the help of the given subcommand(s)
 
 Options:
@@ -19,11 +21,12 @@ Commands:
   ascii     Save to a text file with an ASCII representation of a maze
   game-map  Save to a text file as an ASCII game map for pseudo 3D games that use ray casting for modeling and rendering the map
   image     Save to PNG or JPG file
+  binary    Save to a binary file that can be loaded back by the `convert` and `solve` commands
   help      Print this message or the help of the given subcommand(s)
 
 Options:
   -A, --algorithm <ALGORITHM>
-          Maze generation algorithm [default: recursive-backtracking] [possible values: aldous-broder, binary-tree, eller, growing-tree, hunt-and-kill, kruskal, prim, recursive-backtracking, recursive-division, sidewinder]
+          Maze generation algorithm [default: recursive-backtracking] [possible values: aldous-broder, binary-tree, eller, growing-tree, hunt-and-kill, kruskal, prim, recursive-backtracking, recursive-division, sidewinder, wilson]
   -H, --height <HEIGHT>
           Grid height in a number of cells [default: 10]
   -S, --seed <SEED>
@@ -36,6 +39,10 @@ Options:
           Bias to use for the \"Binary Tree\" algorithm [default: north-east] [possible values: north-west, north-east, south-west, south-east]
       --growing-method[=<GROWING_METHOD>]
           Growing method to use for the \"Growing Tree\" algorithm [default: newest] [possible values: newest, oldest, random, middle, newest50-random50, newest75-random25, newest25-random75]
+      --custom-algorithm <NAME>
+          Name of a registered or built-in algorithm to use instead of `--algorithm`
+      --braid <BRAID>
+          Ratio of dead ends to remove by adding loops, from 0 (a perfect maze) to 1 [default: 0]
   -h, --help
           Print help (see more with '--help')
 ";
@@ -70,6 +70,12 @@ fn build_valid_maze_with_binary_tree_algorithm() {
     assert!(maze!(BinaryTree::new(Bias::SouthEast)).unwrap().is_valid());
 }
 
+#[test]
+fn build_valid_maze_with_dungeon_algorithm() {
+    assert!(maze!(Dungeon::default()).unwrap().is_valid());
+    assert!(maze!(Dungeon::new(10, 2, 3)).unwrap().is_valid());
+}
+
 #[test]
 fn build_valid_maze_with_eller_algorithm() {
     assert!(maze!(Eller).unwrap().is_valid());
@@ -103,6 +109,27 @@ fn build_valid_maze_with_hunt_and_kill_algorithm() {
     assert!(maze!(HuntAndKill::new()).unwrap().is_valid());
 }
 
+#[test]
+fn hunt_and_kill_start_bias_changes_maze() {
+    let build = |corner| {
+        OrthogonalMazeBuilder::new()
+            .width(12)
+            .height(12)
+            .seed(42)
+            .algorithm(Box::new(HuntAndKill::new().start_bias(corner)))
+            .build()
+            .unwrap()
+    };
+
+    let north_west = build(Corner::NorthWest);
+    let south_east = build(Corner::SouthEast);
+
+    assert!(north_west.is_valid());
+    assert!(south_east.is_valid());
+    assert_ne!(north_west, south_east);
+    assert_eq!(south_east, build(Corner::SouthEast));
+}
+
 #[test]
 fn build_valid_maze_with_kruskal_algorithm() {
     assert!(maze!(Kruskal).unwrap().is_valid());
@@ -128,6 +155,76 @@ fn build_valid_maze_with_sidewinder_algorithm() {
     assert!(maze!(Sidewinder).unwrap().is_valid());
 }
 
+#[test]
+fn build_valid_maze_with_wilson_algorithm() {
+    for seed in [0, 1, 7, 42, 99] {
+        let maze = OrthogonalMazeBuilder::new()
+            .seed(seed)
+            .algorithm(Box::new(Wilson))
+            .build()
+            .unwrap();
+        assert!(maze.is_valid());
+    }
+}
+
+#[test]
+fn build_valid_masked_maze_with_wilson_algorithm() {
+    let mut mask = Mask::new(6, 6);
+    mask.set_active((2, 2), false);
+    mask.set_active((3, 2), false);
+    mask.set_active((2, 3), false);
+
+    let maze = OrthogonalMazeBuilder::new()
+        .width(6)
+        .height(6)
+        .seed(3)
+        .mask(mask)
+        .algorithm(Box::new(Wilson))
+        .build()
+        .unwrap();
+
+    assert!(maze.is_valid());
+    assert_eq!(maze.active_cell_count(), 33);
+    assert_eq!(maze[(2, 2)], Cell::empty());
+}
+
+#[test]
+fn build_weave_maze_with_crossings() {
+    for seed in [0, 1, 7, 42, 99] {
+        let maze = OrthogonalMazeBuilder::new()
+            .width(12)
+            .height(12)
+            .seed(seed)
+            .algorithm(Box::new(Weave::new().density(1.0)))
+            .build()
+            .unwrap();
+        assert!(maze.is_valid());
+
+        let crossings: Vec<bevy_knossos::Coords> = maze
+            .iter()
+            .map(|(coords, _)| coords)
+            .filter(|coords| maze.overpass(*coords).is_some())
+            .collect();
+        assert!(!crossings.is_empty());
+        for coords in &crossings {
+            assert_eq!(
+                maze[*coords],
+                Cell::NORTH | Cell::SOUTH | Cell::EAST | Cell::WEST
+            );
+        }
+
+        // A tunnel replaces the two passages through a crossing with a single one, so the maze is
+        // still a spanning tree when crossings are taken into account
+        let passages: usize = maze
+            .iter()
+            .map(|(_, cell)| cell.iter().count())
+            .sum::<usize>()
+            / 2;
+        assert_eq!(passages - crossings.len(), 12 * 12 - 1);
+        assert_eq!(maze.reachable_count((0, 0)), 12 * 12);
+    }
+}
+
 fn assert_maze_consistency(maze: &OrthogonalMaze) {
     let nodes = maze.iter().count();
     let width = maze.iter().map(|((x, _), _)| x).max().map_or(0, |x| x + 1);
@@ -154,7 +251,7 @@ fn assert_maze_consistency(maze: &OrthogonalMaze) {
 
 #[test]
 fn generated_mazes_are_consistent_across_algorithms_and_seeds() {
-    let algorithms: [(&str, fn() -> Box<dyn Algorithm>); 15] = [
+    let algorithms: [(&str, fn() -> Box<dyn Algorithm>); 17] = [
         ("AldousBroder", || Box::new(AldousBroder)),
         ("BinaryTree::NorthWest", || {
             Box::new(BinaryTree::new(Bias::NorthWest))
@@ -186,6 +283,8 @@ fn generated_mazes_are_consistent_across_algorithms_and_seeds() {
         ("Prim", || Box::new(Prim::new())),
         ("RecursiveBacktracking", || Box::new(RecursiveBacktracking)),
         ("Sidewinder", || Box::new(Sidewinder)),
+        ("Weave", || Box::new(Weave::new())),
+        ("Wilson", || Box::new(Wilson)),
     ];
     let seeds = [0_u64, 1, 7, 19, 42, 99];
     let sizes = [(2, 2), (3, 5), (8, 8), (12, 7)];
@@ -308,6 +407,21 @@ fn save_maze_as_png_returns_error() {
     assert_save_maze_error!("this is not valid path/", Image::new(), expected);
 }
 
+#[test]
+fn save_maze_as_svg() {
+    let output_dir = TempDir::new().unwrap();
+    let file_path = format!("{}/maze.svg", output_dir.path().display());
+    let expected = format!(
+        "Maze was successfully written to a file: {}",
+        to_absolute_path!(&file_path)
+    );
+    assert_save_maze!(&file_path, Svg::new(), expected);
+
+    let svg = std::fs::read_to_string(&file_path).unwrap();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
 #[test]
 fn format_maze() {
     let ascii = OrthogonalMazeBuilder::new()
@@ -334,3 +448,44 @@ fn format_maze() {
 
     assert_eq!(expected, ascii);
 }
+
+#[test]
+fn render_scale_sets_matching_formatter_sizes() {
+    let maze = OrthogonalMazeBuilder::new()
+        .width(6)
+        .height(4)
+        .seed(1)
+        .build()
+        .unwrap()
+        .with_render_scale(RenderScale {
+            wall: 4,
+            passage: 12,
+            margin: 8,
+        });
+
+    let image = maze.format(Image::new()).into_inner();
+    let svg = maze.format(Svg::new()).into_inner();
+
+    // 6 cells of 16 pixels plus the closing wall and two margins
+    assert_eq!(image.dimensions(), (116, 84));
+    assert!(svg.contains(r#"width="116" height="84""#));
+
+    // Sizes set explicitly take precedence over the scale
+    let image = maze.format(Image::new().margin(0)).into_inner();
+    assert_eq!(image.dimensions(), (100, 68));
+
+    let map = maze.format(GameMap::new()).into_inner();
+    assert_eq!(map.lines().next().unwrap().len(), 6 * 13 + 1);
+}
+
+#[test]
+fn render_scale_is_not_compared() {
+    let maze = OrthogonalMazeBuilder::new().seed(2).build().unwrap();
+    let scaled = maze.clone().with_render_scale(RenderScale {
+        wall: 1,
+        passage: 2,
+        margin: 3,
+    });
+
+    assert_eq!(scaled, maze);
+}
@@ -39,3 +39,90 @@ fn game_map_save_success() {
         .success()
         .stdout(expected);
 }
+
+#[test]
+fn custom_algorithm_save_success() {
+    let output_dir = TempDir::new().unwrap();
+    let file_path = format!("{}/maze.txt", output_dir.path().display());
+    let expected = format!("Maze was successfully written to a file: {file_path}\n");
+
+    let mut cmd = cargo_bin_cmd!();
+    cmd.args(["generate", "--custom-algorithm", "Sidewinder", "ascii"])
+        .args(["--output-path", &file_path])
+        .assert()
+        .success()
+        .stdout(expected);
+}
+
+#[test]
+fn unknown_custom_algorithm_fails() {
+    let output_dir = TempDir::new().unwrap();
+    let file_path = format!("{}/maze.txt", output_dir.path().display());
+
+    let mut cmd = cargo_bin_cmd!();
+    cmd.args(["generate", "--custom-algorithm", "Unregistered", "ascii"])
+        .args(["--output-path", &file_path])
+        .assert()
+        .failure();
+}
+
+/// Counts the cells with three walls in a maze rendered by the broad ASCII formatter
+fn count_dead_ends(ascii: &str) -> usize {
+    let lines: Vec<&[u8]> = ascii.lines().map(str::as_bytes).collect();
+    let height = lines.len() / 2;
+    let width = lines[0].len() / 4;
+
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            let walls = [
+                lines[2 * y][4 * x + 1] == b'-',
+                lines[2 * y + 2][4 * x + 1] == b'-',
+                lines[2 * y + 1][4 * x] == b'|',
+                lines[2 * y + 1][4 * x + 4] == b'|',
+            ];
+            walls.iter().filter(|wall| **wall).count() == 3
+        })
+        .count()
+}
+
+#[test]
+fn braid_removes_all_dead_ends() {
+    let output_dir = TempDir::new().unwrap();
+    let file_path = format!("{}/maze.txt", output_dir.path().display());
+
+    let mut cmd = cargo_bin_cmd!();
+    cmd.args(["generate", "--seed", "7", "ascii", "--output-type=broad"])
+        .args(["--output-path", &file_path])
+        .assert()
+        .success();
+    let perfect = std::fs::read_to_string(&file_path).unwrap();
+
+    let mut cmd = cargo_bin_cmd!();
+    cmd.args(["generate", "--seed", "7", "--braid", "1.0", "ascii"])
+        .args(["--output-type=broad", "--output-path", &file_path])
+        .assert()
+        .success();
+    let braided = std::fs::read_to_string(&file_path).unwrap();
+
+    assert!(count_dead_ends(&perfect) > 0);
+    assert_eq!(count_dead_ends(&braided), 0);
+}
+
+#[test]
+fn very_tall_eller_maze_save_success() {
+    let output_dir = TempDir::new().unwrap();
+    let file_path = format!("{}/maze.txt", output_dir.path().display());
+    let expected = format!("Maze was successfully written to a file: {file_path}\n");
+
+    let mut cmd = cargo_bin_cmd!();
+    cmd.args(["generate", "--algorithm", "eller", "--height", "100000"])
+        .args(["--width", "8", "ascii", "--output-path", &file_path])
+        .assert()
+        .success()
+        .stdout(expected);
+
+    let maze = std::fs::read_to_string(&file_path).unwrap();
+    assert_eq!(maze.lines().count(), 100_001);
+    assert!(maze.lines().skip(1).all(|line| line.len() == 17));
+}
@@ -0,0 +1,95 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_fs::fixture::TempDir;
+
+#[test]
+fn solve_success() {
+    let expected = "+---+---+---+
+| *   *   * |
++---+---+   +
+|         * |
++---+---+---+
+
+";
+
+    let mut cmd = cargo_bin_cmd!();
+    cmd.args([
+        "solve",
+        "-A",
+        "binary-tree",
+        "-W",
+        "3",
+        "-H",
+        "2",
+        "-S",
+        "1",
+    ])
+    .assert()
+    .success()
+    .stdout(expected);
+}
+
+#[test]
+fn solve_with_bias() {
+    let expected = "+---+---+---+
+| *         |
++   +---+---+
+| *   *   * |
++---+---+---+
+
+";
+
+    let mut cmd = cargo_bin_cmd!();
+    cmd.args([
+        "solve",
+        "-A",
+        "binary-tree",
+        "--bias=south-west",
+        "-W",
+        "3",
+        "-H",
+        "2",
+        "-S",
+        "1",
+    ])
+    .assert()
+    .success()
+    .stdout(expected);
+}
+
+#[test]
+fn solve_goal_out_of_bounds_fails() {
+    let mut cmd = cargo_bin_cmd!();
+    let assert = cmd
+        .args(["solve", "-W", "3", "-H", "2", "--goal", "(5,5)"])
+        .assert()
+        .failure();
+
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr);
+    assert!(stderr.contains("Goal (5,5) can't be reached from (0,0)"));
+}
+
+#[test]
+fn solve_unreachable_goal_fails() {
+    // Two corridors, one per row, with no passage between them
+    let input_dir = TempDir::new().unwrap();
+    let input_path = format!("{}/maze.csv", input_dir.path().display());
+    std::fs::write(
+        &input_path,
+        "x,y,north,south,east,west
+0,0,1,1,0,1
+1,0,1,1,1,0
+0,1,1,1,0,1
+1,1,1,1,1,0
+",
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!();
+    let assert = cmd
+        .args(["solve", "--input-path", &input_path])
+        .assert()
+        .failure();
+
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr);
+    assert!(stderr.contains("Goal (1,1) can't be reached from (0,0)"));
+}